@@ -0,0 +1,160 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Bulk Generation Throughput
+//!
+//! Run with `cargo bench --bench throughput`, optionally followed by `-- <names>` to change how
+//! many names each case generates.
+//!
+//! The rejection sampling case draws tokens from the whole table and retries those that cannot
+//! be placed, as generation did before tables were split into per-position pools, so the two
+//! `2..4 tokens` cases show what the pools save.
+
+use nominae::{Table, Token, Totro, TotroConfig, AIW, BOW, EOW, MOW};
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use std::hint::black_box;
+use std::time::Instant;
+
+const DEFAULT_NAMES: usize = 10_000_000;
+
+fn measure<F: FnMut()>(label: &str, names: usize, mut generate: F) {
+    let start = Instant::now();
+    for _ in 0..names {
+        generate();
+    }
+    let seconds = start.elapsed().as_secs_f64();
+    println!(
+        "{:<32} {:>8.2}M names/sec ({:.2} ns/name)",
+        label,
+        names as f64 / seconds / 1e6,
+        seconds * 1e9 / names as f64
+    );
+}
+
+/// Draw a token from the whole of `table` by weight, retrying until one fits `position`.
+fn reject<'a, T: Rng>(table: &'a Table, position: u8, rng: &mut T) -> &'a Token {
+    let total: u64 = table
+        .tokens()
+        .iter()
+        .map(|token| token.weight() as u64)
+        .sum();
+    loop {
+        let mut roll = rng.gen_range(0..total);
+        let token = table
+            .tokens()
+            .iter()
+            .find(|token| match roll.checked_sub(token.weight() as u64) {
+                Some(rest) => {
+                    roll = rest;
+                    false
+                }
+                None => true,
+            })
+            .expect("the roll is below the total weight");
+        if token.allows(position) {
+            return token;
+        }
+    }
+}
+
+/// Generate a name of `min..max` tokens into `buffer` by rejection sampling.
+fn generate_rejecting<T: Rng>(
+    config: &TotroConfig,
+    min: u8,
+    max: u8,
+    buffer: &mut String,
+    rng: &mut T,
+) {
+    buffer.clear();
+    let length = rng.gen_range(min..max);
+    let mut vowel = rng.gen_bool(0.5);
+    for idx in 0..length {
+        let position = match idx {
+            0 => BOW,
+            idx if idx + 1 == length => EOW,
+            _ => MOW,
+        };
+        let table = if vowel {
+            config.vowels()
+        } else {
+            config.consonants()
+        };
+        buffer.push_str(reject(table, position, rng).text());
+        vowel = !vowel;
+    }
+    if let Some(first) = buffer.get_mut(0..1) {
+        first.make_ascii_uppercase();
+    }
+}
+
+/// Table of `n` made-up tokens with Zipf-like weights, like a table trained from a corpus.
+fn skewed(n: u32, letters: &[char]) -> Table {
+    let tokens = (0..n)
+        .map(|idx| {
+            let mut text = String::new();
+            let mut rest = idx as usize;
+            loop {
+                text.push(letters[rest % letters.len()]);
+                rest /= letters.len();
+                if rest == 0 {
+                    break;
+                }
+            }
+            Token::owned(text, AIW, 100_000 / (idx + 1))
+        })
+        .collect();
+    Table::new(tokens).expect("made-up tokens are distinct")
+}
+
+fn main() {
+    let names = std::env::args()
+        .skip(1)
+        .find_map(|arg| arg.parse().ok())
+        .unwrap_or(DEFAULT_NAMES);
+    let config = TotroConfig::default().with_length(2, 4);
+    let mut rng = SmallRng::seed_from_u64(0);
+    let mut buffer = String::with_capacity(32);
+
+    measure("generate_into (2..4 tokens)", names, || {
+        config.generate_into(&mut buffer, &mut rng);
+        black_box(&buffer);
+    });
+    measure("rejection sampling (2..4 tokens)", names, || {
+        generate_rejecting(&config, 2, 4, &mut buffer, &mut rng);
+        black_box(&buffer);
+    });
+    measure("generate (2..4 tokens)", names, || {
+        black_box(config.generate(&mut rng));
+    });
+    measure("Totro::generate (2..5 tokens)", names, || {
+        black_box(Totro::generate(2, 5, &mut rng));
+    });
+    let large = TotroConfig::new(
+        skewed(5_000, &['a', 'e', 'i', 'o', 'u']),
+        skewed(5_000, &['b', 'd', 'k', 'l', 'm', 'r', 's', 't', 'v']),
+    )
+    .with_length(2, 4);
+    measure("generate_into, 5000-token tables", names, || {
+        large.generate_into(&mut buffer, &mut rng);
+        black_box(&buffer);
+    });
+    let distinct = config.clone().with_distinct_adjacent(true);
+    measure("generate_into, distinct adjacent", names, || {
+        distinct.generate_into(&mut buffer, &mut rng);
+        black_box(&buffer);
+    });
+}
@@ -0,0 +1,195 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Disease and Curse Name Generation
+
+use crate::morphology::genitive;
+use crate::totro::TotroConfig;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::fmt;
+
+/// Disease symptoms as (clinical faux-Latin, folk) pairs.
+const DISEASES: [(&str, &str); 8] = [
+    ("morbus", "sickness"),
+    ("tremor", "shakes"),
+    ("febris", "fever"),
+    ("tabes", "wasting"),
+    ("pestis", "plague"),
+    ("putredo", "rot"),
+    ("tussis", "cough"),
+    ("somnus", "sleep"),
+];
+
+/// Curse effects as (clinical faux-Latin, folk) pairs.
+const CURSES: [(&str, &str); 6] = [
+    ("maledictio", "curse"),
+    ("fascinum", "hex"),
+    ("vinculum", "binding"),
+    ("languor", "blight"),
+    ("umbra", "shadow"),
+    ("silentium", "hush"),
+];
+
+/// Qualifiers as (faux-Latin genitive, folk adjective) pairs.
+const QUALIFIERS: [(&str, &str); 10] = [
+    ("cineris", "grey"),
+    ("sanguinis", "red"),
+    ("noctis", "night"),
+    ("lunae", "moon"),
+    ("ossium", "bone"),
+    ("aquae", "drowning"),
+    ("frigoris", "cold"),
+    ("vermium", "crawling"),
+    ("oculorum", "weeping"),
+    ("ignis", "burning"),
+];
+
+/// Whether an affliction is a natural disease or a curse.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AfflictionKind {
+    /// A natural disease, such as "morbus cineris" or "the grey sickness".
+    Disease,
+    /// A magical curse, such as "maledictio noctis" or "the night curse".
+    Curse,
+}
+
+/// A generated affliction, named in both a clinical and a folk register.
+///
+/// Both names describe the same affliction: "morbus cineris" is what physicians write and
+/// "the grey sickness" is what villagers say. Displays as the folk name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Affliction {
+    /// Disease or curse.
+    pub kind: AfflictionKind,
+    /// Clinical faux-Latin name, such as "morbus cineris" or "tremor velmaris".
+    pub clinical: String,
+    /// Folk name, such as "the grey shakes" or "Velmar's shakes".
+    pub folk: String,
+    /// Person the affliction is named after, if any.
+    pub eponym: Option<String>,
+}
+
+impl fmt::Display for Affliction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.folk)
+    }
+}
+
+/// Generator for [`Affliction`]s with linked clinical and folk names.
+///
+/// ```rust
+/// use nominae::{AfflictionGenerator, AfflictionKind};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let affliction = AfflictionGenerator::default().generate(AfflictionKind::Disease, &mut rng);
+/// println!("{} ({})", affliction.folk, affliction.clinical);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct AfflictionGenerator {
+    names: TotroConfig,
+    eponym: f64,
+}
+
+impl Default for AfflictionGenerator {
+    fn default() -> AfflictionGenerator {
+        AfflictionGenerator {
+            names: TotroConfig::alphabetic().with_length(2, 4),
+            eponym: 0.25,
+        }
+    }
+}
+
+impl AfflictionGenerator {
+    /// Set the configuration used to generate eponyms.
+    pub fn with_names(mut self, names: TotroConfig) -> AfflictionGenerator {
+        self.names = names;
+        self
+    }
+
+    /// Set the probability that an affliction is named after a person.
+    pub fn with_eponym(mut self, probability: f64) -> AfflictionGenerator {
+        assert!(
+            (0.0..=1.0).contains(&probability),
+            "eponym probability must be within 0..=1"
+        );
+        self.eponym = probability;
+        self
+    }
+
+    /// Generate an affliction of the given kind.
+    pub fn generate<T: Rng + ?Sized>(&self, kind: AfflictionKind, rng: &mut T) -> Affliction {
+        let symptoms: &[(&str, &str)] = match kind {
+            AfflictionKind::Disease => &DISEASES,
+            AfflictionKind::Curse => &CURSES,
+        };
+        let (latin, folk) = symptoms.choose(rng).unwrap();
+        if rng.gen_bool(self.eponym) {
+            let eponym = self.names.generate(rng);
+            return Affliction {
+                kind,
+                clinical: format!("{} {}", latin, genitive(&eponym.to_lowercase())),
+                folk: format!("{}'s {}", eponym, folk),
+                eponym: Some(eponym),
+            };
+        }
+        let (genitive, adjective) = QUALIFIERS.choose(rng).unwrap();
+        Affliction {
+            kind,
+            clinical: format!("{} {}", latin, genitive),
+            folk: format!("the {} {}", adjective, folk),
+            eponym: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AfflictionGenerator, AfflictionKind, CURSES, DISEASES, QUALIFIERS};
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_linked_registers() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let generator = AfflictionGenerator::default().with_eponym(0.0);
+        for _ in 0..50 {
+            let affliction = generator.generate(AfflictionKind::Disease, &mut rng);
+            let linked = DISEASES.iter().any(|(latin, folk)| {
+                affliction.clinical.starts_with(latin) && affliction.folk.ends_with(folk)
+            }) && QUALIFIERS.iter().any(|(genitive, adjective)| {
+                affliction.clinical.ends_with(genitive)
+                    && affliction.folk.starts_with(&format!("the {} ", adjective))
+            });
+            assert!(linked, "{} / {}", affliction.clinical, affliction.folk);
+        }
+    }
+
+    #[test]
+    fn test_eponym() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let generator = AfflictionGenerator::default().with_eponym(1.0);
+        let curse = generator.generate(AfflictionKind::Curse, &mut rng);
+        let eponym = curse.eponym.clone().unwrap();
+        assert!(curse.folk.starts_with(&format!("{}'s ", eponym)));
+        assert_eq!(curse.clinical, curse.clinical.to_lowercase());
+        assert!(CURSES
+            .iter()
+            .any(|(latin, _)| curse.clinical.starts_with(latin)));
+    }
+}
@@ -0,0 +1,255 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Batch Generation
+
+use crate::explain::Explanation;
+use crate::stable::below;
+use crate::totro::TotroConfig;
+use rand::Rng;
+use std::collections::BTreeMap;
+
+/// Candidates drawn for one name of a batch before the limits are set aside for it.
+const BATCH_ATTEMPTS: usize = 1000;
+
+/// Limits on how often a token may recur across one batch of names, and on the mix of name
+/// lengths in the batch.
+///
+/// Shares are fractions of the batch size: an initial share of 0.1 lets no opening token start
+/// more than a tenth of the names, so a town roster of 50 does not have a dozen names that start
+/// with "Ka". Every token may be used at least once whatever the share.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatchLimits {
+    initial_share: f64,
+    lengths: Vec<(u8, u32)>,
+}
+
+impl Default for BatchLimits {
+    fn default() -> BatchLimits {
+        BatchLimits {
+            initial_share: 1.0,
+            lengths: Vec::new(),
+        }
+    }
+}
+
+impl BatchLimits {
+    /// Set the largest fraction of a batch that may start with the same token.
+    pub fn with_initial_share(mut self, share: f64) -> BatchLimits {
+        assert!(
+            (0.0..=1.0).contains(&share),
+            "initial share must be within 0..=1"
+        );
+        self.initial_share = share;
+        self
+    }
+
+    /// The largest fraction of a batch that may start with the same token.
+    pub fn initial_share(&self) -> f64 {
+        self.initial_share
+    }
+
+    /// Set a histogram of name lengths as `(length, weight)` pairs, such as `[(2, 20), (3, 50),
+    /// (4, 30)]` for 20% two-syllable, 50% three-syllable and 30% four-syllable names.
+    ///
+    /// Batches match the histogram exactly rather than sampling each name's length
+    /// independently, with counts rounded to whole names by largest remainder. Lengths are in
+    /// the units of the configuration's [`LengthModel`](crate::LengthModel) and override its
+    /// length range.
+    pub fn with_lengths(mut self, histogram: &[(u8, u32)]) -> BatchLimits {
+        assert!(
+            histogram.iter().all(|&(length, _)| length > 0),
+            "lengths must be at least 1"
+        );
+        assert!(
+            histogram.iter().any(|&(_, weight)| weight > 0),
+            "length histogram must have a positive weight"
+        );
+        self.lengths = histogram.to_vec();
+        self
+    }
+
+    /// Histogram of name lengths as `(length, weight)` pairs, empty unless one was set.
+    pub fn lengths(&self) -> &[(u8, u32)] {
+        &self.lengths
+    }
+
+    /// Lengths of the names in a batch of `n`, in a random order, or `None` without a
+    /// histogram.
+    fn draw_lengths<T: Rng + ?Sized>(&self, n: usize, rng: &mut T) -> Option<Vec<u8>> {
+        if self.lengths.is_empty() {
+            return None;
+        }
+        let total: u64 = self.lengths.iter().map(|&(_, weight)| weight as u64).sum();
+        let shares: Vec<(u64, u64)> = self
+            .lengths
+            .iter()
+            .map(|&(_, weight)| {
+                let exact = n as u64 * weight as u64;
+                (exact / total, exact % total)
+            })
+            .collect();
+        let mut counts: Vec<u64> = shares.iter().map(|&(count, _)| count).collect();
+        let mut remaining = n as u64 - counts.iter().sum::<u64>();
+        let mut order: Vec<usize> = (0..shares.len()).collect();
+        order.sort_by_key(|&idx| std::cmp::Reverse(shares[idx].1));
+        for idx in order {
+            if remaining == 0 {
+                break;
+            }
+            counts[idx] += 1;
+            remaining -= 1;
+        }
+        let mut lengths: Vec<u8> = self
+            .lengths
+            .iter()
+            .zip(counts)
+            .flat_map(|(&(length, _), count)| std::iter::repeat_n(length, count as usize))
+            .collect();
+        for idx in (1..lengths.len()).rev() {
+            let other = below(rng, idx as u64 + 1) as usize;
+            lengths.swap(idx, other);
+        }
+        Some(lengths)
+    }
+
+    /// Number of names in a batch of `n` that may start with the same token.
+    fn initial_cap(&self, n: usize) -> usize {
+        ((self.initial_share * n as f64) as usize).max(1)
+    }
+}
+
+impl TotroConfig {
+    /// Generate `n` names that together stay within `limits`.
+    ///
+    /// With a [length histogram](BatchLimits::with_lengths) set, every name's length is drawn
+    /// from the batch's exact share of lengths before its tokens are.
+    ///
+    /// Token usage is counted as the batch is generated, and candidates that would exceed a
+    /// limit are drawn again. When the tables cannot satisfy the limits, such as a share of 0.1
+    /// with only five opening tokens, a name is kept anyway after 1000 rejected candidates.
+    ///
+    /// ```rust
+    /// use nominae::{BatchLimits, TotroConfig};
+    /// use rand::SeedableRng;
+    /// use rand::rngs::SmallRng;
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    /// let limits = BatchLimits::default().with_initial_share(0.1);
+    /// for name in TotroConfig::default().generate_many(50, &limits, &mut rng) {
+    ///     println!("{}", name);
+    /// }
+    /// ```
+    pub fn generate_many<T: Rng + ?Sized>(
+        &self,
+        n: usize,
+        limits: &BatchLimits,
+        rng: &mut T,
+    ) -> Vec<String> {
+        self.explain_many(n, limits, rng)
+            .into_iter()
+            .map(Explanation::into_name)
+            .collect()
+    }
+
+    /// Generate `n` explained names that together stay within `limits`.
+    pub(crate) fn explain_many<T: Rng + ?Sized>(
+        &self,
+        n: usize,
+        limits: &BatchLimits,
+        rng: &mut T,
+    ) -> Vec<Explanation<'_>> {
+        let cap = limits.initial_cap(n);
+        let lengths = limits.draw_lengths(n, rng);
+        let (min, max) = self.length();
+        let mut initials: BTreeMap<&str, usize> = BTreeMap::new();
+        let mut batch = Vec::with_capacity(n);
+        for idx in 0..n {
+            let (min, max) = match &lengths {
+                Some(lengths) => (lengths[idx], lengths[idx]),
+                None => (min, max),
+            };
+            let mut attempts = 0;
+            let explanation = loop {
+                let explanation = self.explain_within(min, max, rng);
+                attempts += 1;
+                let initial = explanation.tokens().first().map(|token| token.text());
+                let used = initial.and_then(|initial| initials.get(initial).copied());
+                if used.unwrap_or(0) < cap || attempts == BATCH_ATTEMPTS {
+                    break explanation;
+                }
+                event!(
+                    trace,
+                    name = explanation.name(),
+                    "initial token over batch limit"
+                );
+            };
+            if let Some(initial) = explanation.tokens().first() {
+                *initials.entry(initial.text()).or_default() += 1;
+            }
+            batch.push(explanation);
+        }
+        batch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BatchLimits;
+    use crate::explain::Explanation;
+    use crate::totro::TotroConfig;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_initial_share() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let config = TotroConfig::default();
+        let opening = |explanations: &[Explanation<'_>]| {
+            let mut counts = BTreeMap::new();
+            for explanation in explanations {
+                *counts.entry(explanation.tokens()[0].text()).or_insert(0) += 1;
+            }
+            counts.values().copied().max().unwrap()
+        };
+        let free = config.explain_many(200, &BatchLimits::default(), &mut rng);
+        assert!(opening(&free) > 10);
+        let limits = BatchLimits::default().with_initial_share(0.05);
+        let capped = config.explain_many(200, &limits, &mut rng);
+        assert_eq!(capped.len(), 200);
+        assert!(opening(&capped) <= 10);
+        assert_eq!(config.generate_many(30, &limits, &mut rng).len(), 30);
+    }
+
+    #[test]
+    fn test_lengths() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let config = TotroConfig::default();
+        let limits = BatchLimits::default().with_lengths(&[(2, 20), (3, 50), (4, 30), (9, 0)]);
+        let batch = config.explain_many(99, &limits, &mut rng);
+        let mut counts = BTreeMap::new();
+        for explanation in &batch {
+            *counts.entry(explanation.tokens().len()).or_insert(0) += 1;
+        }
+        assert_eq!(
+            counts.into_iter().collect::<Vec<_>>(),
+            vec![(2, 20), (3, 49), (4, 30)]
+        );
+        let lengths: Vec<usize> = batch.iter().map(|e| e.tokens().len()).collect();
+        assert_ne!(lengths[..20], [2; 20]);
+    }
+}
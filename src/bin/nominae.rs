@@ -0,0 +1,338 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Nominae Command Line Interface
+
+use nominae::presets::{self, Theme};
+use nominae::{LengthModel, Mode, PersonaGenerator, StableRng, Syllabic, TotroConfig};
+use rand::SeedableRng;
+use std::io::{self, BufWriter, Write};
+use std::process;
+
+const USAGE: &str = "\
+Usage: nominae <command> [options]
+
+Commands:
+  names                   Print generated names, one per line
+  personas                Print a CSV dataset of test personas
+  compare <preset>...     Print columns of names from two or more presets, each
+                          generated from the same seed
+  help                    Print this message
+
+Presets:
+  default, alphabetic, characters, syllabic, pet, elvish, dwarvish, orcish,
+  draconic
+
+Options:
+  --count <n>   Number of names or persona rows (default 10)
+  --seed <n>    Seed for reproducible output (default 0)
+";
+
+/// Names accepted by [`preset`], in the order listed in the usage text.
+const PRESETS: [&str; 9] = [
+    "default",
+    "alphabetic",
+    "characters",
+    "syllabic",
+    "pet",
+    "elvish",
+    "dwarvish",
+    "orcish",
+    "draconic",
+];
+
+/// Options shared by every command.
+struct Options {
+    count: usize,
+    seed: u64,
+    /// Positional arguments, only accepted by `compare`.
+    presets: Vec<String>,
+}
+
+fn parse(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        count: 10,
+        seed: 0,
+        presets: Vec::new(),
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let value = match arg.as_str() {
+            "--count" | "--seed" => args
+                .next()
+                .ok_or_else(|| format!("missing value for {}", arg))?,
+            _ if !arg.starts_with("--") => {
+                options.presets.push(arg.clone());
+                continue;
+            }
+            _ => return Err(format!("unknown option {}", arg)),
+        };
+        let value: u64 = value
+            .parse()
+            .map_err(|_| format!("invalid value {} for {}", value, arg))?;
+        match arg.as_str() {
+            "--count" => options.count = value as usize,
+            _ => options.seed = value,
+        }
+    }
+    Ok(options)
+}
+
+/// Configuration for the preset called `name`.
+fn preset(name: &str) -> Result<TotroConfig, String> {
+    match name {
+        "default" => Ok(TotroConfig::default()),
+        "alphabetic" => Ok(TotroConfig::alphabetic()),
+        "characters" => Ok(TotroConfig::default()
+            .with_length(4, 9)
+            .with_length_model(LengthModel::Characters)),
+        "syllabic" => Ok(TotroConfig::alphabetic().with_mode(Mode::Syllabic(Syllabic::default()))),
+        "pet" => Ok(presets::pet()),
+        _ => match Theme::ALL.iter().find(|theme| theme.to_string() == name) {
+            Some(theme) => Ok(theme.config()),
+            None => Err(format!(
+                "unknown preset {}, expected one of {}",
+                name,
+                PRESETS.join(", ")
+            )),
+        },
+    }
+}
+
+/// Write `columns` of names side by side under their headers, padded to a common width.
+fn write_columns<W: Write>(
+    out: &mut W,
+    headers: &[String],
+    columns: &[Vec<String>],
+) -> io::Result<()> {
+    let widths: Vec<usize> = headers
+        .iter()
+        .zip(columns)
+        .map(|(header, column)| {
+            column
+                .iter()
+                .chain(Some(header))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let rows = columns.iter().map(Vec::len).max().unwrap_or(0);
+    let underline: Vec<String> = widths.iter().map(|&width| "-".repeat(width)).collect();
+    let mut write_row = |cells: Vec<&str>| {
+        let mut line = String::new();
+        for (idx, (cell, width)) in cells.iter().zip(&widths).enumerate() {
+            if idx > 0 {
+                line.push_str("  ");
+            }
+            line.push_str(cell);
+            if idx + 1 < cells.len() {
+                line.extend(std::iter::repeat_n(' ', width - cell.chars().count()));
+            }
+        }
+        // Shorter columns leave empty cells at the end of a row, so drop their padding.
+        writeln!(out, "{}", line.trim_end())
+    };
+    write_row(headers.iter().map(String::as_str).collect())?;
+    write_row(underline.iter().map(String::as_str).collect())?;
+    for row in 0..rows {
+        write_row(
+            columns
+                .iter()
+                .map(|column| column.get(row).map_or("", String::as_str))
+                .collect(),
+        )?;
+    }
+    Ok(())
+}
+
+fn run<W: Write>(command: &str, options: &Options, out: &mut W) -> io::Result<()> {
+    match command {
+        "names" => {
+            let mut rng = StableRng::seed_from_u64(options.seed);
+            let config = TotroConfig::default();
+            for _ in 0..options.count {
+                writeln!(out, "{}", config.generate(&mut rng))?;
+            }
+        }
+        "personas" => PersonaGenerator::default()
+            .with_seed(options.seed)
+            .write_csv(options.count, &mut *out)?,
+        "compare" => {
+            let columns: Vec<Vec<String>> = options
+                .presets
+                .iter()
+                .map(|name| {
+                    let config = preset(name).expect("presets are checked before running");
+                    let mut rng = StableRng::seed_from_u64(options.seed);
+                    (0..options.count)
+                        .map(|_| config.generate(&mut rng))
+                        .collect()
+                })
+                .collect();
+            write_columns(out, &options.presets, &columns)?
+        }
+        _ => unreachable!("commands are checked before running"),
+    }
+    out.flush()
+}
+
+/// Check that positional arguments suit `command`.
+fn check(command: &str, options: Options) -> Result<Options, String> {
+    if command != "compare" {
+        return match options.presets.first() {
+            Some(arg) => Err(format!("unexpected argument {}", arg)),
+            None => Ok(options),
+        };
+    }
+    if options.presets.len() < 2 {
+        return Err("compare needs at least two presets".to_owned());
+    }
+    for name in &options.presets {
+        preset(name)?;
+    }
+    Ok(options)
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = args.first().map(String::as_str).unwrap_or("help");
+    match command {
+        "names" | "personas" | "compare" => {}
+        "help" | "--help" | "-h" => {
+            print!("{}", USAGE);
+            return;
+        }
+        _ => {
+            eprint!("unknown command {}\n\n{}", command, USAGE);
+            process::exit(2);
+        }
+    }
+    let options = match parse(&args[1..]).and_then(|options| check(command, options)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprint!("{}\n\n{}", message, USAGE);
+            process::exit(2);
+        }
+    };
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    if let Err(error) = run(command, &options, &mut out) {
+        if error.kind() != io::ErrorKind::BrokenPipe {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check, parse, preset, run, write_columns, PRESETS};
+    use nominae::presets::Theme;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn output(command: &str, arguments: &[&str]) -> String {
+        let options = parse(&args(arguments))
+            .and_then(|options| check(command, options))
+            .unwrap();
+        let mut out = Vec::new();
+        run(command, &options, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let options = parse(&[]).unwrap();
+        assert_eq!((options.count, options.seed), (10, 0));
+        let options = parse(&args(&["--seed", "7", "elvish", "--count", "3", "pet"])).unwrap();
+        assert_eq!((options.count, options.seed), (3, 7));
+        assert_eq!(options.presets, vec!["elvish", "pet"]);
+        assert_eq!(
+            parse(&args(&["--count"])).err().unwrap(),
+            "missing value for --count"
+        );
+        assert_eq!(
+            parse(&args(&["--seed", "x"])).err().unwrap(),
+            "invalid value x for --seed"
+        );
+        assert_eq!(
+            parse(&args(&["--verbose"])).err().unwrap(),
+            "unknown option --verbose"
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let checked = |command, arguments: &[&str]| {
+            parse(&args(arguments)).and_then(|options| check(command, options))
+        };
+        assert!(checked("names", &["--count", "2"]).is_ok());
+        assert_eq!(
+            checked("names", &["pet"]).err().unwrap(),
+            "unexpected argument pet"
+        );
+        assert_eq!(
+            checked("compare", &["pet"]).err().unwrap(),
+            "compare needs at least two presets"
+        );
+        assert!(checked("compare", &["pet", "gnomish"])
+            .err()
+            .unwrap()
+            .starts_with("unknown preset gnomish"));
+    }
+
+    #[test]
+    fn test_presets() {
+        for name in PRESETS.iter() {
+            assert!(preset(name).is_ok(), "{}", name);
+        }
+        for theme in Theme::ALL.iter() {
+            assert!(PRESETS.contains(&theme.to_string().as_str()), "{}", theme);
+        }
+    }
+
+    #[test]
+    fn test_write_columns() {
+        let headers = args(&["a", "longer"]);
+        let columns = vec![args(&["Velmar", "Os"]), args(&["Ka"])];
+        let mut out = Vec::new();
+        write_columns(&mut out, &headers, &columns).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "a       longer\n------  ------\nVelmar  Ka\nOs\n"
+        );
+    }
+
+    #[test]
+    fn test_output() {
+        let names = output("names", &["--count", "4", "--seed", "9"]);
+        assert_eq!(names, output("names", &["--count", "4", "--seed", "9"]));
+        assert_eq!(names.lines().count(), 4);
+        let compare = output(
+            "compare",
+            &["--count", "4", "--seed", "9", "default", "elvish"],
+        );
+        let lines: Vec<&str> = compare.lines().collect();
+        assert_eq!(lines.len(), 6);
+        // Each column is generated from the same seed as `names`.
+        for (row, name) in lines[2..].iter().zip(names.lines()) {
+            assert!(row.starts_with(name), "{} {}", row, name);
+        }
+    }
+}
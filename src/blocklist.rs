@@ -0,0 +1,29 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Built-in Blocklist
+//!
+//! Common English profanity, slurs and crude terms that randomly assembled names can spell by
+//! accident. Entries are already [`normalize`](crate::normalize)d. Matching is by substring, so
+//! a few harmless names are turned away with them; generation simply draws another.
+
+/// Words no generated name should contain.
+pub(crate) const WORDS: [&str; 40] = [
+    "anal", "anus", "arse", "bastard", "bitch", "bollock", "boner", "boob", "buttplug", "chink",
+    "clit", "cock", "coon", "crap", "cum", "cunt", "dick", "dildo", "dyke", "fag", "fuck", "gook",
+    "homo", "jizz", "kike", "nazi", "negro", "nigg", "penis", "piss", "poop", "porn", "pussy",
+    "rape", "retard", "shit", "slut", "spic", "twat", "whore",
+];
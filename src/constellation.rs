@@ -0,0 +1,214 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Constellation and Star Naming
+
+use crate::morphology::{attach, genitive};
+use crate::totro::TotroConfig;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::fmt;
+
+/// Names generated per constellation before the sky gives up on finding a new one.
+const NAME_ATTEMPTS: usize = 1000;
+
+const ENDINGS: [&str; 4] = ["a", "us", "um", "is"];
+
+const EPITHETS: [&str; 16] = [
+    "the Hunter",
+    "the Weeping Mother",
+    "the Drowned King",
+    "the Serpent",
+    "the Lantern",
+    "the Broken Crown",
+    "the Wanderer",
+    "the Twin Hounds",
+    "the Ferryman",
+    "the Harp",
+    "the Sleeping Giant",
+    "the Spear",
+    "the Widow",
+    "the Stag",
+    "the Scales",
+    "the Forge",
+];
+
+const GREEK: [&str; 24] = [
+    "Alpha", "Beta", "Gamma", "Delta", "Epsilon", "Zeta", "Eta", "Theta", "Iota", "Kappa",
+    "Lambda", "Mu", "Nu", "Xi", "Omicron", "Pi", "Rho", "Sigma", "Tau", "Upsilon", "Phi", "Chi",
+    "Psi", "Omega",
+];
+
+/// A star within a [`Constellation`], designated by brightness.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Star {
+    /// Greek letter ranking the star within its constellation, such as "Beta".
+    pub letter: String,
+    /// Full designation, such as "Beta Velmarae".
+    pub designation: String,
+}
+
+/// A generated constellation and its stars.
+///
+/// Displays as "Velmara, the Hunter".
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Constellation {
+    /// Constellation name, such as "Velmara".
+    pub name: String,
+    /// Genitive form of the name used in star designations, such as "Velmarae".
+    pub genitive: String,
+    /// Mythic epithet, such as "the Hunter".
+    pub epithet: String,
+    /// Stars from brightest to faintest.
+    pub stars: Vec<Star>,
+}
+
+impl fmt::Display for Constellation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {}", self.name, self.epithet)
+    }
+}
+
+/// Generator for a consistent sky of [`Constellation`]s.
+///
+/// Within one sky every constellation has a distinct name and epithet, and each star is
+/// designated by a Greek letter and the genitive of its constellation, as in "Alpha Velmarae".
+///
+/// ```rust
+/// use nominae::SkyGenerator;
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// for constellation in SkyGenerator::default().generate(4, &mut rng) {
+///     println!("{}: {}", constellation, constellation.stars[0].designation);
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SkyGenerator {
+    names: TotroConfig,
+    min_stars: usize,
+    max_stars: usize,
+}
+
+impl Default for SkyGenerator {
+    fn default() -> SkyGenerator {
+        SkyGenerator {
+            names: TotroConfig::alphabetic().with_length(2, 4),
+            min_stars: 3,
+            max_stars: 8,
+        }
+    }
+}
+
+impl SkyGenerator {
+    /// Set the configuration used to generate constellation names.
+    pub fn with_names(mut self, names: TotroConfig) -> SkyGenerator {
+        self.names = names;
+        self
+    }
+
+    /// Set the inclusive range of stars designated in each constellation.
+    pub fn with_stars(mut self, min: usize, max: usize) -> SkyGenerator {
+        assert!(min <= max, "min stars must not exceed max stars");
+        assert!(max <= GREEK.len(), "at most 24 stars can be designated");
+        self.min_stars = min;
+        self.max_stars = max;
+        self
+    }
+
+    /// Generate up to `n` constellations with distinct names and epithets.
+    ///
+    /// Fewer are returned when `n` exceeds the number of available epithets, or when the name
+    /// configuration runs out of new names.
+    pub fn generate<T: Rng + ?Sized>(&self, n: usize, rng: &mut T) -> Vec<Constellation> {
+        let epithets: Vec<_> = EPITHETS.choose_multiple(rng, n).collect();
+        let mut sky: Vec<Constellation> = Vec::with_capacity(epithets.len());
+        for epithet in epithets {
+            let name = (0..NAME_ATTEMPTS)
+                .map(|_| {
+                    let ending = ENDINGS.choose(rng).unwrap();
+                    attach(&self.names.generate(rng), ending)
+                })
+                .find(|name| sky.iter().all(|other| other.name != *name));
+            let name = match name {
+                Some(name) => name,
+                None => break,
+            };
+            let genitive = genitive(&name);
+            let count = rng.gen_range(self.min_stars..=self.max_stars);
+            let stars = GREEK[..count]
+                .iter()
+                .map(|letter| Star {
+                    letter: letter.to_string(),
+                    designation: format!("{} {}", letter, genitive),
+                })
+                .collect();
+            sky.push(Constellation {
+                name,
+                genitive,
+                epithet: epithet.to_string(),
+                stars,
+            });
+        }
+        sky
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SkyGenerator;
+    use crate::table::{Table, Token, AIW};
+    use crate::totro::TotroConfig;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_generate() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let sky = SkyGenerator::default()
+            .with_stars(2, 4)
+            .generate(6, &mut rng);
+        assert_eq!(sky.len(), 6);
+        for constellation in &sky {
+            assert!((2..=4).contains(&constellation.stars.len()));
+            assert_eq!(
+                constellation.stars[0].designation,
+                format!("Alpha {}", constellation.genitive)
+            );
+            let same = sky
+                .iter()
+                .filter(|other| {
+                    other.name == constellation.name || other.epithet == constellation.epithet
+                })
+                .count();
+            assert_eq!(same, 1);
+        }
+    }
+
+    #[test]
+    fn test_few_names() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let vowels = Table::new(vec![Token::new("a", AIW)]).unwrap();
+        let consonants = Table::new(vec![Token::new("k", AIW)]).unwrap();
+        let names = TotroConfig::new(vowels, consonants).with_length(1, 1);
+        // Two stems and four endings leave at most eight distinct names.
+        let sky = SkyGenerator::default()
+            .with_names(names)
+            .generate(12, &mut rng);
+        assert!(!sky.is_empty() && sky.len() <= 8, "{}", sky.len());
+    }
+}
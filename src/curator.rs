@@ -0,0 +1,214 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Interactive Curation
+
+use crate::totro::TotroConfig;
+use rand::Rng;
+use std::collections::BTreeSet;
+
+/// A token used in a presented name, identified by its table and text.
+type Used = (bool, String);
+
+/// Human-in-the-loop tuning of a [`TotroConfig`].
+///
+/// The curator presents candidate names and remembers the tokens each was built from. Accepting
+/// a candidate raises the weights of its tokens and rejecting one lowers them, so later
+/// candidates drift toward the names the editor likes. The tuned configuration can then be
+/// taken out and saved like any other.
+///
+/// ```rust
+/// use nominae::{Curator, TotroConfig};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let mut curator = Curator::new(TotroConfig::default());
+/// for _ in 0..20 {
+///     let name = curator.candidate(&mut rng);
+///     if name.len() > 6 {
+///         curator.reject(&name);
+///     } else {
+///         curator.accept(&name);
+///     }
+/// }
+/// let tuned = curator.into_config();
+/// # let _ = tuned;
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Curator {
+    config: TotroConfig,
+    rate: f64,
+    presented: Vec<(String, BTreeSet<Used>)>,
+    accepted: Vec<String>,
+    rejected: Vec<String>,
+}
+
+impl Curator {
+    /// Curate names generated from `config`.
+    pub fn new(config: TotroConfig) -> Curator {
+        Curator {
+            config,
+            rate: 0.25,
+            presented: Vec::new(),
+            accepted: Vec::new(),
+            rejected: Vec::new(),
+        }
+    }
+
+    /// Set the fraction by which each decision scales the weights of a name's tokens.
+    ///
+    /// Accepted tokens grow to at least one more than their weight, and rejected tokens shrink
+    /// but keep a weight of at least 1, so no token is ever removed outright.
+    pub fn with_rate(mut self, rate: f64) -> Curator {
+        assert!((0.0..=1.0).contains(&rate), "rate must be within 0..=1");
+        self.rate = rate;
+        self
+    }
+
+    /// Fraction by which each decision scales the weights of a name's tokens.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Generate a candidate to present, remembering the tokens it was built from until it is
+    /// accepted or rejected.
+    pub fn candidate<T: Rng + ?Sized>(&mut self, rng: &mut T) -> String {
+        let explanation = self.config.explain(rng);
+        let vowels = self.config.vowels().tokens();
+        let used = explanation
+            .tokens()
+            .iter()
+            .map(|&token| {
+                let vowel = vowels.iter().any(|vowel| core::ptr::eq(vowel, token));
+                (vowel, token.text().to_owned())
+            })
+            .collect();
+        let name = explanation.into_name();
+        self.presented.push((name.clone(), used));
+        name
+    }
+
+    /// Record that the editor liked `name`, raising the weights of its tokens.
+    ///
+    /// Returns `false`, changing nothing, when `name` is not an undecided candidate.
+    pub fn accept(&mut self, name: &str) -> bool {
+        self.decide(name, true)
+    }
+
+    /// Record that the editor disliked `name`, lowering the weights of its tokens.
+    ///
+    /// Returns `false`, changing nothing, when `name` is not an undecided candidate.
+    pub fn reject(&mut self, name: &str) -> bool {
+        self.decide(name, false)
+    }
+
+    /// Names accepted so far, in the order decided.
+    pub fn accepted(&self) -> &[String] {
+        &self.accepted
+    }
+
+    /// Names rejected so far, in the order decided.
+    pub fn rejected(&self) -> &[String] {
+        &self.rejected
+    }
+
+    /// The configuration as tuned so far.
+    pub fn config(&self) -> &TotroConfig {
+        &self.config
+    }
+
+    /// Consume the curator, returning the tuned configuration.
+    pub fn into_config(self) -> TotroConfig {
+        self.config
+    }
+
+    fn decide(&mut self, name: &str, accept: bool) -> bool {
+        let idx = match self.presented.iter().position(|(shown, _)| shown == name) {
+            Some(idx) => idx,
+            None => return false,
+        };
+        let (name, used) = self.presented.remove(idx);
+        let scale = if accept {
+            1.0 + self.rate
+        } else {
+            1.0 - self.rate
+        };
+        for (vowel, text) in used {
+            let table = if vowel {
+                self.config.vowels_mut()
+            } else {
+                self.config.consonants_mut()
+            };
+            let weight = match table.get(&text) {
+                Some(token) => token.weight(),
+                None => continue,
+            };
+            let scaled = weight as f64 * scale;
+            let weight = if accept {
+                (scaled.ceil() as u32).max(weight.saturating_add(1))
+            } else {
+                (scaled.floor() as u32).clamp(1, weight)
+            };
+            table
+                .set_weight(&text, weight)
+                .expect("tokens of presented names are in their tables");
+        }
+        if accept {
+            self.accepted.push(name);
+        } else {
+            self.rejected.push(name);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Curator;
+    use crate::table::{Table, Token, AIW};
+    use crate::totro::TotroConfig;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_decisions() {
+        let vowels = Table::new(vec![Token::new("a", AIW), Token::new("o", AIW)]).unwrap();
+        let consonants = Table::new(vec![Token::new("k", AIW), Token::new("r", AIW)]).unwrap();
+        let config = TotroConfig::new(vowels, consonants).with_length(2, 2);
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut curator = Curator::new(config).with_rate(0.5);
+        let liked = curator.candidate(&mut rng);
+        let disliked = curator.candidate(&mut rng);
+        assert!(curator.accept(&liked));
+        assert!(!curator.accept(&liked));
+        assert!(!curator.reject("Nobody"));
+        let weight = |curator: &Curator, text: &str| {
+            let config = curator.config();
+            let token = config.vowels().get(text).or(config.consonants().get(text));
+            token.unwrap().weight()
+        };
+        for letter in liked.to_lowercase().chars() {
+            assert_eq!(weight(&curator, &letter.to_string()), 2);
+        }
+        assert!(curator.reject(&disliked));
+        for letter in disliked.to_lowercase().chars() {
+            assert_eq!(weight(&curator, &letter.to_string()), 1);
+        }
+        assert_eq!(curator.accepted(), &[liked][..]);
+        assert_eq!(curator.rejected(), &[disliked][..]);
+    }
+}
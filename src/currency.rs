@@ -0,0 +1,203 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Currency and Denomination Name Generation
+
+use crate::morphology::{attach, plural};
+use crate::totro::TotroConfig;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Endings that make an invented stem sound like a coin.
+const ENDINGS: [&str; 8] = ["ar", "et", "in", "mark", "el", "ot", "ine", "ling"];
+
+/// Names generated per denomination before the currency gives up on finding a new one.
+const NAME_ATTEMPTS: usize = 1000;
+
+/// Subunits per next-larger unit, as in twelve pence to the shilling.
+const RATIOS: [u32; 5] = [4, 10, 12, 20, 100];
+
+const SLANG: [&str; 12] = [
+    "bit", "clip", "chip", "shiner", "round", "scale", "knuckle", "crow", "bean", "tooth", "wheel",
+    "button",
+];
+
+/// One unit of a [`Currency`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Denomination {
+    /// Singular name, such as "velmar".
+    pub name: String,
+    /// Plural name, such as "velmars".
+    pub plural: String,
+    /// Street slang for the unit, such as "crow".
+    pub slang: String,
+    /// How many of this unit make one base unit.
+    pub per_base: u32,
+}
+
+/// A culture's currency system: a base unit and progressively smaller subunits.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Currency {
+    /// The main unit, whose `per_base` is 1.
+    pub base: Denomination,
+    /// Smaller units, from largest to smallest.
+    pub subunits: Vec<Denomination>,
+}
+
+impl Currency {
+    /// Every denomination from the base unit down to the smallest subunit.
+    pub fn denominations(&self) -> impl Iterator<Item = &Denomination> {
+        std::iter::once(&self.base).chain(self.subunits.iter())
+    }
+}
+
+/// Generator for consistent [`Currency`] systems.
+///
+/// One call names the base unit and its subunits from the same culture's sounds and gives each a
+/// distinct slang term. Denominations only record how many of each make one base unit, so
+/// economies keep control of prices and exchange rates.
+///
+/// ```rust
+/// use nominae::CurrencyGenerator;
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let currency = CurrencyGenerator::default().generate(&mut rng);
+/// for unit in currency.denominations() {
+///     println!("{} {} (\"{}\")", unit.per_base, unit.plural, unit.slang);
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct CurrencyGenerator {
+    names: TotroConfig,
+    min_subunits: usize,
+    max_subunits: usize,
+}
+
+impl Default for CurrencyGenerator {
+    fn default() -> CurrencyGenerator {
+        CurrencyGenerator {
+            names: TotroConfig::alphabetic().with_length(2, 3),
+            min_subunits: 1,
+            max_subunits: 3,
+        }
+    }
+}
+
+impl CurrencyGenerator {
+    /// Set the configuration used for the culture's sounds.
+    pub fn with_names(mut self, names: TotroConfig) -> CurrencyGenerator {
+        self.names = names;
+        self
+    }
+
+    /// Set the inclusive range of subunits below the base unit.
+    pub fn with_subunits(mut self, min: usize, max: usize) -> CurrencyGenerator {
+        assert!(min <= max, "min subunits must not exceed max subunits");
+        assert!(max < SLANG.len(), "too many subunits for distinct slang");
+        self.min_subunits = min;
+        self.max_subunits = max;
+        self
+    }
+
+    /// Generate a currency system.
+    ///
+    /// Fewer subunits are named when the name configuration runs out of new names.
+    pub fn generate<T: Rng + ?Sized>(&self, rng: &mut T) -> Currency {
+        let count = rng.gen_range(self.min_subunits..=self.max_subunits);
+        let mut slang = SLANG.choose_multiple(rng, count + 1);
+        let mut names: Vec<String> = Vec::with_capacity(count + 1);
+        let mut per_base = 1;
+        let mut denominations = Vec::with_capacity(count + 1);
+        for index in 0..=count {
+            if index > 0 {
+                per_base *= RATIOS.choose(rng).unwrap();
+            }
+            let name = (0..NAME_ATTEMPTS)
+                .map(|_| {
+                    let stem = self.names.generate(rng).to_lowercase();
+                    attach(&stem, ENDINGS.choose(rng).unwrap())
+                })
+                .find(|name| !names.contains(name));
+            let name = match name {
+                Some(name) => name,
+                None => break,
+            };
+            names.push(name.clone());
+            denominations.push(Denomination {
+                plural: plural(&name),
+                name,
+                slang: slang.next().unwrap().to_string(),
+                per_base,
+            });
+        }
+        let base = denominations.remove(0);
+        Currency {
+            base,
+            subunits: denominations,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CurrencyGenerator;
+    use crate::table::{Table, Token, AIW};
+    use crate::totro::TotroConfig;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_generate() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let generator = CurrencyGenerator::default().with_subunits(2, 3);
+        for _ in 0..20 {
+            let currency = generator.generate(&mut rng);
+            assert_eq!(currency.base.per_base, 1);
+            assert!((2..=3).contains(&currency.subunits.len()));
+            let units: Vec<_> = currency.denominations().collect();
+            for pair in units.windows(2) {
+                assert_eq!(pair[1].per_base % pair[0].per_base, 0);
+                assert!(pair[1].per_base > pair[0].per_base);
+            }
+            for unit in &units {
+                assert!(unit.plural.starts_with(unit.name.as_str()));
+                let same = units
+                    .iter()
+                    .filter(|other| other.name == unit.name || other.slang == unit.slang)
+                    .count();
+                assert_eq!(same, 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_few_names() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let vowels = Table::new(vec![Token::new("a", AIW)]).unwrap();
+        let consonants = Table::new(vec![Token::new("k", AIW)]).unwrap();
+        let names = TotroConfig::new(vowels, consonants)
+            .with_length(1, 1)
+            .with_vowel_start(0.0);
+        // One stem and eight endings leave room for the base unit and seven subunits.
+        let currency = CurrencyGenerator::default()
+            .with_names(names)
+            .with_subunits(10, 10)
+            .generate(&mut rng);
+        assert_eq!(currency.subunits.len(), 7);
+    }
+}
@@ -0,0 +1,218 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Decorative Name Suffixes
+//!
+//! Decorations are appended after a name has been generated, so sci-fi registry numbers or clan
+//! marks never leak into the token tables and the names underneath stay usable on their own.
+
+use crate::morphology::attach;
+use crate::stable::{below, chance};
+use rand::Rng;
+
+/// Letters of Roman numerals with their values, largest first.
+const ROMAN: [(u32, &str); 13] = [
+    (1000, "M"),
+    (900, "CM"),
+    (500, "D"),
+    (400, "CD"),
+    (100, "C"),
+    (90, "XC"),
+    (50, "L"),
+    (40, "XL"),
+    (10, "X"),
+    (9, "IX"),
+    (5, "V"),
+    (4, "IV"),
+    (1, "I"),
+];
+
+/// A suffix that can be appended to a name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Decoration {
+    /// A number drawn from `min..=max`, as in "Kessa-7".
+    Numeral {
+        /// Smallest number drawn.
+        min: u32,
+        /// Largest number drawn.
+        max: u32,
+    },
+    /// A Roman numeral drawn from `min..=max`, as in "Velmar IV". Numbers are clamped to
+    /// `1..=3999`, the range Roman numerals can write.
+    Roman {
+        /// Smallest number drawn.
+        min: u32,
+        /// Largest number drawn.
+        max: u32,
+    },
+    /// Fixed text such as a clan mark, as in "Korr-dai".
+    Mark(String),
+}
+
+impl Decoration {
+    fn render<T: Rng + ?Sized>(&self, rng: &mut T) -> String {
+        match self {
+            Decoration::Numeral { min, max } => draw(*min, *max, rng).to_string(),
+            Decoration::Roman { min, max } => {
+                roman(draw((*min).clamp(1, 3999), (*max).clamp(1, 3999), rng))
+            }
+            Decoration::Mark(mark) => mark.clone(),
+        }
+    }
+}
+
+/// Draw from `min..=max`, or return `min` when the range is empty.
+fn draw<T: Rng + ?Sized>(min: u32, max: u32, rng: &mut T) -> u32 {
+    if max <= min {
+        return min;
+    }
+    min + below(rng, (max - min) as u64 + 1) as u32
+}
+
+/// `number` written in Roman numerals, which must be within `1..=3999`.
+pub(crate) fn roman(mut number: u32) -> String {
+    let mut output = String::new();
+    for &(value, letters) in ROMAN.iter() {
+        while number >= value {
+            output.push_str(letters);
+            number -= value;
+        }
+    }
+    output
+}
+
+/// Post-processor appending a randomly chosen [`Decoration`] to some of the names it is given.
+///
+/// Each decoration has its own separator and weight. An empty separator joins the suffix to the
+/// name as a word ending, dropping a final vowel before a vowel and merging a doubled consonant,
+/// so "Korra" with the mark "ai" becomes "Korrai" rather than "Korraai".
+///
+/// ```rust
+/// use nominae::{Decoration, Decorator, TotroConfig};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let decorator = Decorator::default()
+///     .with_probability(0.5)
+///     .with(Decoration::Numeral { min: 1, max: 99 }, "-", 2)
+///     .with(Decoration::Roman { min: 2, max: 9 }, " ", 1)
+///     .with(Decoration::Mark("dai".into()), "-", 1);
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let name = TotroConfig::default().generate(&mut rng);
+/// println!("{}", decorator.decorate(&name, &mut rng));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Decorator {
+    decorations: Vec<(Decoration, String, u32)>,
+    probability: f64,
+}
+
+impl Default for Decorator {
+    fn default() -> Decorator {
+        Decorator {
+            decorations: Vec::new(),
+            probability: 1.0,
+        }
+    }
+}
+
+impl Decorator {
+    /// Add `decoration`, joined to names with `separator` and chosen in proportion to `weight`.
+    pub fn with(mut self, decoration: Decoration, separator: &str, weight: u32) -> Decorator {
+        self.decorations
+            .push((decoration, separator.to_owned(), weight));
+        self
+    }
+
+    /// Set the probability that a name is decorated at all.
+    pub fn with_probability(mut self, probability: f64) -> Decorator {
+        assert!(
+            (0.0..=1.0).contains(&probability),
+            "decoration probability must be within 0..=1"
+        );
+        self.probability = probability;
+        self
+    }
+
+    /// Probability that a name is decorated.
+    pub fn probability(&self) -> f64 {
+        self.probability
+    }
+
+    /// `name` with a decoration appended, or unchanged when no decoration is drawn.
+    pub fn decorate<T: Rng + ?Sized>(&self, name: &str, rng: &mut T) -> String {
+        let total: u64 = self
+            .decorations
+            .iter()
+            .map(|&(_, _, weight)| weight as u64)
+            .sum();
+        if total == 0 || !chance(rng, self.probability) {
+            return name.to_owned();
+        }
+        let mut roll = below(rng, total);
+        let (decoration, separator, _) = self
+            .decorations
+            .iter()
+            .find(|&&(_, _, weight)| match roll.checked_sub(weight as u64) {
+                Some(rest) => {
+                    roll = rest;
+                    false
+                }
+                None => true,
+            })
+            .expect("roll is below the total weight");
+        let suffix = decoration.render(rng);
+        if separator.is_empty() {
+            attach(name, &suffix)
+        } else {
+            format!("{}{}{}", name, separator, suffix)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{roman, Decoration, Decorator};
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_roman() {
+        assert_eq!(roman(4), "IV");
+        assert_eq!(roman(1994), "MCMXCIV");
+        assert_eq!(roman(3999), "MMMCMXCIX");
+    }
+
+    #[test]
+    fn test_decorate() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let marks = Decorator::default().with(Decoration::Mark("ai".into()), "", 1);
+        assert_eq!(marks.decorate("Korra", &mut rng), "Korrai");
+        let numbers = Decorator::default()
+            .with(Decoration::Roman { min: 2, max: 3 }, " ", 1)
+            .with(Decoration::Numeral { min: 7, max: 7 }, "-", 1);
+        for _ in 0..50 {
+            let name = numbers.decorate("Velmar", &mut rng);
+            assert!(
+                ["Velmar II", "Velmar III", "Velmar-7"].contains(&name.as_str()),
+                "{}",
+                name
+            );
+        }
+        let never = numbers.with_probability(0.0);
+        assert_eq!(never.decorate("Velmar", &mut rng), "Velmar");
+    }
+}
@@ -0,0 +1,590 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Profile Diffing and Merging
+//!
+//! Teams maintaining several related culture files, such as the dialects of one culture, can
+//! compare and combine both halves of a culture programmatically:
+//!
+//! - Its sounds are a [`TotroConfig`], whose token tables, weights and phonotactic rules are
+//!   compared with [`TotroConfig::diff`] and folded together with [`TotroConfig::merge`].
+//! - Its morphology is a [`Language`], whose adjective suffixes, possessive, plural, case
+//!   exceptions and respellings are compared with [`Language::diff`] and folded together with
+//!   [`Language::merge`].
+
+use crate::language::{Language, Plural, Possessive};
+use crate::phonotactics::{Adjacency, Constraint};
+use crate::table::{Table, Token, BOW, EOW, MOW};
+use crate::totro::TotroConfig;
+use std::fmt;
+
+/// A single difference between two profiles.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Change<T> {
+    /// Present only in the other profile.
+    Added(T),
+    /// Present only in this profile.
+    Removed(T),
+    /// Present in both, but with a different weight, positions or attributes.
+    Changed {
+        /// This profile's version.
+        before: T,
+        /// The other profile's version.
+        after: T,
+    },
+}
+
+/// Every token, weight and constraint difference between two profiles, as found by
+/// [`TotroConfig::diff`].
+///
+/// Constraints and adjacency rules have no identity beyond their value, so they are only ever
+/// added or removed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProfileDiff {
+    /// Differences between the vowel tables, matching tokens by their text.
+    pub vowels: Vec<Change<Token>>,
+    /// Differences between the consonant tables, matching tokens by their text.
+    pub consonants: Vec<Change<Token>>,
+    /// Phonotactic constraints only one profile enforces.
+    pub constraints: Vec<Change<Constraint>>,
+    /// Adjacency rules only one profile enforces.
+    pub adjacencies: Vec<Change<Adjacency>>,
+}
+
+impl ProfileDiff {
+    /// Whether the tables and rules of both profiles are identical.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of differences found.
+    pub fn len(&self) -> usize {
+        self.vowels.len() + self.consonants.len() + self.constraints.len() + self.adjacencies.len()
+    }
+}
+
+impl fmt::Display for ProfileDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} differences", self.len())?;
+        let tables = [("vowel", &self.vowels), ("consonant", &self.consonants)];
+        for (table, changes) in tables.iter() {
+            for change in changes.iter() {
+                match change {
+                    Change::Added(token) => write!(f, "\n  + {} {}", table, describe(token))?,
+                    Change::Removed(token) => write!(f, "\n  - {} {}", table, describe(token))?,
+                    Change::Changed { before, after } => write!(
+                        f,
+                        "\n  ~ {} {} -> {}",
+                        table,
+                        describe(before),
+                        describe(after)
+                    )?,
+                }
+            }
+        }
+        for change in &self.constraints {
+            write_rule(f, "constraint", change)?;
+        }
+        for change in &self.adjacencies {
+            write_rule(f, "adjacency", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// A token as its text, weight and positions, in the order of a language pack line.
+fn describe(token: &Token) -> String {
+    let positions: String = [(BOW, 'b'), (MOW, 'm'), (EOW, 'e')]
+        .iter()
+        .filter(|&&(flag, _)| token.allows(flag))
+        .map(|&(_, letter)| letter)
+        .collect();
+    format!("{:?} {} {}", token.text(), token.weight(), positions)
+}
+
+fn write_rule<T: fmt::Debug>(
+    f: &mut fmt::Formatter<'_>,
+    kind: &str,
+    change: &Change<T>,
+) -> fmt::Result {
+    match change {
+        Change::Added(rule) => write!(f, "\n  + {} {:?}", kind, rule),
+        Change::Removed(rule) => write!(f, "\n  - {} {:?}", kind, rule),
+        Change::Changed { before, after } => {
+            write!(f, "\n  ~ {} {:?} -> {:?}", kind, before, after)
+        }
+    }
+}
+
+/// Every morphology difference between two languages, as found by [`Language::diff`].
+///
+/// Settings held as a whole, such as the possessive, are only ever changed. Case exceptions are
+/// matched by their lowercase form, so a change of casing is reported as changed, while
+/// respellings, like constraints, are only ever added or removed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LanguageDiff {
+    /// The adjective suffixes, compared in order of preference.
+    pub adjective_suffixes: Option<Change<Vec<String>>>,
+    /// How possessives are rendered.
+    pub possessive: Option<Change<Possessive>>,
+    /// How plurals are rendered.
+    pub plural: Option<Change<Plural>>,
+    /// Words whose casing only one language keeps, or keeps differently.
+    pub case_exceptions: Vec<Change<String>>,
+    /// Respellings as `(from, to)` pairs that only one language applies.
+    pub respellings: Vec<Change<(String, String)>>,
+}
+
+impl LanguageDiff {
+    /// Whether the morphology of both languages is identical.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of differences found.
+    pub fn len(&self) -> usize {
+        usize::from(self.adjective_suffixes.is_some())
+            + usize::from(self.possessive.is_some())
+            + usize::from(self.plural.is_some())
+            + self.case_exceptions.len()
+            + self.respellings.len()
+    }
+}
+
+impl fmt::Display for LanguageDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} differences", self.len())?;
+        if let Some(change) = &self.adjective_suffixes {
+            write_rule(f, "adjective suffixes", change)?;
+        }
+        if let Some(change) = &self.possessive {
+            write_rule(f, "possessive", change)?;
+        }
+        if let Some(change) = &self.plural {
+            write_rule(f, "plural", change)?;
+        }
+        for change in &self.case_exceptions {
+            write_rule(f, "case exception", change)?;
+        }
+        for change in &self.respellings {
+            write_rule(f, "respelling", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// How [`TotroConfig::merge`] and [`Language::merge`] settle a token or setting both define
+/// differently.
+///
+/// Tokens and rules found in only one profile are always kept.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Keep this profile's version of the token.
+    Ours,
+    /// Take the other profile's version of the token.
+    Theirs,
+    /// Add the weights of both versions and allow the positions of either, keeping this
+    /// profile's attributes, as when pooling the tables of two dialects. Languages keep their
+    /// own possessive and plural and append the other's extra adjective suffixes.
+    Sum,
+}
+
+impl TotroConfig {
+    /// Compare the tables and rules of this profile to `other`.
+    ///
+    /// Removed and changed tokens are listed in the order of this profile's tables, followed by
+    /// added tokens in the order of `other`'s. Settings outside the tables and rules, such as
+    /// length and mode, are not compared.
+    ///
+    /// ```rust
+    /// use nominae::{Change, Constraint, TotroConfig};
+    ///
+    /// let base = TotroConfig::default();
+    /// let mut dialect = base.clone().with_constraint(Constraint::MaxVowelRun(2));
+    /// dialect.consonants_mut().set_weight("r", 9).unwrap();
+    /// let diff = base.diff(&dialect);
+    /// assert_eq!(diff.len(), 2);
+    /// assert!(matches!(&diff.consonants[0], Change::Changed { after, .. } if after.weight() == 9));
+    /// println!("{}", diff);
+    /// ```
+    pub fn diff(&self, other: &TotroConfig) -> ProfileDiff {
+        ProfileDiff {
+            vowels: diff_tables(self.vowels(), other.vowels()),
+            consonants: diff_tables(self.consonants(), other.consonants()),
+            constraints: diff_rules(self.constraints(), other.constraints()),
+            adjacencies: diff_rules(self.adjacencies(), other.adjacencies()),
+        }
+    }
+
+    /// Fold the tables and rules of `other` into this profile, settling tokens both define
+    /// differently by `strategy`.
+    ///
+    /// The merged tables hold every token of either profile and the merged profile enforces
+    /// every rule of either. Every other setting, such as length and mode, is kept from `self`.
+    ///
+    /// ```rust
+    /// use nominae::{MergeStrategy, TotroConfig};
+    ///
+    /// let base = TotroConfig::default();
+    /// let mut dialect = base.clone();
+    /// dialect.consonants_mut().set_weight("r", 9).unwrap();
+    /// let merged = base.merge(&dialect, MergeStrategy::Sum);
+    /// let weight = base.consonants().get("r").unwrap().weight();
+    /// assert_eq!(merged.consonants().get("r").unwrap().weight(), weight + 9);
+    /// assert!(base.merge(&dialect, MergeStrategy::Theirs).diff(&dialect).is_empty());
+    /// ```
+    pub fn merge(&self, other: &TotroConfig, strategy: MergeStrategy) -> TotroConfig {
+        let mut merged = self
+            .clone()
+            .with_vowels(merge_tables(self.vowels(), other.vowels(), strategy))
+            .with_consonants(merge_tables(
+                self.consonants(),
+                other.consonants(),
+                strategy,
+            ));
+        for constraint in other.constraints() {
+            if !self.constraints().contains(constraint) {
+                merged = merged.with_constraint(constraint.clone());
+            }
+        }
+        for adjacency in other.adjacencies() {
+            if !self.adjacencies().contains(adjacency) {
+                merged = merged.with_adjacency(adjacency.clone());
+            }
+        }
+        merged
+    }
+}
+
+impl Language {
+    /// Compare the morphology of this language to `other`.
+    ///
+    /// ```rust
+    /// use nominae::{Change, Language, Possessive};
+    ///
+    /// let common = Language::default();
+    /// let high = common
+    ///     .clone()
+    ///     .with_possessive(Possessive::Preposition("of".into()))
+    ///     .with_case_exception("von");
+    /// let diff = common.diff(&high);
+    /// assert_eq!(diff.len(), 2);
+    /// assert_eq!(diff.case_exceptions, vec![Change::Added("von".to_owned())]);
+    /// println!("{}", diff);
+    /// ```
+    pub fn diff(&self, other: &Language) -> LanguageDiff {
+        LanguageDiff {
+            adjective_suffixes: changed(
+                &self.adjective_suffixes().to_vec(),
+                &other.adjective_suffixes().to_vec(),
+            ),
+            possessive: changed(self.possessive(), other.possessive()),
+            plural: changed(self.plural(), other.plural()),
+            case_exceptions: diff_exceptions(self, other),
+            respellings: diff_rules(&respellings(self), &respellings(other)),
+        }
+    }
+
+    /// Fold the morphology of `other` into this language, settling settings both define
+    /// differently by `strategy`.
+    ///
+    /// The merged language keeps the case exceptions and respellings of either, with this
+    /// language's respellings applied first.
+    ///
+    /// ```rust
+    /// use nominae::{Language, MergeStrategy, Plural};
+    ///
+    /// let common = Language::default().with_respelling("th", "t");
+    /// let high = Language::default()
+    ///     .with_plural(Plural::Suffix("i".into()))
+    ///     .with_respelling("ph", "f");
+    /// let merged = common.merge(&high, MergeStrategy::Theirs);
+    /// assert_eq!(merged.plural(), &Plural::Suffix("i".into()));
+    /// assert_eq!(merged.respellings().count(), 2);
+    /// ```
+    pub fn merge(&self, other: &Language, strategy: MergeStrategy) -> Language {
+        let (base, rest) = match strategy {
+            MergeStrategy::Theirs => (other, self),
+            MergeStrategy::Ours | MergeStrategy::Sum => (self, other),
+        };
+        let mut suffixes = base.adjective_suffixes().to_vec();
+        if strategy == MergeStrategy::Sum {
+            for suffix in rest.adjective_suffixes() {
+                if !suffixes.contains(suffix) {
+                    suffixes.push(suffix.clone());
+                }
+            }
+        }
+        let mut merged = Language::default()
+            .with_adjective_suffixes(suffixes)
+            .with_possessive(base.possessive().clone())
+            .with_plural(base.plural().clone());
+        // Adding the winning side last lets its casing replace the other's for the same word.
+        for form in rest.case_exceptions().chain(base.case_exceptions()) {
+            merged = merged.with_case_exception(form);
+        }
+        let ours = respellings(self);
+        for (from, to) in ours.iter().chain(
+            respellings(other)
+                .iter()
+                .filter(|rule| !ours.contains(rule)),
+        ) {
+            merged = merged.with_respelling(from, to);
+        }
+        merged
+    }
+}
+
+/// A change from `before` to `after` when the two differ.
+fn changed<T: Clone + PartialEq>(before: &T, after: &T) -> Option<Change<T>> {
+    if before == after {
+        None
+    } else {
+        Some(Change::Changed {
+            before: before.clone(),
+            after: after.clone(),
+        })
+    }
+}
+
+fn respellings(language: &Language) -> Vec<(String, String)> {
+    language
+        .respellings()
+        .map(|(from, to)| (from.to_owned(), to.to_owned()))
+        .collect()
+}
+
+fn diff_exceptions(ours: &Language, theirs: &Language) -> Vec<Change<String>> {
+    let find = |language: &Language, form: &str| {
+        let key = form.to_lowercase();
+        language
+            .case_exceptions()
+            .find(|other| other.to_lowercase() == key)
+            .map(str::to_owned)
+    };
+    let mut changes: Vec<Change<String>> = ours
+        .case_exceptions()
+        .filter_map(|form| match find(theirs, form) {
+            None => Some(Change::Removed(form.to_owned())),
+            Some(their) if their != form => Some(Change::Changed {
+                before: form.to_owned(),
+                after: their,
+            }),
+            Some(_) => None,
+        })
+        .collect();
+    changes.extend(
+        theirs
+            .case_exceptions()
+            .filter(|form| find(ours, form).is_none())
+            .map(|form| Change::Added(form.to_owned())),
+    );
+    changes
+}
+
+fn diff_tables(ours: &Table, theirs: &Table) -> Vec<Change<Token>> {
+    let mut changes: Vec<Change<Token>> = ours
+        .tokens()
+        .iter()
+        .filter_map(|token| match theirs.get(token.text()) {
+            None => Some(Change::Removed(token.clone())),
+            Some(their) if their != token => Some(Change::Changed {
+                before: token.clone(),
+                after: their.clone(),
+            }),
+            Some(_) => None,
+        })
+        .collect();
+    changes.extend(
+        theirs
+            .tokens()
+            .iter()
+            .filter(|token| ours.get(token.text()).is_none())
+            .map(|token| Change::Added(token.clone())),
+    );
+    changes
+}
+
+fn diff_rules<T: Clone + PartialEq>(ours: &[T], theirs: &[T]) -> Vec<Change<T>> {
+    let removed = ours
+        .iter()
+        .filter(|rule| !theirs.contains(rule))
+        .map(|rule| Change::Removed(rule.clone()));
+    let added = theirs
+        .iter()
+        .filter(|rule| !ours.contains(rule))
+        .map(|rule| Change::Added(rule.clone()));
+    removed.chain(added).collect()
+}
+
+fn merge_tables(ours: &Table, theirs: &Table, strategy: MergeStrategy) -> Table {
+    let mut tokens: Vec<Token> = ours
+        .tokens()
+        .iter()
+        .map(|token| match (theirs.get(token.text()), strategy) {
+            (Some(their), MergeStrategy::Theirs) => their.clone(),
+            _ => token.clone(),
+        })
+        .collect();
+    tokens.extend(
+        theirs
+            .tokens()
+            .iter()
+            .filter(|token| ours.get(token.text()).is_none())
+            .cloned(),
+    );
+    let mut table = Table::new(tokens).expect("the union of two valid tables is valid");
+    if strategy == MergeStrategy::Sum {
+        for token in ours.tokens() {
+            if let Some(their) = theirs.get(token.text()) {
+                let weight = token.weight().saturating_add(their.weight());
+                table
+                    .set_weight(token.text(), weight)
+                    .and_then(|_| table.set_flags(token.text(), token.flags() | their.flags()))
+                    .expect("widening a token keeps the table valid");
+            }
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Change, MergeStrategy};
+    use crate::language::{Language, Plural, Possessive};
+    use crate::phonotactics::Constraint;
+    use crate::table::{Table, Token, AIW, BOW, EOW, MOW};
+    use crate::totro::TotroConfig;
+
+    fn profiles() -> (TotroConfig, TotroConfig) {
+        let ours = TotroConfig::new(
+            Table::new(vec![Token::new("a", AIW), Token::new("e", AIW)]).unwrap(),
+            Table::new(vec![
+                Token::new("k", AIW),
+                Token::new("r", BOW | MOW).with_weight(2),
+            ])
+            .unwrap(),
+        );
+        let theirs = TotroConfig::new(
+            Table::new(vec![Token::new("a", AIW), Token::new("o", AIW)]).unwrap(),
+            Table::new(vec![
+                Token::new("k", AIW),
+                Token::new("r", MOW | EOW).with_weight(3),
+            ])
+            .unwrap(),
+        )
+        .with_constraint(Constraint::NoEnding("k".into()));
+        (ours, theirs)
+    }
+
+    #[test]
+    fn test_diff() {
+        let (ours, theirs) = profiles();
+        let diff = ours.diff(&theirs);
+        assert_eq!(
+            diff.vowels,
+            vec![
+                Change::Removed(Token::new("e", AIW)),
+                Change::Added(Token::new("o", AIW)),
+            ]
+        );
+        assert_eq!(
+            diff.consonants,
+            vec![Change::Changed {
+                before: Token::new("r", BOW | MOW).with_weight(2),
+                after: Token::new("r", MOW | EOW).with_weight(3),
+            }]
+        );
+        assert_eq!(
+            diff.constraints,
+            vec![Change::Added(Constraint::NoEnding("k".into()))]
+        );
+        assert_eq!(
+            diff.to_string(),
+            "4 differences\n  - vowel \"e\" 1 bme\n  + vowel \"o\" 1 bme\n  \
+             ~ consonant \"r\" 2 bm -> \"r\" 3 me\n  + constraint NoEnding(\"k\")"
+        );
+        assert!(ours.diff(&ours).is_empty());
+    }
+
+    #[test]
+    fn test_merge() {
+        let (ours, theirs) = profiles();
+        let kept = ours.merge(&theirs, MergeStrategy::Ours);
+        assert_eq!(kept.vowels().tokens().len(), 3);
+        assert_eq!(kept.consonants().get("r"), ours.consonants().get("r"));
+        assert_eq!(kept.constraints(), theirs.constraints());
+        let taken = ours.merge(&theirs, MergeStrategy::Theirs);
+        assert_eq!(taken.consonants().get("r"), theirs.consonants().get("r"));
+        let summed = ours.merge(&theirs, MergeStrategy::Sum);
+        assert_eq!(
+            summed.consonants().get("r"),
+            Some(&Token::new("r", AIW).with_weight(5))
+        );
+        assert!(summed.validate().is_ok());
+    }
+
+    #[test]
+    fn test_language() {
+        let ours = Language::default()
+            .with_case_exception("Von")
+            .with_respelling("th", "t");
+        let theirs = Language::default()
+            .with_adjective_suffixes(vec!["ine", "ic"])
+            .with_plural(Plural::Suffix("i".into()))
+            .with_case_exception("von")
+            .with_case_exception("al-")
+            .with_respelling("ph", "f");
+        let diff = ours.diff(&theirs);
+        assert_eq!(diff.possessive, None);
+        assert_eq!(
+            diff.plural,
+            Some(Change::Changed {
+                before: Plural::English,
+                after: Plural::Suffix("i".into()),
+            })
+        );
+        assert_eq!(
+            diff.case_exceptions,
+            vec![
+                Change::Changed {
+                    before: "Von".into(),
+                    after: "von".into(),
+                },
+                Change::Added("al-".into()),
+            ]
+        );
+        assert_eq!(diff.len(), 6);
+        assert!(diff
+            .to_string()
+            .contains("\n  ~ plural English -> Suffix(\"i\")"));
+        assert!(ours.diff(&ours).is_empty());
+
+        let kept = ours.merge(&theirs, MergeStrategy::Ours);
+        assert_eq!(kept.plural(), &Plural::English);
+        assert_eq!(kept.capitalize("karl von rashid"), "Karl Von Rashid");
+        assert_eq!(kept.respellings().count(), 2);
+        let taken = ours.merge(&theirs, MergeStrategy::Theirs);
+        assert_eq!(taken.capitalize("karl von al-rashid"), "Karl von al-Rashid");
+        assert_eq!(
+            taken.diff(&theirs).respellings,
+            vec![Change::Removed(("th".into(), "t".into()))]
+        );
+        let summed = ours.merge(&theirs, MergeStrategy::Sum);
+        assert_eq!(summed.adjective_suffixes(), &["ic", "ine", "ian"]);
+        assert_eq!(summed.possessive(), &Possessive::Apostrophe);
+    }
+}
@@ -0,0 +1,189 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Email Address Local Part Generation
+
+use crate::normalize::{fold_letters, normalize};
+use crate::totro::TotroConfig;
+use rand::Rng;
+use std::collections::HashSet;
+
+/// Longest local part allowed by RFC 5321.
+pub const MAX_LOCAL_PART: usize = 64;
+
+/// Name pairs drawn before falling back to [`PLACEHOLDER_STEM`].
+const LOCAL_ATTEMPTS: usize = 100;
+
+/// Stem used when the configured tables never produce a usable local part.
+const PLACEHOLDER_STEM: &str = "user";
+
+/// Join `given` and `surname` into an RFC 5321 dot-atom local part, such as "essa.drake".
+///
+/// Letters are lowercased, accented letters are folded to their base letters, and anything
+/// other than ASCII letters and digits is dropped, so names like "D'Vára" become "dvara". Empty
+/// parts are skipped rather than leaving a stray dot, and the result is cut to [`MAX_LOCAL_PART`] octets.
+pub fn local_part(given: &str, surname: &str) -> String {
+    let parts: Vec<String> = [given, surname]
+        .iter()
+        .map(|part| {
+            fold_letters(part)
+                .chars()
+                .filter(char::is_ascii_alphanumeric)
+                .collect::<String>()
+        })
+        .filter(|part| !part.is_empty())
+        .collect();
+    let mut local = parts.join(".");
+    local.truncate(MAX_LOCAL_PART);
+    local.trim_end_matches('.').to_owned()
+}
+
+/// Generator of unique email local parts for seeding user databases.
+///
+/// Each local part joins a generated given name and surname with a dot. When a local part has
+/// already been handed out, a number is appended ("essa.drake2", "essa.drake3"), trimming the
+/// name if needed to stay within [`MAX_LOCAL_PART`].
+///
+/// ```rust
+/// use nominae::EmailLocalParts;
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let mut emails = EmailLocalParts::default();
+/// emails.reserve("admin");
+/// for _ in 0..3 {
+///     println!("{}@example.com", emails.generate(&mut rng));
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct EmailLocalParts {
+    given: TotroConfig,
+    surname: TotroConfig,
+    taken: HashSet<String>,
+}
+
+impl Default for EmailLocalParts {
+    fn default() -> EmailLocalParts {
+        EmailLocalParts {
+            given: TotroConfig::alphabetic().with_length(2, 4),
+            surname: TotroConfig::alphabetic().with_length(2, 5),
+            taken: HashSet::new(),
+        }
+    }
+}
+
+impl EmailLocalParts {
+    /// Set the configuration used for given names.
+    pub fn with_given(mut self, config: TotroConfig) -> EmailLocalParts {
+        self.given = config;
+        self
+    }
+
+    /// Set the configuration used for surnames.
+    pub fn with_surname(mut self, config: TotroConfig) -> EmailLocalParts {
+        self.surname = config;
+        self
+    }
+
+    /// Mark `local` as taken, such as an address already in the database.
+    ///
+    /// Local parts are compared in [`normalize`]d form, so reserving "J.Doe" also rules out
+    /// "jdoe".
+    pub fn reserve(&mut self, local: &str) {
+        self.taken.insert(normalize(local));
+    }
+
+    /// Whether `local`, or a local part that normalizes the same, has been generated or reserved.
+    pub fn is_taken(&self, local: &str) -> bool {
+        self.taken.contains(&normalize(local))
+    }
+
+    /// Generate a local part not handed out or reserved before.
+    ///
+    /// If 100 name pairs in a row leave nothing after [`local_part`], as with tables whose
+    /// tokens never fold to ASCII, the stem "user" is numbered instead ("user", "user2").
+    pub fn generate<T: Rng + ?Sized>(&mut self, rng: &mut T) -> String {
+        let base = (0..LOCAL_ATTEMPTS)
+            .map(|_| local_part(&self.given.generate(rng), &self.surname.generate(rng)))
+            .find(|base| !base.is_empty())
+            .unwrap_or_else(|| {
+                event!(trace, "no usable local part, using the placeholder stem");
+                PLACEHOLDER_STEM.to_owned()
+            });
+        let mut local = base.clone();
+        let mut counter = 2;
+        while self.is_taken(&local) {
+            event!(trace, %local, "local part taken");
+            let suffix = counter.to_string();
+            let mut stem = base.clone();
+            stem.truncate(MAX_LOCAL_PART - suffix.len());
+            local = format!("{}{}", stem.trim_end_matches('.'), suffix);
+            counter += 1;
+        }
+        self.reserve(&local);
+        local
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{local_part, EmailLocalParts, MAX_LOCAL_PART};
+    use crate::table::{Table, Token, AIW};
+    use crate::totro::TotroConfig;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_local_part() {
+        assert_eq!(local_part("Essa", "Drake"), "essa.drake");
+        assert_eq!(local_part("D'Vara", "'"), "dvara");
+        assert_eq!(local_part("José", "Müller"), "jose.muller");
+        let long = "a".repeat(80);
+        assert_eq!(local_part(&long, "b").len(), MAX_LOCAL_PART);
+        assert!(!local_part(&"a".repeat(63), "b").ends_with('.'));
+    }
+
+    #[test]
+    fn test_collision_suffixing() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let tiny = TotroConfig::alphabetic().with_length(1, 1);
+        let mut emails = EmailLocalParts::default()
+            .with_given(tiny.clone())
+            .with_surname(tiny);
+        let locals: Vec<String> = (0..200).map(|_| emails.generate(&mut rng)).collect();
+        for local in &locals {
+            assert_eq!(locals.iter().filter(|other| *other == local).count(), 1);
+            assert!(!local.starts_with('.') && !local.ends_with('.') && !local.contains(".."));
+        }
+        assert!(locals.iter().any(|local| local.ends_with(char::is_numeric)));
+        emails.reserve("Essa.Drake");
+        assert!(emails.is_taken("essadrake"));
+    }
+
+    #[test]
+    fn test_placeholder_stem() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let vowels = Table::new(vec![Token::new("а", AIW), Token::new("о", AIW)]).unwrap();
+        let consonants = Table::new(vec![Token::new("в", AIW), Token::new("л", AIW)]).unwrap();
+        let cyrillic = TotroConfig::new(vowels, consonants);
+        let mut emails = EmailLocalParts::default()
+            .with_given(cyrillic.clone())
+            .with_surname(cyrillic);
+        assert_eq!(emails.generate(&mut rng), "user");
+        assert_eq!(emails.generate(&mut rng), "user2");
+    }
+}
@@ -0,0 +1,185 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Epithets and Honorifics
+
+use crate::stable::below;
+use rand::Rng;
+use std::fmt;
+
+const ADJECTIVES: [&str; 16] = [
+    "Bold", "Cruel", "Wise", "Fair", "Grim", "Unbroken", "Pale", "Red", "Silent", "Young", "Old",
+    "Just", "Mad", "Black", "Golden", "Wanderer",
+];
+
+const TITLES: [&str; 10] = [
+    "Bane", "Scourge", "Slayer", "Breaker", "Warden", "Hammer", "Terror", "Hope", "Shield", "Heir",
+];
+
+const OBJECTS: [&str; 14] = [
+    "Serpents",
+    "Kings",
+    "the North",
+    "Storms",
+    "the Deep",
+    "a Thousand Ships",
+    "Giants",
+    "the Old Gods",
+    "Dragons",
+    "the Marches",
+    "Chains",
+    "the Dawn",
+    "Wolves",
+    "the Fallen",
+];
+
+const COUNTS: [&str; 4] = ["Twice", "Thrice", "Ever", "Once"];
+
+const PARTICIPLES: [&str; 10] = [
+    "Crowned", "Blessed", "Cursed", "Born", "Slain", "Exiled", "Forsworn", "Scarred", "Wed",
+    "Risen",
+];
+
+/// Shape of an [`Epithet`], which decides how it is joined to a name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EpithetForm {
+    /// An article and adjective, as in "Velmar the Bold".
+    Adjective,
+    /// A title of something, as in "Velmar, Bane of Serpents".
+    Title,
+    /// A counted participle, as in "Velmar Thrice-Crowned".
+    Compound,
+}
+
+/// A generated epithet.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Epithet {
+    /// Shape of the epithet.
+    pub form: EpithetForm,
+    /// The epithet alone, such as "the Bold".
+    pub text: String,
+}
+
+impl Epithet {
+    /// `name` followed by this epithet, with a comma before titles.
+    pub fn attach(&self, name: &str) -> String {
+        match self.form {
+            EpithetForm::Title => format!("{}, {}", name, self.text),
+            EpithetForm::Adjective | EpithetForm::Compound => format!("{} {}", name, self.text),
+        }
+    }
+}
+
+impl fmt::Display for Epithet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+/// Generator for [`Epithet`]s for legendary characters and boss monsters.
+///
+/// ```rust
+/// use nominae::{EpithetGenerator, Name};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let epithet = EpithetGenerator::default().generate(&mut rng);
+/// println!("{}", Name::new("Velmar").with_epithet(epithet));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EpithetGenerator {
+    forms: Vec<(EpithetForm, u32)>,
+}
+
+impl Default for EpithetGenerator {
+    fn default() -> EpithetGenerator {
+        EpithetGenerator {
+            forms: vec![
+                (EpithetForm::Adjective, 2),
+                (EpithetForm::Title, 2),
+                (EpithetForm::Compound, 1),
+            ],
+        }
+    }
+}
+
+impl EpithetGenerator {
+    /// Generate epithets in a mix of forms, each chosen in proportion to its weight.
+    ///
+    /// Panics if every weight is zero.
+    pub fn with_forms(mut self, forms: &[(EpithetForm, u32)]) -> EpithetGenerator {
+        assert!(
+            forms.iter().any(|&(_, weight)| weight > 0),
+            "at least one form must have a weight above zero"
+        );
+        self.forms = forms.to_vec();
+        self
+    }
+
+    /// Generate an epithet.
+    pub fn generate<T: Rng + ?Sized>(&self, rng: &mut T) -> Epithet {
+        let total: u64 = self.forms.iter().map(|&(_, weight)| weight as u64).sum();
+        let mut roll = below(rng, total);
+        let form = self
+            .forms
+            .iter()
+            .find(|&&(_, weight)| match roll.checked_sub(weight as u64) {
+                Some(rest) => {
+                    roll = rest;
+                    false
+                }
+                None => true,
+            })
+            .map(|&(form, _)| form)
+            .expect("roll is below the total weight");
+        let text = match form {
+            EpithetForm::Adjective => format!("the {}", pick(&ADJECTIVES, rng)),
+            EpithetForm::Title => format!("{} of {}", pick(&TITLES, rng), pick(&OBJECTS, rng)),
+            EpithetForm::Compound => {
+                format!("{}-{}", pick(&COUNTS, rng), pick(&PARTICIPLES, rng))
+            }
+        };
+        Epithet { form, text }
+    }
+}
+
+fn pick<'a, T: Rng + ?Sized>(words: &[&'a str], rng: &mut T) -> &'a str {
+    words[below(rng, words.len() as u64) as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Epithet, EpithetForm, EpithetGenerator};
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_generate() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let titles = EpithetGenerator::default().with_forms(&[(EpithetForm::Title, 1)]);
+        for _ in 0..20 {
+            let epithet = titles.generate(&mut rng);
+            assert_eq!(epithet.form, EpithetForm::Title);
+            assert!(epithet.text.contains(" of "), "{}", epithet);
+        }
+        let bold = Epithet {
+            form: EpithetForm::Adjective,
+            text: "the Bold".into(),
+        };
+        assert_eq!(bold.attach("Velmar"), "Velmar the Bold");
+    }
+}
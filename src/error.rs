@@ -0,0 +1,192 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Crate-Wide Errors
+
+use crate::table::TableError;
+use alloc::format;
+use alloc::string::String;
+use core::fmt;
+
+/// Errors produced anywhere in the crate.
+///
+/// Messages name the constraint that failed and, for exhausted retries, how many attempts were
+/// made, so they can be shown to end users or logged as is.
+///
+/// ```rust
+/// use nominae::NominaeError;
+///
+/// let error = NominaeError::RetriesExhausted {
+///     constraint: "starts with 'Q'".into(),
+///     attempts: 1000,
+/// };
+/// assert_eq!(
+///     error.to_string(),
+///     "gave up on constraint starts with 'Q' after 1000 attempts"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NominaeError {
+    /// A length range had its minimum above its maximum.
+    InvalidRange {
+        /// Requested minimum.
+        min: usize,
+        /// Requested maximum.
+        max: usize,
+    },
+    /// One-token names are within a length range starting at `min`, but cannot be generated
+    /// since no token may both begin and end a word. Generation falls back to names of
+    /// `shortest` tokens whenever it draws a length of one.
+    MinimumLength {
+        /// Requested minimum length.
+        min: usize,
+        /// Shortest length that can be generated.
+        shortest: usize,
+    },
+    /// A constraint can never be satisfied by the configured tables.
+    ImpossibleConstraint(String),
+    /// Generation gave up on a constraint after drawing `attempts` candidates.
+    RetriesExhausted {
+        /// Description of the constraint that was never met.
+        constraint: String,
+        /// Number of candidates drawn before giving up.
+        attempts: usize,
+    },
+    /// A token table failed validation.
+    Table(TableError),
+    /// A profile version is unknown to this release of the crate.
+    UnknownProfile(String),
+    /// A serialized model could not be decoded.
+    InvalidModel(String),
+    /// A name pattern could not be parsed.
+    InvalidPattern(String),
+    /// Saved generator state is inconsistent and cannot be restored.
+    InvalidState(String),
+    /// A setting, such as a probability, was outside its allowed range.
+    OutOfRange(String),
+    /// A style pack could not be loaded.
+    Pack {
+        /// One-based line the problem was found on, if known.
+        line: Option<usize>,
+        /// Description of the problem.
+        message: String,
+    },
+}
+
+impl fmt::Display for NominaeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NominaeError::InvalidRange { min, max } => write!(
+                f,
+                "invalid range: min must be less than or equal to max: {} <= {}",
+                min, max
+            ),
+            NominaeError::MinimumLength { min, shortest } => write!(
+                f,
+                "one-token names cannot be generated for a minimum length of {}: no token may \
+                 both begin and end a word, so names of {} tokens are generated instead",
+                min, shortest
+            ),
+            NominaeError::ImpossibleConstraint(constraint) => {
+                write!(f, "constraint {} can never be satisfied", constraint)
+            }
+            NominaeError::RetriesExhausted {
+                constraint,
+                attempts,
+            } => write!(
+                f,
+                "gave up on constraint {} after {} attempts",
+                constraint, attempts
+            ),
+            NominaeError::Table(error) => write!(f, "invalid table: {}", error),
+            NominaeError::InvalidModel(message) => write!(f, "invalid model: {}", message),
+            NominaeError::InvalidPattern(message) => write!(f, "invalid pattern: {}", message),
+            NominaeError::InvalidState(message) => write!(f, "invalid saved state: {}", message),
+            NominaeError::OutOfRange(message) => write!(f, "out of range: {}", message),
+            NominaeError::UnknownProfile(version) => {
+                write!(f, "unknown profile version {}", version)
+            }
+            NominaeError::Pack {
+                line: Some(line),
+                message,
+            } => write!(f, "invalid pack at line {}: {}", line, message),
+            NominaeError::Pack {
+                line: None,
+                message,
+            } => write!(f, "invalid pack: {}", message),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NominaeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            NominaeError::Table(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Check that the probability named `setting` is within `0..=1`.
+pub(crate) fn check_probability(setting: &str, probability: f64) -> Result<(), NominaeError> {
+    if (0.0..=1.0).contains(&probability) {
+        Ok(())
+    } else {
+        Err(NominaeError::OutOfRange(format!(
+            "{} {} must be within 0..=1",
+            setting, probability
+        )))
+    }
+}
+
+impl From<TableError> for NominaeError {
+    fn from(error: TableError) -> NominaeError {
+        NominaeError::Table(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NominaeError;
+    use crate::table::{TableError, EOW};
+    use std::error::Error;
+
+    #[test]
+    fn test_display() {
+        let range = NominaeError::InvalidRange { min: 5, max: 2 };
+        assert_eq!(
+            range.to_string(),
+            "invalid range: min must be less than or equal to max: 5 <= 2"
+        );
+        let pack = NominaeError::Pack {
+            line: Some(3),
+            message: "unknown section".into(),
+        };
+        assert_eq!(pack.to_string(), "invalid pack at line 3: unknown section");
+    }
+
+    #[test]
+    fn test_from_table_error() {
+        let error: NominaeError = TableError::UncoveredPosition(EOW).into();
+        assert_eq!(
+            error.to_string(),
+            "invalid table: no token can be placed at the end of a word"
+        );
+        assert!(error.source().is_some());
+    }
+}
@@ -0,0 +1,242 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Trained Model Evaluation
+
+use crate::markov::Markov;
+use rand::Rng;
+
+/// Quality metrics of a trained model.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Evaluation {
+    /// Per-character perplexity on held-out names; lower means the model predicts unseen
+    /// names of the corpus better. Infinite when a held-out transition has no probability.
+    pub perplexity: f64,
+    /// Share of sampled names that are not training names, from 0 to 1.
+    pub novelty: f64,
+    /// Average similarity of each sampled name to its closest training name, from 0 (nothing
+    /// in common) to 1 (a copy), measured by normalized edit distance.
+    pub similarity: f64,
+}
+
+/// Settings for evaluating [`Markov`] models, so different orders can be compared by numbers
+/// rather than by eyeballing samples.
+///
+/// ```rust
+/// use nominae::Evaluator;
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let corpus = ["Velmar", "Velda", "Maren", "Doran", "Veris", "Meldar", "Daren", "Vorin"];
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let evaluator = Evaluator::default().with_samples(50);
+/// for order in 1..=3 {
+///     let evaluation = evaluator.cross_validate(order, &corpus, 4, &mut rng);
+///     println!("order {}: {:.2} perplexity", order, evaluation.perplexity);
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Evaluator {
+    smoothing: f64,
+    samples: usize,
+}
+
+impl Default for Evaluator {
+    fn default() -> Evaluator {
+        Evaluator {
+            smoothing: 0.1,
+            samples: 200,
+        }
+    }
+}
+
+impl Evaluator {
+    /// Set the additive smoothing applied to counts when scoring held-out names.
+    pub fn with_smoothing(mut self, smoothing: f64) -> Evaluator {
+        assert!(smoothing >= 0.0, "smoothing must not be negative");
+        self.smoothing = smoothing;
+        self
+    }
+
+    /// Set the number of names sampled to measure novelty and similarity.
+    pub fn with_samples(mut self, samples: usize) -> Evaluator {
+        assert!(samples > 0, "at least one sample is required");
+        self.samples = samples;
+        self
+    }
+
+    /// Evaluate `model` against `held_out` names it was not trained on.
+    pub fn evaluate<S, T>(&self, model: &Markov, held_out: &[S], rng: &mut T) -> Evaluation
+    where
+        S: AsRef<str>,
+        T: Rng + ?Sized,
+    {
+        let (log_likelihood, transitions) = self.score(model, held_out);
+        let (novelty, similarity) = self.sample(model, rng);
+        Evaluation {
+            perplexity: perplexity(log_likelihood, transitions),
+            novelty,
+            similarity,
+        }
+    }
+
+    /// Train a model of `order` on all but one of `folds` slices of `names` in turn and
+    /// evaluate it on the slice left out.
+    ///
+    /// Name `i` belongs to fold `i % folds`, so shuffle `names` first if they are sorted.
+    /// Perplexity is pooled over every held-out transition; novelty and similarity are averaged
+    /// over the folds.
+    pub fn cross_validate<S, T>(
+        &self,
+        order: usize,
+        names: &[S],
+        folds: usize,
+        rng: &mut T,
+    ) -> Evaluation
+    where
+        S: AsRef<str>,
+        T: Rng + ?Sized,
+    {
+        assert!(
+            folds >= 2 && folds <= names.len(),
+            "folds must be within 2..=names.len()"
+        );
+        let (mut log_likelihood, mut transitions) = (0.0, 0);
+        let (mut novelty, mut similarity) = (0.0, 0.0);
+        for fold in 0..folds {
+            let (held_out, training): (Vec<_>, Vec<_>) = names
+                .iter()
+                .enumerate()
+                .partition(|(idx, _)| idx % folds == fold);
+            let held_out: Vec<&str> = held_out.iter().map(|(_, name)| name.as_ref()).collect();
+            let mut model = Markov::new(order);
+            model.update(training.iter().map(|(_, name)| name.as_ref()));
+            let (fold_likelihood, fold_transitions) = self.score(&model, &held_out);
+            log_likelihood += fold_likelihood;
+            transitions += fold_transitions;
+            let (fold_novelty, fold_similarity) = self.sample(&model, rng);
+            novelty += fold_novelty;
+            similarity += fold_similarity;
+        }
+        Evaluation {
+            perplexity: perplexity(log_likelihood, transitions),
+            novelty: novelty / folds as f64,
+            similarity: similarity / folds as f64,
+        }
+    }
+
+    /// Total log likelihood of `names` and the number of transitions scored.
+    fn score<S: AsRef<str>>(&self, model: &Markov, names: &[S]) -> (f64, usize) {
+        names.iter().fold((0.0, 0), |(total, count), name| {
+            let (likelihood, transitions) = model.log_likelihood(name.as_ref(), self.smoothing);
+            (total + likelihood, count + transitions)
+        })
+    }
+
+    /// Novelty rate and average closest-match similarity of sampled names.
+    fn sample<T: Rng + ?Sized>(&self, model: &Markov, rng: &mut T) -> (f64, f64) {
+        let training: Vec<Vec<char>> = model.names().map(|name| name.chars().collect()).collect();
+        let (mut novel, mut similarity) = (0, 0.0);
+        for _ in 0..self.samples {
+            let name = model.generate(rng);
+            if !model.is_training_name(&name) {
+                novel += 1;
+            }
+            let name: Vec<char> = name.to_lowercase().chars().collect();
+            similarity += training
+                .iter()
+                .map(|other| closeness(&name, other))
+                .fold(0.0, f64::max);
+        }
+        (
+            novel as f64 / self.samples as f64,
+            similarity / self.samples as f64,
+        )
+    }
+}
+
+fn perplexity(log_likelihood: f64, transitions: usize) -> f64 {
+    if transitions == 0 {
+        return f64::INFINITY;
+    }
+    (-log_likelihood / transitions as f64).exp()
+}
+
+/// One minus the Levenshtein distance between `a` and `b` divided by the longer length.
+pub(crate) fn closeness(a: &[char], b: &[char]) -> f64 {
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, &ca) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + (ca != cb) as usize;
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    1.0 - row[b.len()] as f64 / longest as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{closeness, Evaluator};
+    use crate::markov::Markov;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_closeness() {
+        let chars = |text: &str| text.chars().collect::<Vec<_>>();
+        assert_eq!(closeness(&chars("velmar"), &chars("velmar")), 1.0);
+        assert_eq!(closeness(&chars("abcd"), &chars("wxyz")), 0.0);
+        assert_eq!(
+            closeness(&chars("velmar"), &chars("velmor")),
+            1.0 - 1.0 / 6.0
+        );
+        assert_eq!(closeness(&chars(""), &chars("")), 1.0);
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut copycat = Markov::new(4);
+        copycat.update(&["Velmar"]);
+        let strict = Evaluator::default().with_smoothing(0.0);
+        let evaluation = strict.evaluate(&copycat, &["Velmar"], &mut rng);
+        assert_eq!(evaluation.novelty, 0.0);
+        assert_eq!(evaluation.similarity, 1.0);
+        assert!(
+            (evaluation.perplexity - 1.0).abs() < 1e-9,
+            "{:?}",
+            evaluation
+        );
+        assert!(strict
+            .evaluate(&copycat, &["Doran"], &mut rng)
+            .perplexity
+            .is_infinite());
+        let corpus = [
+            "velmar", "velda", "maren", "doran", "veris", "meldar", "daren", "vorin",
+        ];
+        let evaluation = Evaluator::default().cross_validate(2, &corpus, 4, &mut rng);
+        assert!(evaluation.perplexity.is_finite() && evaluation.perplexity > 1.0);
+        assert!((0.0..=1.0).contains(&evaluation.novelty));
+        assert!((0.0..=1.0).contains(&evaluation.similarity));
+    }
+}
@@ -0,0 +1,232 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Historical Sound Change
+
+use std::fmt;
+
+/// Sound changes applied by [`Evolution::default`], in historical order.
+const SOUND_CHANGES: [(&str, &str, &str); 9] = [
+    ("monophthongization", "ae", "e"),
+    ("monophthongization", "ai", "e"),
+    ("spirantization", "ph", "f"),
+    ("degemination", "ll", "l"),
+    ("degemination", "nn", "n"),
+    ("degemination", "rr", "r"),
+    ("degemination", "ss", "s"),
+    ("apocope", "a$", ""),
+    ("apocope", "e$", ""),
+];
+
+/// One stage of a [`Derivation`]: the rule applied and the name it produced.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step {
+    /// Label of the rule, such as "apocope".
+    pub rule: String,
+    /// The name after the rule was applied.
+    pub name: String,
+}
+
+/// The history of a name from its original form to its current one.
+///
+/// Displays as each form in turn with the rule that produced it, as in
+/// `Vaelmarra > Velmarra (monophthongization) > Velmara (degemination) > Velmar (apocope)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Derivation {
+    base: String,
+    steps: Vec<Step>,
+}
+
+impl Derivation {
+    /// The original form of the name.
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+
+    /// Rules that changed the name, in the order they applied.
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    /// The current form of the name.
+    pub fn name(&self) -> &str {
+        self.steps.last().map_or(&self.base, |step| &step.name)
+    }
+
+    /// Earlier forms of the name from oldest to most recent, for lore such as "this city was
+    /// once called Vaelmarra".
+    pub fn former_names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.base.as_str())
+            .chain(self.steps.iter().map(|step| step.name.as_str()))
+            .take(self.steps.len())
+    }
+}
+
+impl fmt::Display for Derivation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.base)?;
+        for step in &self.steps {
+            write!(f, " > {} ({})", step.name, step.rule)?;
+        }
+        Ok(())
+    }
+}
+
+/// Ordered sound changes that age a name, as a language changes over centuries.
+///
+/// Each rule replaces every occurrence of a letter sequence. A leading `^` or trailing `$`
+/// anchors the sequence to the start or end of the name, and a rule is skipped when it would
+/// leave the name empty. Rules are matched ignoring case, and a capitalized name stays
+/// capitalized.
+///
+/// ```rust
+/// use nominae::Evolution;
+///
+/// let evolution = Evolution::default().rule("lenition", "^k", "h");
+/// let derivation = evolution.derive("Kaelmarra");
+/// assert_eq!(derivation.name(), "Helmar");
+/// println!("{}", derivation);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Evolution {
+    rules: Vec<(String, String, String)>,
+}
+
+impl Default for Evolution {
+    fn default() -> Evolution {
+        SOUND_CHANGES
+            .iter()
+            .fold(Evolution::new(), |evolution, &(label, from, to)| {
+                evolution.rule(label, from, to)
+            })
+    }
+}
+
+impl Evolution {
+    /// An evolution without any rules.
+    pub fn new() -> Evolution {
+        Evolution { rules: Vec::new() }
+    }
+
+    /// Add a rule labeled `label` replacing `from` with `to`, applied after the existing rules.
+    pub fn rule(mut self, label: &str, from: &str, to: &str) -> Evolution {
+        self.rules
+            .push((label.to_owned(), from.to_lowercase(), to.to_lowercase()));
+        self
+    }
+
+    /// Rules as their label, pattern and replacement, in the order they apply.
+    pub fn rules(&self) -> impl Iterator<Item = (&str, &str, &str)> {
+        self.rules
+            .iter()
+            .map(|(label, from, to)| (label.as_str(), from.as_str(), to.as_str()))
+    }
+
+    /// The current form of `name`.
+    pub fn evolve(&self, name: &str) -> String {
+        self.derive(name).name().to_owned()
+    }
+
+    /// The current form of `name` along with every form it passed through.
+    ///
+    /// Consecutive rules sharing a label are reported as one step.
+    pub fn derive(&self, name: &str) -> Derivation {
+        let capitalized = name.starts_with(char::is_uppercase);
+        let mut current = name.to_lowercase();
+        let mut steps: Vec<Step> = Vec::new();
+        for (label, from, to) in &self.rules {
+            let next = apply(&current, from, to);
+            if next == current || next.is_empty() {
+                continue;
+            }
+            current = next;
+            let mut name = current.clone();
+            if capitalized {
+                capitalize(&mut name);
+            }
+            match steps.last_mut() {
+                Some(step) if step.rule == *label => step.name = name,
+                _ => steps.push(Step {
+                    rule: label.clone(),
+                    name,
+                }),
+            }
+        }
+        Derivation {
+            base: name.to_owned(),
+            steps,
+        }
+    }
+}
+
+/// Replace `from` with `to` in `text`, honoring `^` and `$` anchors.
+fn apply(text: &str, from: &str, to: &str) -> String {
+    if let Some(prefix) = from.strip_prefix('^') {
+        match text.strip_prefix(prefix) {
+            Some(rest) if !prefix.is_empty() => format!("{}{}", to, rest),
+            _ => text.to_owned(),
+        }
+    } else if let Some(suffix) = from.strip_suffix('$') {
+        match text.strip_suffix(suffix) {
+            Some(rest) if !suffix.is_empty() => format!("{}{}", rest, to),
+            _ => text.to_owned(),
+        }
+    } else if from.is_empty() {
+        text.to_owned()
+    } else {
+        text.replace(from, to)
+    }
+}
+
+/// Uppercase the first letter of `name`.
+fn capitalize(name: &mut String) {
+    if let Some(first) = name.chars().next() {
+        let upper: String = first.to_uppercase().collect();
+        name.replace_range(..first.len_utf8(), &upper);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply, Evolution};
+
+    #[test]
+    fn test_apply() {
+        assert_eq!(apply("velmara", "a$", ""), "velmar");
+        assert_eq!(apply("karak", "^k", "h"), "harak");
+        assert_eq!(apply("karak", "k", "g"), "garag");
+        assert_eq!(apply("karak", "^", "x"), "karak");
+    }
+
+    #[test]
+    fn test_derive() {
+        let derivation = Evolution::default().derive("Vaelmarra");
+        assert_eq!(derivation.name(), "Velmar");
+        assert_eq!(
+            derivation.to_string(),
+            "Vaelmarra > Velmarra (monophthongization) > Velmara (degemination) > Velmar (apocope)"
+        );
+        assert_eq!(
+            derivation.former_names().collect::<Vec<_>>(),
+            vec!["Vaelmarra", "Velmarra", "Velmara"]
+        );
+        let unchanged = Evolution::default().derive("Doran");
+        assert_eq!(unchanged.name(), "Doran");
+        assert!(unchanged.steps().is_empty());
+        assert_eq!(unchanged.former_names().count(), 0);
+        assert_eq!(Evolution::default().evolve("a"), "a");
+    }
+}
@@ -0,0 +1,215 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Generation Explanations and Constraints
+
+use crate::error::NominaeError;
+use crate::table::Token;
+use crate::totro::{assemble, TotroConfig};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use rand::Rng;
+
+/// Candidates drawn by [`TotroConfig::generate_where`] before giving up.
+const CONSTRAINT_ATTEMPTS: usize = 1000;
+
+/// A generated name together with the tokens it was assembled from.
+///
+/// Displays as the name followed by its tokens and their attributes, as in
+/// `Karak = k[hard] + a + r + a + k[hard]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Explanation<'a> {
+    name: String,
+    tokens: Vec<&'a Token>,
+}
+
+impl<'a> Explanation<'a> {
+    /// The generated name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Tokens of the name, in order.
+    pub fn tokens(&self) -> &[&'a Token] {
+        &self.tokens
+    }
+
+    /// Number of tokens that have the attribute `key`.
+    pub fn count(&self, key: &str) -> usize {
+        self.tokens.iter().filter(|token| token.has(key)).count()
+    }
+
+    /// Consume the explanation, returning the name.
+    pub fn into_name(self) -> String {
+        self.name
+    }
+}
+
+impl fmt::Display for Explanation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} =", self.name)?;
+        for (idx, token) in self.tokens.iter().enumerate() {
+            f.write_str(if idx == 0 { " " } else { " + " })?;
+            f.write_str(token.text())?;
+            let mut attributes = token.attributes().peekable();
+            if attributes.peek().is_some() {
+                f.write_str("[")?;
+                for (idx, (key, value)) in attributes.enumerate() {
+                    if idx > 0 {
+                        f.write_str(", ")?;
+                    }
+                    match value.to_string() {
+                        value if value.is_empty() => f.write_str(key)?,
+                        value => write!(f, "{}={}", key, value)?,
+                    }
+                }
+                f.write_str("]")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TotroConfig {
+    /// Generate a name along with the tokens it was assembled from.
+    pub fn explain<T: Rng + ?Sized>(&self, rng: &mut T) -> Explanation<'_> {
+        let (min, max) = self.length();
+        self.explain_within(min, max, rng)
+    }
+
+    /// Generate a name of `min..max` tokens along with the tokens it was assembled from.
+    pub(crate) fn explain_within<T: Rng + ?Sized>(
+        &self,
+        min: u8,
+        max: u8,
+        rng: &mut T,
+    ) -> Explanation<'_> {
+        let mut tokens = Vec::new();
+        assemble(self, min, max, None, &mut tokens, rng);
+        let mut name: String = tokens.iter().map(|token| token.text()).collect();
+        if let Some(first) = name.get_mut(0..1) {
+            first.make_ascii_uppercase();
+        }
+        Explanation { name, tokens }
+    }
+
+    /// Generate a name whose explanation satisfies `constraint`, such as a limit on tokens with
+    /// a given attribute.
+    ///
+    /// Gives up with [`NominaeError::RetriesExhausted`] after 1000 rejected candidates.
+    ///
+    /// ```rust
+    /// use nominae::{Attribute, TotroConfig};
+    /// use rand::SeedableRng;
+    /// use rand::rngs::SmallRng;
+    ///
+    /// let mut config = TotroConfig::default();
+    /// for hard in ["k", "g", "t", "d"].iter() {
+    ///     config.consonants_mut().set_attribute(hard, "hard", Attribute::Flag).unwrap();
+    /// }
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    /// let name = config
+    ///     .generate_where(|explanation| explanation.count("hard") <= 1, &mut rng)
+    ///     .unwrap();
+    /// println!("{}", name);
+    /// ```
+    pub fn generate_where<F, T>(
+        &self,
+        mut constraint: F,
+        rng: &mut T,
+    ) -> Result<String, NominaeError>
+    where
+        F: FnMut(&Explanation<'_>) -> bool,
+        T: Rng + ?Sized,
+    {
+        for _ in 0..CONSTRAINT_ATTEMPTS {
+            let explanation = self.explain(rng);
+            if constraint(&explanation) {
+                return Ok(explanation.into_name());
+            }
+            event!(trace, name = explanation.name(), "rejected by constraint");
+        }
+        Err(NominaeError::RetriesExhausted {
+            constraint: "custom constraint".into(),
+            attempts: CONSTRAINT_ATTEMPTS,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Explanation;
+    use crate::error::NominaeError;
+    use crate::table::{Attribute, Token, AIW};
+    use crate::totro::TotroConfig;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_explain() {
+        let config = TotroConfig::default();
+        let explanation = config.explain(&mut SmallRng::seed_from_u64(7));
+        let text: String = explanation.tokens().iter().map(|t| t.text()).collect();
+        assert!(explanation.name().eq_ignore_ascii_case(&text));
+        assert_eq!(
+            explanation.name(),
+            config.generate(&mut SmallRng::seed_from_u64(7))
+        );
+        let k = Token::new("k", AIW)
+            .with_tag("hard")
+            .with_attribute("stress", 2);
+        let a = Token::new("a", AIW);
+        let explanation = Explanation {
+            name: "Kak".into(),
+            tokens: vec![&k, &a, &k],
+        };
+        assert_eq!(explanation.count("hard"), 2);
+        assert_eq!(
+            explanation.to_string(),
+            "Kak = k[hard, stress=2] + a + k[hard, stress=2]"
+        );
+    }
+
+    #[test]
+    fn test_generate_where() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut config = TotroConfig::default();
+        let rhotic: Vec<String> = config
+            .consonants()
+            .tokens()
+            .iter()
+            .filter(|token| token.text().contains('r'))
+            .map(|token| token.text().to_owned())
+            .collect();
+        for text in &rhotic {
+            config
+                .consonants_mut()
+                .set_attribute(text, "rhotic", Attribute::Flag)
+                .unwrap();
+        }
+        for _ in 0..100 {
+            let name = config
+                .generate_where(|explanation| explanation.count("rhotic") == 0, &mut rng)
+                .unwrap();
+            assert!(!name.to_lowercase().contains('r'), "{}", name);
+        }
+        assert!(matches!(
+            config.generate_where(|_| false, &mut rng),
+            Err(NominaeError::RetriesExhausted { attempts: 1000, .. })
+        ));
+    }
+}
@@ -0,0 +1,112 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Streaming Export
+
+use crate::generator::NameGenerator;
+use crate::persona::csv_field;
+use rand::RngCore;
+use std::io::{self, Write};
+
+/// How [`GeneratorExt::generate_to_writer`](crate::GeneratorExt::generate_to_writer) lays
+/// out the names it writes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ExportFormat {
+    /// One bare name per line.
+    Lines,
+    /// CSV with a `name` header row, quoting names that need it.
+    Csv,
+    /// One `{"name":"..."}` JSON object per line.
+    JsonLines,
+}
+
+/// Write `n` names from `generator` to `writer` in `format`, one at a time.
+pub(crate) fn write_names<G: NameGenerator + ?Sized, W: Write>(
+    generator: &G,
+    mut writer: W,
+    n: usize,
+    format: ExportFormat,
+    rng: &mut dyn RngCore,
+) -> io::Result<()> {
+    if format == ExportFormat::Csv {
+        writeln!(writer, "name")?;
+    }
+    for _ in 0..n {
+        let name = generator.generate(rng);
+        match format {
+            ExportFormat::Lines => writeln!(writer, "{}", name)?,
+            ExportFormat::Csv => writeln!(writer, "{}", csv_field(&name))?,
+            ExportFormat::JsonLines => writeln!(writer, "{{\"name\":{}}}", json_string(&name))?,
+        }
+    }
+    writer.flush()
+}
+
+/// Quote `text` as a JSON string.
+fn json_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{json_string, write_names, ExportFormat};
+    use crate::totro::TotroConfig;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_write_names() {
+        let config = TotroConfig::default();
+        let mut rng = SmallRng::seed_from_u64(0);
+        let expected: Vec<String> = (0..3).map(|_| config.generate(&mut rng)).collect();
+        let export = |format| {
+            let mut out = Vec::new();
+            write_names(
+                &config,
+                &mut out,
+                3,
+                format,
+                &mut SmallRng::seed_from_u64(0),
+            )
+            .unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(export(ExportFormat::Lines), expected.join("\n") + "\n");
+        let csv = export(ExportFormat::Csv);
+        assert_eq!(csv.lines().next(), Some("name"));
+        assert_eq!(csv.lines().count(), 4);
+        let json = export(ExportFormat::JsonLines);
+        assert_eq!(
+            json.lines().next().unwrap(),
+            format!("{{\"name\":\"{}\"}}", expected[0])
+        );
+        assert_eq!(json_string("a\"b\\\u{1}"), "\"a\\\"b\\\\\\u0001\"");
+    }
+}
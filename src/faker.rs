@@ -0,0 +1,103 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Faker Integration
+//!
+//! With the `fake` feature, every generator in the crate is a [`fake`] configuration for
+//! `String`, and [`Name`] is [`Dummy`] for [`Faker`], so test data built with `fake` can use
+//! nominae presets like any other faker:
+//!
+//! ```rust
+//! use fake::{Fake, Faker};
+//! use nominae::{presets, Name, TotroConfig};
+//!
+//! let pet: String = presets::pet().fake();
+//! let npc: String = TotroConfig::default().fake();
+//! let name: Name = Faker.fake();
+//! assert!(name.regenerate().is_some());
+//! # let _ = (pet, npc);
+//! ```
+
+use crate::full_name::FullNameGenerator;
+use crate::gender::Gendered;
+use crate::generator::NameGenerator;
+use crate::guided::Guided;
+use crate::markov::Markov;
+use crate::mixture::Mixture;
+use crate::name::Name;
+use crate::pattern::Pattern;
+use crate::totro::{Totro, TotroConfig};
+use fake::{Dummy, Faker};
+use rand::Rng;
+
+/// Implement `Dummy<generator>` for `String` through [`NameGenerator`].
+macro_rules! dummy_names {
+    ($($generator:ty),*) => {
+        $(
+            impl Dummy<$generator> for String {
+                fn dummy_with_rng<R: Rng + ?Sized>(generator: &$generator, rng: &mut R) -> String {
+                    let mut rng = rng;
+                    NameGenerator::generate(generator, &mut rng)
+                }
+            }
+        )*
+    };
+}
+
+dummy_names!(
+    Totro,
+    TotroConfig,
+    Pattern,
+    Markov,
+    FullNameGenerator,
+    Gendered,
+    Guided,
+    Mixture
+);
+
+/// A name from the default configuration, drawn from its own seed so that it can be
+/// [regenerated](Name::regenerate).
+impl Dummy<Faker> for Name {
+    fn dummy_with_rng<R: Rng + ?Sized>(_: &Faker, rng: &mut R) -> Name {
+        TotroConfig::default().generate_name(rng.gen())
+    }
+}
+
+/// A name from `config`, drawn from its own seed so that it can be
+/// [regenerated](Name::regenerate_with).
+impl Dummy<TotroConfig> for Name {
+    fn dummy_with_rng<R: Rng + ?Sized>(config: &TotroConfig, rng: &mut R) -> Name {
+        config.generate_name(rng.gen())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::name::Name;
+    use crate::totro::TotroConfig;
+    use fake::Fake;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_fake() {
+        let config = TotroConfig::default();
+        let name: String = config.fake_with_rng(&mut SmallRng::seed_from_u64(0));
+        assert_eq!(name, config.generate(&mut SmallRng::seed_from_u64(0)));
+        let name: Name = config.fake_with_rng(&mut SmallRng::seed_from_u64(0));
+        assert_eq!(name.regenerate_with(&config), Some(name));
+    }
+}
@@ -0,0 +1,134 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Length Fitting
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Consonant pairs that may end a word.
+const FINAL_CLUSTERS: [&str; 32] = [
+    "ch", "ck", "ct", "ft", "gh", "ld", "lf", "lk", "ll", "lt", "mb", "mp", "nd", "ng", "nk", "ns",
+    "nt", "ph", "rb", "rd", "rg", "rk", "rm", "rn", "rs", "rt", "sh", "sk", "sp", "ss", "st", "th",
+];
+
+/// Consonants that never end a word on their own.
+const FORBIDDEN_FINALS: [char; 2] = ['j', 'q'];
+
+/// Shorten `name` to at most `max_chars` characters, dropping whole syllables from its end.
+///
+/// The onset of the first dropped syllable is kept as a final consonant when it fits and
+/// makes a legal ending ("Velmari" fits six characters as "Velmar"), and endings that no word
+/// could have are trimmed back ("Velmari" fits four as "Vel", not "Velm"). When even the first
+/// syllable is too long, the name is cut to `max_chars` and then trimmed the same way.
+///
+/// ```rust
+/// use nominae::fit_to;
+///
+/// assert_eq!(fit_to("Velmari", 6), "Velmar");
+/// assert_eq!(fit_to("Velmari", 4), "Vel");
+/// assert_eq!(fit_to("Velmari", 9), "Velmari");
+/// ```
+pub fn fit_to(name: &str, max_chars: usize) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    if chars.len() <= max_chars {
+        return String::from(name);
+    }
+    let end = syllable_starts(&chars)
+        .into_iter()
+        .rfind(|&start| start > 0 && start <= max_chars)
+        .unwrap_or(max_chars);
+    let mut fitted = chars[..end].to_vec();
+    if end < max_chars && is_consonant(chars[end]) {
+        fitted.push(chars[end]);
+        if !ends_legally(&fitted) {
+            fitted.pop();
+        }
+    }
+    while fitted.len() > 1 && !ends_legally(&fitted) {
+        fitted.pop();
+    }
+    fitted.into_iter().collect()
+}
+
+/// Index of the first character of each syllable of `chars`.
+///
+/// Each syllable holds one run of vowels, and of the consonants between two runs only the
+/// last starts the next syllable.
+fn syllable_starts(chars: &[char]) -> Vec<usize> {
+    let mut starts = vec![0];
+    let mut seen_vowel = false;
+    for idx in 0..chars.len() {
+        let opens = is_vowel(chars[idx]) && (idx == 0 || !is_vowel(chars[idx - 1]));
+        if opens && seen_vowel {
+            starts.push(idx - 1);
+        }
+        seen_vowel |= opens;
+    }
+    starts
+}
+
+/// Whether a word may end with the last characters of `chars`.
+fn ends_legally(chars: &[char]) -> bool {
+    let last = match chars.last() {
+        Some(&last) => last.to_ascii_lowercase(),
+        None => return true,
+    };
+    if is_vowel(last) {
+        return true;
+    }
+    if !last.is_alphabetic() || FORBIDDEN_FINALS.contains(&last) {
+        return false;
+    }
+    match chars
+        .len()
+        .checked_sub(2)
+        .map(|idx| chars[idx].to_ascii_lowercase())
+    {
+        Some(previous) if is_consonant(previous) => {
+            let cluster: String = [previous, last].iter().collect();
+            let before = chars.len().checked_sub(3).map(|idx| chars[idx]);
+            FINAL_CLUSTERS.contains(&cluster.as_str()) && !before.is_some_and(is_consonant)
+        }
+        _ => true,
+    }
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c.to_ascii_lowercase(), 'a' | 'e' | 'i' | 'o' | 'u' | 'y')
+}
+
+fn is_consonant(c: char) -> bool {
+    c.is_alphabetic() && !is_vowel(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fit_to;
+
+    #[test]
+    fn test_fit_to() {
+        assert_eq!(fit_to("Velmari", 7), "Velmari");
+        assert_eq!(fit_to("Velmari", 6), "Velmar");
+        assert_eq!(fit_to("Velmari", 5), "Velma");
+        assert_eq!(fit_to("Velmari", 4), "Vel");
+        assert_eq!(fit_to("Strakhorn", 4), "Stra");
+        assert_eq!(fit_to("Kor'dan", 4), "Kor");
+        assert_eq!(fit_to("Aeliq", 4), "Ael");
+        assert_eq!(fit_to("Velmari", 0), "");
+    }
+}
@@ -0,0 +1,227 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Full Name Composition
+
+use crate::morphology::attach;
+use crate::stable::below;
+use crate::totro::TotroConfig;
+use rand::Rng;
+use std::fmt;
+
+/// How the parts of a [`FullName`] are put together.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FullNameStyle {
+    /// A given name and a surname, as in "Essa Drake".
+    GivenSurname,
+    /// A given name and a patronymic formed from the father's name, as in "Essa Velmarson".
+    GivenPatronymic,
+    /// A given name and a place of origin, as in "Essa of Velmar".
+    GivenOfPlace,
+}
+
+/// What follows the given name of a [`FullName`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Family {
+    /// A surname, such as "Drake".
+    Surname(String),
+    /// A patronymic, such as "Velmarson", along with the father's name it was formed from.
+    Patronymic {
+        /// The father's given name, such as "Velmar".
+        father: String,
+        /// The patronymic itself, such as "Velmarson".
+        patronymic: String,
+    },
+    /// A place of origin, such as "Velmar".
+    Place(String),
+}
+
+/// A composed name with each of its parts kept separate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FullName {
+    /// Given name, such as "Essa".
+    pub given: String,
+    /// Surname, patronymic or place following the given name.
+    pub family: Family,
+}
+
+impl FullName {
+    /// Style the name was composed in.
+    pub fn style(&self) -> FullNameStyle {
+        match self.family {
+            Family::Surname(_) => FullNameStyle::GivenSurname,
+            Family::Patronymic { .. } => FullNameStyle::GivenPatronymic,
+            Family::Place(_) => FullNameStyle::GivenOfPlace,
+        }
+    }
+}
+
+impl fmt::Display for FullName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.family {
+            Family::Surname(surname) => write!(f, "{} {}", self.given, surname),
+            Family::Patronymic { patronymic, .. } => write!(f, "{} {}", self.given, patronymic),
+            Family::Place(place) => write!(f, "{} of {}", self.given, place),
+        }
+    }
+}
+
+/// Generator for [`FullName`]s in one or more weighted styles.
+///
+/// ```rust
+/// use nominae::{FullNameGenerator, FullNameStyle};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let names = FullNameGenerator::default().with_styles(&[
+///     (FullNameStyle::GivenSurname, 3),
+///     (FullNameStyle::GivenPatronymic, 1),
+///     (FullNameStyle::GivenOfPlace, 1),
+/// ]);
+/// let name = names.generate(&mut rng);
+/// println!("{} ({:?})", name, name.style());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct FullNameGenerator {
+    given: TotroConfig,
+    surname: TotroConfig,
+    place: TotroConfig,
+    styles: Vec<(FullNameStyle, u32)>,
+    patronymic_suffix: String,
+}
+
+impl Default for FullNameGenerator {
+    fn default() -> FullNameGenerator {
+        FullNameGenerator {
+            given: TotroConfig::alphabetic().with_length(2, 4),
+            surname: TotroConfig::alphabetic().with_length(2, 5),
+            place: TotroConfig::alphabetic().with_length(2, 4),
+            styles: vec![(FullNameStyle::GivenSurname, 1)],
+            patronymic_suffix: "son".to_owned(),
+        }
+    }
+}
+
+impl FullNameGenerator {
+    /// Set the configuration used for given names, including fathers' names.
+    pub fn with_given(mut self, config: TotroConfig) -> FullNameGenerator {
+        self.given = config;
+        self
+    }
+
+    /// Set the configuration used for surnames.
+    pub fn with_surname(mut self, config: TotroConfig) -> FullNameGenerator {
+        self.surname = config;
+        self
+    }
+
+    /// Set the configuration used for places of origin.
+    pub fn with_place(mut self, config: TotroConfig) -> FullNameGenerator {
+        self.place = config;
+        self
+    }
+
+    /// Compose every name in `style`.
+    pub fn with_style(self, style: FullNameStyle) -> FullNameGenerator {
+        self.with_styles(&[(style, 1)])
+    }
+
+    /// Compose names in a mix of styles, each chosen in proportion to its weight.
+    ///
+    /// Panics if every weight is zero.
+    pub fn with_styles(mut self, styles: &[(FullNameStyle, u32)]) -> FullNameGenerator {
+        assert!(
+            styles.iter().any(|&(_, weight)| weight > 0),
+            "at least one style must have a weight above zero"
+        );
+        self.styles = styles.to_vec();
+        self
+    }
+
+    /// Set the suffix patronymics are formed with, such as "son" or "dottir".
+    pub fn with_patronymic_suffix(mut self, suffix: &str) -> FullNameGenerator {
+        self.patronymic_suffix = suffix.to_owned();
+        self
+    }
+
+    /// Generate a full name.
+    pub fn generate<T: Rng + ?Sized>(&self, rng: &mut T) -> FullName {
+        let style = self.style(rng);
+        let given = self.given.generate(rng);
+        let family = match style {
+            FullNameStyle::GivenSurname => Family::Surname(self.surname.generate(rng)),
+            FullNameStyle::GivenPatronymic => {
+                let father = self.given.generate(rng);
+                let patronymic = attach(&father, &self.patronymic_suffix);
+                Family::Patronymic { father, patronymic }
+            }
+            FullNameStyle::GivenOfPlace => Family::Place(self.place.generate(rng)),
+        };
+        FullName { given, family }
+    }
+
+    fn style<T: Rng + ?Sized>(&self, rng: &mut T) -> FullNameStyle {
+        if let [(style, _)] = self.styles.as_slice() {
+            return *style;
+        }
+        let total: u64 = self.styles.iter().map(|&(_, weight)| weight as u64).sum();
+        let mut roll = below(rng, total);
+        for &(style, weight) in &self.styles {
+            if roll < weight as u64 {
+                return style;
+            }
+            roll -= weight as u64;
+        }
+        unreachable!("roll is below the total weight")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Family, FullName, FullNameGenerator, FullNameStyle};
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_display() {
+        let mut name = FullName {
+            given: "Essa".into(),
+            family: Family::Surname("Drake".into()),
+        };
+        assert_eq!(name.to_string(), "Essa Drake");
+        name.family = Family::Place("Velmar".into());
+        assert_eq!(name.to_string(), "Essa of Velmar");
+        assert_eq!(name.style(), FullNameStyle::GivenOfPlace);
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let names = FullNameGenerator::default()
+            .with_style(FullNameStyle::GivenPatronymic)
+            .with_patronymic_suffix("dottir");
+        for _ in 0..50 {
+            match names.generate(&mut rng).family {
+                Family::Patronymic { father, patronymic } => {
+                    assert!(patronymic.ends_with("dottir"), "{}", patronymic);
+                    assert!(patronymic.starts_with(&father[..father.len() - 1]));
+                }
+                family => panic!("unexpected {:?}", family),
+            }
+        }
+    }
+}
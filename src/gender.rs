@@ -0,0 +1,349 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Genders and Gender Distributions
+
+use crate::stable::below;
+use crate::table::{Attribute, Table};
+use crate::totro::TotroConfig;
+use rand::Rng;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Gender of a person being named.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Gender {
+    /// Masculine names.
+    Masculine,
+    /// Feminine names.
+    Feminine,
+    /// Neutral names, for nonbinary people or where gender is unmarked.
+    Neutral,
+}
+
+impl Gender {
+    /// Every gender, in declaration order.
+    pub const ALL: [Gender; 3] = [Gender::Masculine, Gender::Feminine, Gender::Neutral];
+}
+
+impl fmt::Display for Gender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Gender::Masculine => "masculine",
+            Gender::Feminine => "feminine",
+            Gender::Neutral => "neutral",
+        })
+    }
+}
+
+/// Relative weights of genders in a population, along with the naming style of each.
+///
+/// The default is 48% masculine, 48% feminine and 4% neutral, with every gender named in the
+/// default style. Any gender can be given its own [`TotroConfig`], and a weight of zero leaves a
+/// gender out entirely.
+///
+/// ```rust
+/// use nominae::{Gender, GenderDistribution, TotroConfig};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let distribution = GenderDistribution::default()
+///     .with_weight(Gender::Neutral, 10)
+///     .with_style(Gender::Feminine, TotroConfig::alphabetic().with_length(3, 5));
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let gender = distribution.sample(&mut rng);
+/// println!("{}: {}", gender, distribution.style(gender).generate(&mut rng));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct GenderDistribution {
+    weights: BTreeMap<Gender, u32>,
+    styles: BTreeMap<Gender, TotroConfig>,
+    style: TotroConfig,
+}
+
+impl Default for GenderDistribution {
+    fn default() -> GenderDistribution {
+        let weights = [
+            (Gender::Masculine, 48),
+            (Gender::Feminine, 48),
+            (Gender::Neutral, 4),
+        ];
+        GenderDistribution {
+            weights: weights.iter().copied().collect(),
+            styles: BTreeMap::new(),
+            style: TotroConfig::default(),
+        }
+    }
+}
+
+impl GenderDistribution {
+    /// Set the relative weight of `gender`.
+    pub fn with_weight(mut self, gender: Gender, weight: u32) -> GenderDistribution {
+        self.weights.insert(gender, weight);
+        self
+    }
+
+    /// Name `gender` in the style of `config` instead of the shared style.
+    pub fn with_style(mut self, gender: Gender, config: TotroConfig) -> GenderDistribution {
+        self.styles.insert(gender, config);
+        self
+    }
+
+    /// Set the style used for genders without a style of their own.
+    pub fn with_shared_style(mut self, config: TotroConfig) -> GenderDistribution {
+        self.style = config;
+        self
+    }
+
+    /// Relative weight of `gender`.
+    pub fn weight(&self, gender: Gender) -> u32 {
+        self.weights.get(&gender).copied().unwrap_or(0)
+    }
+
+    /// Fraction of the population expected to have `gender`.
+    pub fn share(&self, gender: Gender) -> f64 {
+        let total: u64 = self.weights.values().map(|&weight| weight as u64).sum();
+        if total == 0 {
+            return 0.0;
+        }
+        self.weight(gender) as f64 / total as f64
+    }
+
+    /// Genders with a non-zero weight, in declaration order.
+    pub fn genders(&self) -> impl Iterator<Item = Gender> + '_ {
+        Gender::ALL
+            .iter()
+            .copied()
+            .filter(move |&gender| self.weight(gender) > 0)
+    }
+
+    /// Naming style of `gender`.
+    pub fn style(&self, gender: Gender) -> &TotroConfig {
+        self.styles.get(&gender).unwrap_or(&self.style)
+    }
+
+    /// Draw a gender according to the weights.
+    ///
+    /// Panics if every weight is zero.
+    pub fn sample<T: Rng + ?Sized>(&self, rng: &mut T) -> Gender {
+        let total: u64 = self.weights.values().map(|&weight| weight as u64).sum();
+        assert!(total > 0, "gender distribution has no weight");
+        let mut roll = below(rng, total);
+        for gender in self.genders() {
+            let weight = self.weight(gender) as u64;
+            if roll < weight {
+                return gender;
+            }
+            roll -= weight;
+        }
+        unreachable!("roll is below the total weight")
+    }
+}
+
+/// A configuration whose syllables and endings lean towards a requested [`Gender`].
+///
+/// Each table decides how its tokens lean: a token with a number attribute named after a
+/// gender, such as `"feminine"`, has its weight scaled by that percentage when generating for
+/// that gender, so `150` makes it half again as likely and `0` leaves it out. Separately, each
+/// gender may prefer vowel endings: by default three in four feminine names and one in four
+/// masculine names end on a vowel, while neutral names are left unbiased. The ending is
+/// weighted while the last token is selected, so names are never drawn again to get it.
+///
+/// ```rust
+/// use nominae::{Gender, Gendered, Table, Token, TotroConfig, AIW};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let vowels = Table::new(vec![
+///     Token::new("a", AIW).with_attribute("feminine", 200),
+///     Token::new("o", AIW).with_attribute("masculine", 200),
+/// ])
+/// .unwrap();
+/// let gendered = Gendered::new(TotroConfig::default().with_vowels(vowels));
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// println!("{}", gendered.generate(Some(Gender::Feminine), &mut rng));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gendered {
+    config: TotroConfig,
+    configs: BTreeMap<Gender, TotroConfig>,
+}
+
+impl Gendered {
+    /// Lean `config` towards each gender as its tables describe.
+    pub fn new(config: TotroConfig) -> Gendered {
+        let configs = Gender::ALL
+            .iter()
+            .map(|&gender| {
+                let vowel_ending = match gender {
+                    Gender::Masculine => Some(0.25),
+                    Gender::Feminine => Some(0.75),
+                    Gender::Neutral => None,
+                };
+                let leaning = lean(&config, gender).with_vowel_ending(vowel_ending);
+                (gender, leaning)
+            })
+            .collect();
+        Gendered { config, configs }
+    }
+
+    /// Set the probability that names of `gender` end on a vowel, or `None` to leave endings
+    /// as the tables produce them.
+    pub fn with_vowel_endings(mut self, gender: Gender, probability: Option<f64>) -> Gendered {
+        if let Some(probability) = probability {
+            assert!(
+                (0.0..=1.0).contains(&probability),
+                "vowel ending probability must be within 0..=1"
+            );
+        }
+        if let Some(config) = self.configs.remove(&gender) {
+            self.configs
+                .insert(gender, config.with_vowel_ending(probability));
+        }
+        self
+    }
+
+    /// Probability that names of `gender` end on a vowel, if endings are biased.
+    pub fn vowel_endings(&self, gender: Gender) -> Option<f64> {
+        self.configs
+            .get(&gender)
+            .and_then(TotroConfig::vowel_ending)
+    }
+
+    /// Configuration used for `gender`, or the unbiased configuration for no gender.
+    pub fn config(&self, gender: Option<Gender>) -> &TotroConfig {
+        gender
+            .and_then(|gender| self.configs.get(&gender))
+            .unwrap_or(&self.config)
+    }
+
+    /// Generate a name leaning towards `gender`, or an unbiased name for no gender.
+    pub fn generate<T: Rng + ?Sized>(&self, gender: Option<Gender>, rng: &mut T) -> String {
+        self.config(gender).generate(rng)
+    }
+}
+
+/// `config` with token weights scaled by their `gender` attribute.
+fn lean(config: &TotroConfig, gender: Gender) -> TotroConfig {
+    let key = gender.to_string();
+    let scale = |table: &Table| {
+        let mut table = table.clone();
+        let scaled: Vec<(String, u32, i64)> = table
+            .tokens()
+            .iter()
+            .filter_map(|token| match token.attribute(&key) {
+                Some(&Attribute::Number(percent)) => {
+                    Some((token.text().to_owned(), token.weight(), percent))
+                }
+                _ => None,
+            })
+            .collect();
+        for (text, weight, percent) in scaled {
+            let weight = (weight as i64 * percent.max(0) / 100).min(u32::MAX as i64) as u32;
+            if weight == 0 {
+                // A token the table cannot do without is kept at its original weight.
+                table.remove(&text).ok();
+            } else {
+                table
+                    .set_weight(&text, weight)
+                    .expect("scaled tokens are in the table");
+            }
+        }
+        table
+    };
+    config
+        .clone()
+        .with_vowels(scale(config.vowels()))
+        .with_consonants(scale(config.consonants()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Gender, GenderDistribution, Gendered};
+    use crate::syllable::Syllabic;
+    use crate::totro::{Mode, TotroConfig};
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_distribution() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let distribution = GenderDistribution::default()
+            .with_weight(Gender::Masculine, 0)
+            .with_weight(Gender::Feminine, 3)
+            .with_weight(Gender::Neutral, 1)
+            .with_style(Gender::Neutral, TotroConfig::alphabetic());
+        assert!((distribution.share(Gender::Feminine) - 0.75).abs() < 1e-9);
+        assert_eq!(
+            distribution.style(Gender::Neutral),
+            &TotroConfig::alphabetic()
+        );
+        assert_eq!(
+            distribution.style(Gender::Feminine),
+            &TotroConfig::default()
+        );
+        let mut counts = BTreeMap::new();
+        for _ in 0..4000 {
+            *counts.entry(distribution.sample(&mut rng)).or_insert(0) += 1;
+        }
+        assert!(!counts.contains_key(&Gender::Masculine));
+        assert!(
+            (2800..3200).contains(&counts[&Gender::Feminine]),
+            "{:?}",
+            counts
+        );
+    }
+
+    #[test]
+    fn test_gendered() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut config = TotroConfig::alphabetic();
+        config
+            .vowels_mut()
+            .set_attribute("o", "feminine", 0)
+            .unwrap();
+        let gendered = Gendered::new(config);
+        let feminine = gendered.config(Some(Gender::Feminine));
+        assert!(feminine.vowels().get("o").is_none());
+        assert!(gendered.config(None).vowels().get("o").is_some());
+        let mut vowel_endings = |gender| {
+            (0..1000)
+                .filter(|_| {
+                    let name = gendered.generate(Some(gender), &mut rng);
+                    name.ends_with(|c| "aeiouy".contains(c))
+                })
+                .count()
+        };
+        assert!((650..850).contains(&vowel_endings(Gender::Feminine)));
+        assert!((150..350).contains(&vowel_endings(Gender::Masculine)));
+    }
+
+    #[test]
+    fn test_syllabic_endings() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let config =
+            TotroConfig::alphabetic().with_mode(Mode::Syllabic(Syllabic::default().with_coda(0.5)));
+        let gendered = Gendered::new(config).with_vowel_endings(Gender::Feminine, Some(1.0));
+        assert_eq!(gendered.vowel_endings(Gender::Feminine), Some(1.0));
+        for _ in 0..200 {
+            let name = gendered.generate(Some(Gender::Feminine), &mut rng);
+            assert!(name.ends_with(|c| "aeiouy".contains(c)), "{}", name);
+        }
+        let unbiased = gendered.with_vowel_endings(Gender::Feminine, None);
+        assert_eq!(unbiased.vowel_endings(Gender::Feminine), None);
+    }
+}
@@ -0,0 +1,447 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Common Generator Interface
+
+use crate::error::NominaeError;
+#[cfg(feature = "std")]
+use crate::export::{write_names, ExportFormat};
+#[cfg(feature = "std")]
+use crate::full_name::FullNameGenerator;
+#[cfg(feature = "std")]
+use crate::gender::Gendered;
+#[cfg(feature = "std")]
+use crate::guided::Guided;
+#[cfg(feature = "std")]
+use crate::markov::Markov;
+#[cfg(feature = "std")]
+use crate::mixture::Mixture;
+use crate::pattern::Pattern;
+#[cfg(feature = "std")]
+use crate::quality::{rank, score};
+use crate::totro::{Totro, TotroConfig};
+use alloc::boxed::Box;
+use alloc::string::String;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+use rand::distributions::Distribution;
+use rand::{Rng, RngCore};
+#[cfg(feature = "std")]
+use std::io;
+
+/// A source of names, implemented by every algorithm in the crate.
+///
+/// The random generator is taken as a trait object so the trait itself is object safe:
+/// downstream code can hold a `Box<dyn NameGenerator>` and swap algorithms at runtime. The
+/// inherent `generate` methods accept one too, since their generators may be unsized, so a
+/// plugin or scripting layer holding only a `&mut dyn RngCore` can call any of them.
+///
+/// ```rust
+/// use nominae::{presets, Markov, NameGenerator, TotroConfig};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut model = Markov::new(2);
+/// model.update(&["Velmar", "Velda", "Maren"]);
+/// let generators: Vec<Box<dyn NameGenerator>> = vec![
+///     Box::new(TotroConfig::default()),
+///     Box::new(presets::pet()),
+///     Box::new(model),
+/// ];
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// for generator in &generators {
+///     println!("{}", generator.generate(&mut rng));
+/// }
+/// ```
+///
+/// Every generator in the crate is also a [`Distribution<String>`], for code written against
+/// `rand`:
+///
+/// ```rust
+/// use nominae::TotroConfig;
+/// use rand::{Rng, SeedableRng};
+/// use rand::rngs::SmallRng;
+///
+/// let rng = SmallRng::seed_from_u64(0);
+/// let names: Vec<String> = rng.sample_iter(TotroConfig::default()).take(10).collect();
+/// assert_eq!(names.len(), 10);
+/// ```
+pub trait NameGenerator {
+    /// Generate a name.
+    fn generate(&self, rng: &mut dyn RngCore) -> String;
+}
+
+impl<G: NameGenerator + ?Sized> NameGenerator for &G {
+    fn generate(&self, rng: &mut dyn RngCore) -> String {
+        (**self).generate(rng)
+    }
+}
+
+impl<G: NameGenerator + ?Sized> NameGenerator for Box<G> {
+    fn generate(&self, rng: &mut dyn RngCore) -> String {
+        (**self).generate(rng)
+    }
+}
+
+/// Candidates [`Filtered`] draws before giving up, unless set otherwise.
+const FILTER_ATTEMPTS: usize = 100;
+
+/// Adapters available on every [`NameGenerator`].
+pub trait GeneratorExt: NameGenerator + Sized {
+    /// Only yield names `filter` accepts, drawing again for each one it turns away.
+    ///
+    /// A [`Screen`](crate::Screen) makes a ready-made filter, and with the `blocklist` feature
+    /// one can turn away accidental profanity before players ever see it:
+    ///
+    /// ```rust
+    /// use nominae::{GeneratorExt, NameGenerator, Screen, TotroConfig};
+    /// use rand::SeedableRng;
+    /// use rand::rngs::SmallRng;
+    ///
+    /// let screen = Screen::default().with_blocked(&["ka", "ra"]);
+    /// let names = TotroConfig::default().filtered(move |name| screen.passes(name));
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    /// let name = names.try_generate(&mut rng).unwrap();
+    /// assert!(!name.to_lowercase().contains("ka"));
+    /// ```
+    fn filtered<F: Fn(&str) -> bool>(self, filter: F) -> Filtered<Self, F> {
+        Filtered {
+            generator: self,
+            filter,
+            attempts: FILTER_ATTEMPTS,
+        }
+    }
+
+    /// Generate a fixed batch of `N` names, in the order [`generate`](NameGenerator::generate)
+    /// would have produced them one at a time.
+    ///
+    /// The batch lives on the stack, so the only allocations are the names themselves, which
+    /// suits callers that budget memory up front.
+    ///
+    /// ```rust
+    /// use nominae::{GeneratorExt, TotroConfig};
+    /// use rand::SeedableRng;
+    /// use rand::rngs::SmallRng;
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    /// let [captain, mate, cook] = TotroConfig::default().generate_array(&mut rng);
+    /// println!("{}, {} and {}", captain, mate, cook);
+    /// ```
+    fn generate_array<const N: usize>(&self, rng: &mut dyn RngCore) -> [String; N] {
+        core::array::from_fn(|_| self.generate(rng))
+    }
+
+    /// Generate `n` candidates and return the best `k` of them, best first, so interfaces can
+    /// offer only good suggestions.
+    ///
+    /// Candidates are ranked by [`score`], with penalties for awkward clusters, and each pick
+    /// after the first also has to differ from those before it, so
+    /// the suggestions are not all variations on one name. Fewer than `k` names are returned
+    /// when the candidates hold fewer distinct names.
+    ///
+    /// ```rust
+    /// use nominae::{GeneratorExt, TotroConfig};
+    /// use rand::SeedableRng;
+    /// use rand::rngs::SmallRng;
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    /// let suggestions = TotroConfig::default().generate_ranked(50, 5, &mut rng);
+    /// assert_eq!(suggestions.len(), 5);
+    /// ```
+    #[cfg(feature = "std")]
+    fn generate_ranked(&self, n: usize, k: usize, rng: &mut dyn RngCore) -> Vec<String> {
+        let candidates = (0..n).map(|_| self.generate(rng)).collect();
+        rank(candidates, k)
+    }
+
+    /// Generate `n` candidates and keep the one with the best [`score`], the first of them
+    /// on a tie.
+    ///
+    /// ```rust
+    /// use nominae::{score, GeneratorExt, TotroConfig};
+    /// use rand::SeedableRng;
+    /// use rand::rngs::SmallRng;
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    /// let name = TotroConfig::default().generate_best(10, &mut rng);
+    /// assert!(score(&name) > 0.5);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when `n` is zero.
+    #[cfg(feature = "std")]
+    fn generate_best(&self, n: usize, rng: &mut dyn RngCore) -> String {
+        assert!(n > 0, "at least one candidate is required");
+        let mut best = self.generate(rng);
+        let mut best_score = score(&best);
+        for _ in 1..n {
+            let candidate = self.generate(rng);
+            let candidate_score = score(&candidate);
+            if candidate_score > best_score {
+                best = candidate;
+                best_score = candidate_score;
+            }
+        }
+        best
+    }
+
+    /// Write `n` names to `writer` in `format`, generating each as it is written so that
+    /// datasets far larger than memory can be exported.
+    ///
+    /// Names are written one line at a time, so wrap unbuffered writers such as files in a
+    /// [`BufWriter`](std::io::BufWriter).
+    ///
+    /// ```rust
+    /// use nominae::{ExportFormat, GeneratorExt, TotroConfig};
+    /// use rand::SeedableRng;
+    /// use rand::rngs::SmallRng;
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    /// let mut out = Vec::new();
+    /// TotroConfig::default()
+    ///     .generate_to_writer(&mut out, 3, ExportFormat::JsonLines, &mut rng)
+    ///     .unwrap();
+    /// assert!(String::from_utf8(out).unwrap().starts_with("{\"name\":"));
+    /// ```
+    #[cfg(feature = "std")]
+    fn generate_to_writer<W: io::Write>(
+        &self,
+        writer: W,
+        n: usize,
+        format: ExportFormat,
+        rng: &mut dyn RngCore,
+    ) -> io::Result<()> {
+        write_names(self, writer, n, format, rng)
+    }
+}
+
+impl<G: NameGenerator> GeneratorExt for G {}
+
+/// A generator whose names must pass a filter, returned by [`GeneratorExt::filtered`].
+#[derive(Clone, Debug)]
+pub struct Filtered<G, F> {
+    generator: G,
+    filter: F,
+    attempts: usize,
+}
+
+impl<G: NameGenerator, F: Fn(&str) -> bool> Filtered<G, F> {
+    /// Set how many candidates are drawn before giving up. Defaults to 100.
+    pub fn with_attempts(mut self, attempts: usize) -> Filtered<G, F> {
+        assert!(attempts > 0, "at least one attempt is required");
+        self.attempts = attempts;
+        self
+    }
+
+    /// Generate a name the filter accepts, or [`NominaeError::RetriesExhausted`] when none
+    /// turns up within the configured attempts.
+    pub fn try_generate(&self, rng: &mut dyn RngCore) -> Result<String, NominaeError> {
+        for _ in 0..self.attempts {
+            let name = self.generator.generate(rng);
+            if (self.filter)(&name) {
+                return Ok(name);
+            }
+            event!(trace, name = name.as_str(), "rejected by filter");
+        }
+        Err(NominaeError::RetriesExhausted {
+            constraint: "name filter".into(),
+            attempts: self.attempts,
+        })
+    }
+}
+
+/// Names the filter accepts.
+///
+/// # Panics
+///
+/// Panics when no accepted name turns up within the configured attempts, rather than yield a
+/// name the filter turned away. Use [`Filtered::try_generate`] where that can happen.
+impl<G: NameGenerator, F: Fn(&str) -> bool> NameGenerator for Filtered<G, F> {
+    fn generate(&self, rng: &mut dyn RngCore) -> String {
+        match self.try_generate(rng) {
+            Ok(name) => name,
+            Err(error) => panic!("{}", error),
+        }
+    }
+}
+
+/// Names of 2 to 5 syllables from the built-in tables, as `Totro::generate(2, 5, rng)`.
+impl NameGenerator for Totro {
+    fn generate(&self, mut rng: &mut dyn RngCore) -> String {
+        Totro::generate(2, 5, &mut rng)
+    }
+}
+
+impl NameGenerator for TotroConfig {
+    fn generate(&self, mut rng: &mut dyn RngCore) -> String {
+        TotroConfig::generate(self, &mut rng)
+    }
+}
+
+impl NameGenerator for Pattern {
+    fn generate(&self, mut rng: &mut dyn RngCore) -> String {
+        Pattern::generate(self, &mut rng)
+    }
+}
+
+#[cfg(feature = "std")]
+impl NameGenerator for Markov {
+    fn generate(&self, mut rng: &mut dyn RngCore) -> String {
+        Markov::generate(self, &mut rng)
+    }
+}
+
+/// Full names as displayed, such as "Essa Drake".
+#[cfg(feature = "std")]
+impl NameGenerator for FullNameGenerator {
+    fn generate(&self, mut rng: &mut dyn RngCore) -> String {
+        FullNameGenerator::generate(self, &mut rng).to_string()
+    }
+}
+
+/// Names without a gender bias, as `Gendered::generate(None, rng)`.
+#[cfg(feature = "std")]
+impl NameGenerator for Gendered {
+    fn generate(&self, mut rng: &mut dyn RngCore) -> String {
+        Gendered::generate(self, None, &mut rng)
+    }
+}
+
+#[cfg(feature = "std")]
+impl NameGenerator for Guided {
+    fn generate(&self, mut rng: &mut dyn RngCore) -> String {
+        Guided::generate(self, &mut rng)
+    }
+}
+
+/// Names from whichever preset is drawn; use [`Mixture::generate`] to learn which it was.
+#[cfg(feature = "std")]
+impl NameGenerator for Mixture {
+    fn generate(&self, mut rng: &mut dyn RngCore) -> String {
+        Mixture::generate(self, &mut rng).name
+    }
+}
+
+/// Implement [`Distribution<String>`] through [`NameGenerator`], so that generators compose with
+/// [`Rng::sample`] and [`Rng::sample_iter`].
+macro_rules! distribution {
+    ($($(#[$attr:meta])* $generator:ty),* $(,)?) => {
+        $(
+            $(#[$attr])*
+            impl Distribution<String> for $generator {
+                fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+                    let mut rng = rng;
+                    NameGenerator::generate(self, &mut rng)
+                }
+            }
+        )*
+    };
+}
+
+distribution!(
+    Totro,
+    TotroConfig,
+    Pattern,
+    #[cfg(feature = "std")]
+    Markov,
+    #[cfg(feature = "std")]
+    FullNameGenerator,
+    #[cfg(feature = "std")]
+    Gendered,
+    #[cfg(feature = "std")]
+    Guided,
+    #[cfg(feature = "std")]
+    Mixture,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::{GeneratorExt, NameGenerator};
+    use crate::error::NominaeError;
+    use crate::totro::{Totro, TotroConfig};
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, RngCore, SeedableRng};
+
+    #[test]
+    fn test_dyn_generator() {
+        let config = TotroConfig::default();
+        let generators: Vec<Box<dyn NameGenerator>> =
+            vec![Box::new(Totro), Box::new(config.clone()), Box::new(&config)];
+        let mut rng = SmallRng::seed_from_u64(0);
+        for generator in &generators {
+            assert!(!generator.generate(&mut rng).is_empty());
+        }
+        let expected = config.generate(&mut SmallRng::seed_from_u64(1));
+        let boxed: Box<dyn NameGenerator> = Box::new(config.clone());
+        assert_eq!(boxed.generate(&mut SmallRng::seed_from_u64(1)), expected);
+    }
+
+    #[test]
+    fn test_distribution() {
+        let config = TotroConfig::default().with_length(2, 5);
+        let mut rng = SmallRng::seed_from_u64(0);
+        let expected: Vec<String> = (0..5).map(|_| config.generate(&mut rng)).collect();
+        let mut rng = SmallRng::seed_from_u64(0);
+        let sampled: Vec<String> = (&mut rng).sample_iter(&config).take(5).collect();
+        assert_eq!(sampled, expected);
+        assert!(!rng.sample(Totro).is_empty());
+    }
+
+    #[test]
+    fn test_generate_array() {
+        let config = TotroConfig::default();
+        let mut rng = SmallRng::seed_from_u64(0);
+        let expected: Vec<String> = (0..4).map(|_| config.generate(&mut rng)).collect();
+        let names: [String; 4] = config.generate_array(&mut SmallRng::seed_from_u64(0));
+        assert_eq!(names.to_vec(), expected);
+        let none: [String; 0] = config.generate_array(&mut rng);
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn test_erased_rng() {
+        let config = TotroConfig::default();
+        let expected = config.generate(&mut SmallRng::seed_from_u64(0));
+        let mut rng = SmallRng::seed_from_u64(0);
+        let erased: &mut dyn RngCore = &mut rng;
+        assert_eq!(config.generate(erased), expected);
+        let mut boxed: Box<dyn RngCore> = Box::new(SmallRng::seed_from_u64(0));
+        assert_eq!(
+            Totro::generate(2, 5, &mut *boxed),
+            Totro::generate(2, 5, &mut SmallRng::seed_from_u64(0))
+        );
+    }
+
+    #[test]
+    fn test_filtered() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let short = TotroConfig::default().filtered(|name| name.len() <= 4);
+        for _ in 0..50 {
+            assert!(short.generate(&mut rng).len() <= 4);
+        }
+        let never = TotroConfig::default().filtered(|_| false).with_attempts(10);
+        assert_eq!(
+            never.try_generate(&mut rng),
+            Err(NominaeError::RetriesExhausted {
+                constraint: "name filter".into(),
+                attempts: 10,
+            })
+        );
+    }
+}
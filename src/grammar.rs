@@ -0,0 +1,163 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Phrase Grammars
+
+use crate::template::expand;
+use crate::totro::TotroConfig;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Deepest level of nested rules expanded before placeholders are left as written.
+const MAX_DEPTH: usize = 16;
+
+/// Placeholder replaced with a freshly generated proper noun.
+const NAME: &str = "name";
+
+/// Text produced by a [`Grammar`] along with the proper nouns generated for it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Phrase {
+    /// The expanded text.
+    pub text: String,
+    /// Proper nouns generated for `{name}` placeholders, in order of appearance.
+    pub names: Vec<String>,
+}
+
+impl fmt::Display for Phrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+/// A simple context-free grammar over `{placeholder}` templates.
+///
+/// Each rule maps a symbol to alternative templates. Expanding a symbol picks one alternative at
+/// random and expands its placeholders in turn: `{name}` becomes a generated proper noun,
+/// placeholders naming a rule are expanded recursively, and anything else is left as written.
+///
+/// ```rust
+/// use nominae::Grammar;
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let grammar = Grammar::default()
+///     .rule("greeting", &["Hail, {name} of {place}!", "Well met, {name}."])
+///     .rule("place", &["the {name} Hills", "{name}"]);
+/// println!("{}", grammar.generate("greeting", &mut rng));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grammar {
+    rules: BTreeMap<String, Vec<String>>,
+    names: TotroConfig,
+}
+
+impl Default for Grammar {
+    fn default() -> Grammar {
+        Grammar {
+            rules: BTreeMap::new(),
+            names: TotroConfig::alphabetic().with_length(2, 4),
+        }
+    }
+}
+
+impl Grammar {
+    /// Add alternatives for `symbol`, keeping any already present.
+    pub fn rule(mut self, symbol: &str, alternatives: &[&str]) -> Grammar {
+        self.rules.entry(symbol.to_owned()).or_default().extend(
+            alternatives
+                .iter()
+                .map(|&alternative| alternative.to_owned()),
+        );
+        self
+    }
+
+    /// Set the configuration used to generate proper nouns.
+    pub fn with_names(mut self, names: TotroConfig) -> Grammar {
+        self.names = names;
+        self
+    }
+
+    /// Alternatives for `symbol`, if it is a rule.
+    pub fn alternatives(&self, symbol: &str) -> Option<&[String]> {
+        self.rules.get(symbol).map(Vec::as_slice)
+    }
+
+    /// Expand `symbol` into a phrase whose first letter is capitalized.
+    pub fn generate<T: Rng + ?Sized>(&self, symbol: &str, rng: &mut T) -> Phrase {
+        let mut names = Vec::new();
+        let mut text = self.expand(symbol, 0, &mut names, rng);
+        if let Some(first) = text.get_mut(0..1) {
+            first.make_ascii_uppercase();
+        }
+        Phrase { text, names }
+    }
+
+    fn expand<T: Rng + ?Sized>(
+        &self,
+        symbol: &str,
+        depth: usize,
+        names: &mut Vec<String>,
+        rng: &mut T,
+    ) -> String {
+        if symbol == NAME {
+            let name = self.names.generate(rng);
+            names.push(name.clone());
+            return name;
+        }
+        match self.rules.get(symbol).and_then(|rule| rule.choose(rng)) {
+            Some(template) if depth < MAX_DEPTH => {
+                expand(template, |inner| self.expand(inner, depth + 1, names, rng))
+            }
+            _ => format!("{{{}}}", symbol),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grammar;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_generate() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let grammar = Grammar::default()
+            .rule("start", &["{name} meets {who}"])
+            .rule("who", &["{name}"]);
+        let phrase = grammar.generate("start", &mut rng);
+        assert_eq!(phrase.names.len(), 2);
+        assert_eq!(
+            phrase.text,
+            format!("{} meets {}", phrase.names[0], phrase.names[1])
+        );
+    }
+
+    #[test]
+    fn test_unknown_and_recursive() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let grammar = Grammar::default()
+            .rule("start", &["a {missing} {loop}"])
+            .rule("loop", &["{loop}"]);
+        assert_eq!(
+            grammar.generate("start", &mut rng).text,
+            "A {missing} {loop}"
+        );
+    }
+}
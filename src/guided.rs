@@ -0,0 +1,193 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Markov-Guided Totro Generation
+
+use crate::markov::Markov;
+use crate::stable::below;
+use crate::table::Token;
+use crate::totro::{draw_length, position, TotroConfig};
+use rand::Rng;
+
+/// Fixed-point scale of candidate weights, so the final draw is made on integers.
+const SCALE: f64 = (1u64 << 32) as f64;
+
+/// A hybrid generator that assembles names from Totro tables but weights each candidate token
+/// by how likely a trained [`Markov`] model finds it after the name so far.
+///
+/// The tables keep every name pronounceable, since only tokens allowed at each position are
+/// ever considered, while the model pulls the choice among them toward the style of its
+/// corpus. Names alternate between vowel and consonant tokens with a token-counted length, as
+/// in [`Mode::Alternating`](crate::Mode::Alternating), whatever the mode of the configuration.
+///
+/// ```rust
+/// use nominae::{Guided, Markov, TotroConfig};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut model = Markov::new(2);
+/// model.update(&["Velmar", "Velda", "Maren", "Doran", "Veris"]);
+/// let guided = Guided::new(TotroConfig::alphabetic(), model).with_strength(2.0);
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// println!("{}", guided.generate(&mut rng));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Guided {
+    config: TotroConfig,
+    model: Markov,
+    smoothing: f64,
+    strength: f64,
+}
+
+impl Guided {
+    /// Combine the tables of `config` with the style of `model`.
+    pub fn new(config: TotroConfig, model: Markov) -> Guided {
+        Guided {
+            config,
+            model,
+            smoothing: 0.05,
+            strength: 1.0,
+        }
+    }
+
+    /// Set the additive smoothing applied to model counts, which keeps tokens the corpus never
+    /// shows in play at a small weight.
+    pub fn with_smoothing(mut self, smoothing: f64) -> Guided {
+        assert!(smoothing > 0.0, "smoothing must be positive");
+        self.smoothing = smoothing;
+        self
+    }
+
+    /// Set how strongly the model steers selection: 0 ignores it, 1 multiplies each token's
+    /// table weight by its model probability, and higher values follow the corpus more closely.
+    pub fn with_strength(mut self, strength: f64) -> Guided {
+        assert!(strength >= 0.0, "strength must not be negative");
+        self.strength = strength;
+        self
+    }
+
+    /// Table configuration names are assembled from.
+    pub fn config(&self) -> &TotroConfig {
+        &self.config
+    }
+
+    /// Model that weights token selection.
+    pub fn model(&self) -> &Markov {
+        &self.model
+    }
+
+    /// Generate a name.
+    pub fn generate<T: Rng + ?Sized>(&self, rng: &mut T) -> String {
+        let config = &self.config;
+        let scorer = self.model.scorer(self.smoothing);
+        let (min, max) = config.length();
+        let length = draw_length(min, max, rng);
+        let (vowel_pools, consonant_pools) = config.pools();
+        let mut vowel = config.starts_with_vowel(None, Some(length), rng);
+        let mut name = String::new();
+        let mut previous: Option<&str> = None;
+        for idx in 0..length {
+            let position = position(idx, length);
+            let (table, pools) = if vowel {
+                (config.vowels(), vowel_pools)
+            } else {
+                (config.consonants(), consonant_pools)
+            };
+            let pool = pools
+                .get(position)
+                .expect("validated tables cover every position");
+            let avoid = config.avoid(previous);
+            let candidates: Vec<&Token> = pool
+                .indices()
+                .map(|idx| &table.tokens()[idx])
+                .filter(|token| !avoid.contains(&token.text()))
+                .collect();
+            let candidates = if candidates.is_empty() {
+                pool.indices().map(|idx| &table.tokens()[idx]).collect()
+            } else {
+                candidates
+            };
+            let last = idx + 1 == length;
+            let weights: Vec<f64> = candidates
+                .iter()
+                .map(|token| {
+                    let fit = scorer.continuation(&name, token.text(), last);
+                    token.weight() as f64 * fit.powf(self.strength)
+                })
+                .collect();
+            let token = candidates[draw(&weights, rng)];
+            name.push_str(token.text());
+            previous = Some(token.text());
+            vowel = !vowel;
+        }
+        if let Some(first) = name.get_mut(0..1) {
+            first.make_ascii_uppercase();
+        }
+        name
+    }
+}
+
+/// Draw an index with probability proportional to `weights`, uniformly if all are zero.
+fn draw<T: Rng + ?Sized>(weights: &[f64], rng: &mut T) -> usize {
+    let max = weights.iter().copied().fold(0.0, f64::max);
+    let scaled: Vec<u64> = weights
+        .iter()
+        .map(|&weight| {
+            if max > 0.0 {
+                (weight / max * SCALE) as u64
+            } else {
+                1
+            }
+        })
+        .collect();
+    let mut roll = below(rng, scaled.iter().sum());
+    for (idx, &weight) in scaled.iter().enumerate() {
+        if roll < weight {
+            return idx;
+        }
+        roll -= weight;
+    }
+    unreachable!("roll is below the total weight")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Guided;
+    use crate::markov::Markov;
+    use crate::totro::TotroConfig;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_generate() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut model = Markov::new(1);
+        model.update(&["lalala", "alala"]);
+        let config = TotroConfig::alphabetic().with_length(4, 6);
+        let guided = Guided::new(config.clone(), model.clone()).with_strength(4.0);
+        let fits = |name: &str| name.to_lowercase().chars().all(|c| c == 'l' || c == 'a');
+        let guided_fits = (0..200)
+            .filter(|_| fits(&guided.generate(&mut rng)))
+            .count();
+        let plain_fits = (0..200)
+            .filter(|_| fits(&config.generate(&mut rng)))
+            .count();
+        assert!(guided_fits > 150, "{}", guided_fits);
+        assert!(plain_fits < 10, "{}", plain_fits);
+        let ignored = Guided::new(config, model).with_strength(0.0);
+        assert!((0..100).all(|_| !ignored.generate(&mut rng).is_empty()));
+    }
+}
@@ -0,0 +1,148 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Hostname and DNS Label Generation
+
+use crate::error::NominaeError;
+use crate::normalize::fold_letters;
+use crate::totro::TotroConfig;
+use rand::Rng;
+
+/// Longest DNS label allowed by RFC 1035.
+pub const MAX_LABEL: usize = 63;
+
+/// Names drawn for each word of a label before giving up.
+const LABEL_ATTEMPTS: usize = 100;
+
+/// Convert `text` into an RFC 1123 hostname label, or `None` if nothing usable remains.
+///
+/// Letters are lowercased and accented Latin letters folded to their base letters, runs of
+/// anything other than ASCII letters and digits become a single hyphen, leading and trailing
+/// hyphens are removed, and the label is cut to [`MAX_LABEL`] characters ("D'Vára Keep" becomes
+/// "d-vara-keep").
+pub fn dns_label(text: &str) -> Option<String> {
+    let mut label = String::with_capacity(text.len());
+    for c in fold_letters(text).chars() {
+        if c.is_ascii_alphanumeric() {
+            label.push(c.to_ascii_lowercase());
+        } else if !label.is_empty() && !label.ends_with('-') {
+            label.push('-');
+        }
+    }
+    label.truncate(MAX_LABEL);
+    let label = label.trim_end_matches('-');
+    if label.is_empty() {
+        None
+    } else {
+        Some(label.to_owned())
+    }
+}
+
+impl TotroConfig {
+    /// Generate an RFC 1123 hostname label from `words` generated names joined by hyphens, such
+    /// as "velmar" or "velmar-oskan", for naming servers, pods and subdomains.
+    ///
+    /// Names that leave nothing usable after [`dns_label`] are regenerated, and the label is cut
+    /// to `max_len` (at most [`MAX_LABEL`]) without leaving a trailing hyphen. Tables whose
+    /// tokens never fold to ASCII, such as Cyrillic ones, give up with
+    /// [`NominaeError::RetriesExhausted`] after 100 unusable names for a word.
+    ///
+    /// ```rust
+    /// use nominae::TotroConfig;
+    /// use rand::SeedableRng;
+    /// use rand::rngs::SmallRng;
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    /// let label = TotroConfig::default().generate_label(2, 20, &mut rng).unwrap();
+    /// assert!(label.len() <= 20 && !label.ends_with('-'));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `words` or `max_len` is zero.
+    pub fn generate_label<T: Rng + ?Sized>(
+        &self,
+        words: usize,
+        max_len: usize,
+        rng: &mut T,
+    ) -> Result<String, NominaeError> {
+        assert!(words > 0, "a label needs at least one word");
+        assert!(max_len > 0, "a label needs at least one character");
+        let parts = (0..words)
+            .map(|_| {
+                (0..LABEL_ATTEMPTS)
+                    .find_map(|_| dns_label(&self.generate(rng)))
+                    .ok_or_else(|| NominaeError::RetriesExhausted {
+                        constraint: "usable DNS label".into(),
+                        attempts: LABEL_ATTEMPTS,
+                    })
+            })
+            .collect::<Result<Vec<String>, NominaeError>>()?;
+        let mut label = parts.join("-");
+        label.truncate(max_len.min(MAX_LABEL));
+        Ok(label.trim_end_matches('-').to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dns_label, MAX_LABEL};
+    use crate::error::NominaeError;
+    use crate::table::{Table, Token, AIW};
+    use crate::totro::TotroConfig;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_dns_label() {
+        assert_eq!(dns_label("D'Vara Keep").as_deref(), Some("d-vara-keep"));
+        assert_eq!(dns_label("'Essa'").as_deref(), Some("essa"));
+        assert_eq!(dns_label("Élan Vítal").as_deref(), Some("elan-vital"));
+        assert_eq!(dns_label("'--'"), None);
+        assert_eq!(dns_label("Влад"), None);
+        assert_eq!(dns_label(&"a".repeat(100)).unwrap().len(), MAX_LABEL);
+    }
+
+    #[test]
+    fn test_generate_label() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let config = TotroConfig::default();
+        for _ in 0..200 {
+            let label = config.generate_label(3, 12, &mut rng).unwrap();
+            assert!(!label.is_empty() && label.len() <= 12, "{}", label);
+            assert!(
+                !label.starts_with('-') && !label.ends_with('-'),
+                "{}",
+                label
+            );
+            assert!(label
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-'));
+        }
+    }
+
+    #[test]
+    fn test_unusable_tables() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let vowels = Table::new(vec![Token::new("а", AIW), Token::new("о", AIW)]).unwrap();
+        let consonants = Table::new(vec![Token::new("в", AIW), Token::new("л", AIW)]).unwrap();
+        let config = TotroConfig::new(vowels, consonants);
+        assert!(matches!(
+            config.generate_label(2, 20, &mut rng),
+            Err(NominaeError::RetriesExhausted { attempts: 100, .. })
+        ));
+    }
+}
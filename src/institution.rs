@@ -0,0 +1,202 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Academy and Institution Name Generation
+
+use crate::template::{expand, placeholders};
+use crate::totro::TotroConfig;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::fmt;
+
+const DISCIPLINES: [&str; 10] = [
+    "the Unseen Arts",
+    "the Healing Arts",
+    "Arcane Geometry",
+    "Natural Philosophy",
+    "Stars and Tides",
+    "the Blade",
+    "Alchemy",
+    "Music and Verse",
+    "the Open Road",
+    "Ciphers",
+];
+
+/// Kind of institution, which selects the suffix pool used to name it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InstitutionKind {
+    /// Schools, academies and conservatories.
+    School,
+    /// Universities and colleges.
+    University,
+    /// Knightly, monastic or magical orders.
+    Order,
+}
+
+impl InstitutionKind {
+    fn templates(self) -> &'static [&'static str] {
+        match self {
+            InstitutionKind::School => &[
+                "The {founder} Conservatory of {discipline}",
+                "The {founder} Academy",
+                "The {founder} School of {discipline}",
+                "{founder} Seminary",
+            ],
+            InstitutionKind::University => &[
+                "{founder} University",
+                "The {founder} Collegium of {discipline}",
+                "Royal {founder} College",
+                "The University of {discipline} at {founder}",
+            ],
+            InstitutionKind::Order => &[
+                "The Order of {founder}",
+                "The {founder} Brotherhood of {discipline}",
+                "The Sisterhood of {founder}",
+                "Knights of {founder}",
+            ],
+        }
+    }
+}
+
+/// A generated institution, displayed by its full name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Institution {
+    /// Kind of institution.
+    pub kind: InstitutionKind,
+    /// Founder the institution is named after.
+    pub founder: String,
+    /// Field of study or devotion, for names that mention one.
+    pub discipline: Option<String>,
+    /// Full name, such as "The Velmar Conservatory of the Unseen Arts".
+    pub name: String,
+}
+
+impl fmt::Display for Institution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/// Generator for schools, universities and orders named after their founders.
+///
+/// The founder is generated for every institution unless one is bound with
+/// [`with_founder`](InstitutionGenerator::with_founder), which lets a campaign name several
+/// institutions after the same historical figure.
+///
+/// ```rust
+/// use nominae::{InstitutionGenerator, InstitutionKind};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let institutions = InstitutionGenerator::default().with_founder("Velmar");
+/// let school = institutions.generate(InstitutionKind::School, &mut rng);
+/// assert_eq!(school.founder, "Velmar");
+/// println!("{}", school);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct InstitutionGenerator {
+    founders: TotroConfig,
+    founder: Option<String>,
+}
+
+impl Default for InstitutionGenerator {
+    fn default() -> InstitutionGenerator {
+        InstitutionGenerator {
+            founders: TotroConfig::alphabetic().with_length(2, 4),
+            founder: None,
+        }
+    }
+}
+
+impl InstitutionGenerator {
+    /// Set the configuration used to generate founder names.
+    pub fn with_founders(mut self, founders: TotroConfig) -> InstitutionGenerator {
+        self.founders = founders;
+        self
+    }
+
+    /// Name every institution after `founder` instead of generating one.
+    pub fn with_founder(mut self, founder: &str) -> InstitutionGenerator {
+        self.founder = Some(founder.to_owned());
+        self
+    }
+
+    /// Generate an institution of the given kind.
+    pub fn generate<T: Rng + ?Sized>(&self, kind: InstitutionKind, rng: &mut T) -> Institution {
+        let template = kind.templates().choose(rng).unwrap();
+        let founder = match &self.founder {
+            Some(founder) => founder.clone(),
+            None => self.founders.generate(rng),
+        };
+        let discipline = if placeholders(template).contains(&"discipline") {
+            DISCIPLINES
+                .choose(rng)
+                .map(|&discipline| discipline.to_owned())
+        } else {
+            None
+        };
+        let name = expand(template, |placeholder| match placeholder {
+            "founder" => founder.clone(),
+            _ => discipline.clone().unwrap_or_default(),
+        });
+        Institution {
+            kind,
+            founder,
+            discipline,
+            name,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InstitutionGenerator, InstitutionKind};
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_founder_binding() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let institutions = InstitutionGenerator::default().with_founder("Velmar");
+        let kinds = [
+            InstitutionKind::School,
+            InstitutionKind::University,
+            InstitutionKind::Order,
+        ];
+        for _ in 0..20 {
+            for &kind in kinds.iter() {
+                let institution = institutions.generate(kind, &mut rng);
+                assert_eq!(institution.kind, kind);
+                assert!(institution.name.contains("Velmar"), "{}", institution);
+            }
+        }
+    }
+
+    #[test]
+    fn test_discipline() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let institutions = InstitutionGenerator::default();
+        for _ in 0..50 {
+            let institution = institutions.generate(InstitutionKind::University, &mut rng);
+            assert!(institution.name.contains(institution.founder.as_str()));
+            if let Some(discipline) = &institution.discipline {
+                assert!(institution.name.contains(discipline.as_str()));
+            }
+            assert!(!institution.name.contains('{'), "{}", institution);
+        }
+    }
+}
@@ -0,0 +1,343 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Per-Language Morphology
+
+use crate::evolution::Evolution;
+use crate::morphology::{adjective, derive_demonym, plural};
+use std::collections::BTreeMap;
+
+/// How a language renders the possessive of a name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Possessive {
+    /// English apostrophe-s: "Velmar's", or "Velras'" after a final s.
+    Apostrophe,
+    /// A suffix attached to the name, such as "s" for "Velmars".
+    Suffix(String),
+    /// A preposition before the name, such as "of" for "of Velmar".
+    Preposition(String),
+}
+
+/// How a language renders the plural of a name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Plural {
+    /// English "-s", or "-es" after a sibilant: "Velmars", "Velrases".
+    English,
+    /// A suffix attached to the name, such as "i" for "Velmari".
+    Suffix(String),
+    /// The name is unchanged, as in "the Velmar".
+    Invariant,
+}
+
+/// A collective form of a name for narrative text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Collective {
+    /// The members of a family or house as a group: "the Velmars".
+    Family,
+    /// Something belonging to the house of the name: `House("banners".into())` renders as
+    /// "House Velmar's banners".
+    House(String),
+    /// The people of a place or culture: "the Velmari people".
+    People,
+}
+
+/// Adjective suffixes tried, in order, by [`Language::default`].
+const ADJECTIVE_SUFFIXES: [&str; 3] = ["ic", "ine", "ian"];
+
+/// Morphology preferences of an invented language, used to derive word forms from its names.
+///
+/// ```rust
+/// use nominae::Language;
+///
+/// let elvish = Language::default().with_adjective_suffixes(vec!["ine", "ic"]);
+/// assert_eq!(elvish.derive_adjective("Velmar"), "Velmarine");
+/// assert_eq!(Language::default().derive_adjective("Velmar"), "Velmaric");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Language {
+    adjective_suffixes: Vec<String>,
+    possessive: Possessive,
+    plural: Plural,
+    case_exceptions: BTreeMap<String, String>,
+    respellings: Evolution,
+}
+
+impl Default for Language {
+    fn default() -> Language {
+        Language {
+            adjective_suffixes: ADJECTIVE_SUFFIXES
+                .iter()
+                .map(|&suffix| suffix.to_owned())
+                .collect(),
+            possessive: Possessive::Apostrophe,
+            plural: Plural::English,
+            case_exceptions: BTreeMap::new(),
+            respellings: Evolution::new(),
+        }
+    }
+}
+
+impl Language {
+    /// Set the adjective suffixes in order of preference.
+    ///
+    /// Later suffixes are used where earlier ones would join awkwardly with the stem.
+    pub fn with_adjective_suffixes<S: Into<String>>(mut self, suffixes: Vec<S>) -> Language {
+        assert!(
+            !suffixes.is_empty(),
+            "at least one adjective suffix is required"
+        );
+        self.adjective_suffixes = suffixes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Adjective suffixes in order of preference.
+    pub fn adjective_suffixes(&self) -> &[String] {
+        &self.adjective_suffixes
+    }
+
+    /// Set how possessives of names are rendered.
+    pub fn with_possessive(mut self, possessive: Possessive) -> Language {
+        self.possessive = possessive;
+        self
+    }
+
+    /// How possessives of names are rendered.
+    pub fn possessive(&self) -> &Possessive {
+        &self.possessive
+    }
+
+    /// Set how plurals of names are rendered.
+    pub fn with_plural(mut self, plural: Plural) -> Language {
+        self.plural = plural;
+        self
+    }
+
+    /// How plurals of names are rendered.
+    pub fn plural(&self) -> &Plural {
+        &self.plural
+    }
+
+    /// Add a word whose casing [`Language::capitalize`] keeps as written, such as the particles
+    /// "von" and "de" or "McDonald". A form ending in a hyphen, such as "al-", applies to the
+    /// start of hyphenated words ("al-Rashid").
+    pub fn with_case_exception<S: Into<String>>(mut self, form: S) -> Language {
+        let form = form.into();
+        self.case_exceptions.insert(form.to_lowercase(), form);
+        self
+    }
+
+    /// Words whose casing is kept as written, in order of their lowercase forms.
+    pub fn case_exceptions(&self) -> impl Iterator<Item = &str> {
+        self.case_exceptions.values().map(String::as_str)
+    }
+
+    /// Add a sound substitution applied when a name from another language is rendered in this
+    /// one, such as "th" to "t" for a language without that sound. As with [`Evolution`] rules,
+    /// a leading `^` or trailing `$` limits `from` to the start or end of the name, and
+    /// substitutions apply in the order they were added.
+    pub fn with_respelling(mut self, from: &str, to: &str) -> Language {
+        self.respellings = self.respellings.rule("respelling", from, to);
+        self
+    }
+
+    /// Sound substitutions as `(from, to)` pairs, in the order they apply.
+    pub fn respellings(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.respellings.rules().map(|(_, from, to)| (from, to))
+    }
+
+    /// `name` as speakers of this language would say and spell it, after every respelling.
+    pub fn respell(&self, name: &str) -> String {
+        self.respellings.evolve(name)
+    }
+
+    /// Capitalize each word and hyphenated part of `name`, then restore the casing of the
+    /// language's exceptions: with "von" and "al-" as exceptions, "KARL VON AL-RASHID" becomes
+    /// "Karl von al-Rashid".
+    pub fn capitalize(&self, name: &str) -> String {
+        let mut output = String::with_capacity(name.len());
+        for (idx, word) in name.split(' ').enumerate() {
+            if idx > 0 {
+                output.push(' ');
+            }
+            let lower = word.to_lowercase();
+            if let Some(form) = self.case_exceptions.get(&lower) {
+                output.push_str(form);
+                continue;
+            }
+            let particle = self.case_exceptions.iter().find(|(key, _)| {
+                key.ends_with('-') && lower.len() > key.len() && lower.starts_with(key.as_str())
+            });
+            let rest = match particle {
+                Some((key, form)) => {
+                    output.push_str(form);
+                    &lower[key.len()..]
+                }
+                None => &lower[..],
+            };
+            for (idx, part) in rest.split('-').enumerate() {
+                if idx > 0 {
+                    output.push('-');
+                }
+                let mut chars = part.chars();
+                if let Some(first) = chars.next() {
+                    output.extend(first.to_uppercase());
+                    output.push_str(chars.as_str());
+                }
+            }
+        }
+        output
+    }
+
+    /// Possessive form of `name`, such as "Velmar's", "Velmars" or "of Velmar".
+    pub fn derive_possessive(&self, name: &str) -> String {
+        match &self.possessive {
+            Possessive::Apostrophe if name.ends_with('s') => format!("{}'", name),
+            Possessive::Apostrophe => format!("{}'s", name),
+            Possessive::Suffix(suffix) => format!("{}{}", name, suffix),
+            Possessive::Preposition(preposition) => format!("{} {}", preposition, name),
+        }
+    }
+
+    /// Adjective form of `name`, as in "the Velmaric coast".
+    pub fn derive_adjective(&self, name: &str) -> String {
+        adjective(name, &self.adjective_suffixes)
+    }
+
+    /// Plural form of `name`, such as "Velmars" or "Velmari".
+    pub fn derive_plural(&self, name: &str) -> String {
+        match &self.plural {
+            Plural::English => plural(name),
+            Plural::Suffix(suffix) => format!("{}{}", name, suffix),
+            Plural::Invariant => name.to_owned(),
+        }
+    }
+
+    /// Collective form of `name` in `style`, such as "the Velmars", "House Velmar's banners" or
+    /// "the Velmari people".
+    ///
+    /// Possessions of a house follow the language's possessive, so a prepositional language
+    /// renders "the banners of House Velmar".
+    pub fn derive_collective(&self, name: &str, style: &Collective) -> String {
+        match style {
+            Collective::Family => format!("the {}", self.derive_plural(name)),
+            Collective::House(noun) => {
+                let house = format!("House {}", name);
+                match &self.possessive {
+                    Possessive::Preposition(_) => {
+                        format!("the {} {}", noun, self.derive_possessive(&house))
+                    }
+                    _ => format!("{} {}", self.derive_possessive(&house), noun),
+                }
+            }
+            Collective::People => format!("the {} people", derive_demonym(name)),
+        }
+    }
+}
+
+/// Adjective form of `name` using the default [`Language`] ("Velmar" becomes "Velmaric").
+pub fn derive_adjective(name: &str) -> String {
+    Language::default().derive_adjective(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{derive_adjective, Collective, Language, Plural, Possessive};
+
+    #[test]
+    fn test_capitalize() {
+        let language = Language::default()
+            .with_case_exception("von")
+            .with_case_exception("de")
+            .with_case_exception("al-")
+            .with_case_exception("McDonald");
+        assert_eq!(
+            language.capitalize("KARL VON AL-RASHID"),
+            "Karl von al-Rashid"
+        );
+        assert_eq!(language.capitalize("maria de la cruz"), "Maria de La Cruz");
+        assert_eq!(language.capitalize("velmar-ashport"), "Velmar-Ashport");
+        assert_eq!(language.capitalize("ronald mcdonald"), "Ronald McDonald");
+        assert_eq!(language.capitalize("al-"), "al-");
+        assert_eq!(
+            Language::default().capitalize("velmar von doran"),
+            "Velmar Von Doran"
+        );
+    }
+
+    #[test]
+    fn test_derive_adjective() {
+        assert_eq!(derive_adjective("Velmar"), "Velmaric");
+        assert_eq!(derive_adjective("Karak"), "Karakine");
+        let language = Language::default().with_adjective_suffixes(vec!["ine", "ic"]);
+        assert_eq!(language.derive_adjective("Velmar"), "Velmarine");
+        assert_eq!(language.derive_adjective("Velmin"), "Velminic");
+    }
+
+    #[test]
+    fn test_derive_possessive() {
+        let language = Language::default();
+        assert_eq!(language.derive_possessive("Velmar"), "Velmar's");
+        assert_eq!(language.derive_possessive("Velras"), "Velras'");
+        let language = language.with_possessive(Possessive::Suffix("s".into()));
+        assert_eq!(language.derive_possessive("Velmar"), "Velmars");
+        let language = language.with_possessive(Possessive::Preposition("of".into()));
+        assert_eq!(language.derive_possessive("Velmar"), "of Velmar");
+    }
+
+    #[test]
+    fn test_respell() {
+        let language = Language::default()
+            .with_respelling("^v", "w")
+            .with_respelling("ar$", "aer");
+        assert_eq!(language.respell("Velmar"), "Welmaer");
+        assert_eq!(language.respell("Avar"), "Avaer");
+        assert_eq!(language.respellings().count(), 2);
+        assert_eq!(Language::default().respell("Velmar"), "Velmar");
+    }
+
+    #[test]
+    fn test_derive_collective() {
+        let language = Language::default();
+        let banners = Collective::House("banners".into());
+        assert_eq!(
+            language.derive_collective("Velmar", &Collective::Family),
+            "the Velmars"
+        );
+        assert_eq!(
+            language.derive_collective("Velras", &Collective::Family),
+            "the Velrases"
+        );
+        assert_eq!(
+            language.derive_collective("Velmar", &banners),
+            "House Velmar's banners"
+        );
+        assert_eq!(
+            language.derive_collective("Dravan", &Collective::People),
+            "the Dravani people"
+        );
+        let language = language
+            .with_plural(Plural::Suffix("i".into()))
+            .with_possessive(Possessive::Preposition("of".into()));
+        assert_eq!(
+            language.derive_collective("Velmar", &Collective::Family),
+            "the Velmari"
+        );
+        assert_eq!(
+            language.derive_collective("Velmar", &banners),
+            "the banners of House Velmar"
+        );
+    }
+}
@@ -18,8 +18,238 @@
 //!
 //! Name Generators
 //! - Totro Fantasy Name Generator
+//! - Markov Chain Name Generator
 //!
+//! The `std` feature is enabled by default. Without it the crate is `no_std` and needs only
+//! `alloc`, keeping [`Totro`], [`TotroConfig`] and their tables, while the other generators
+//! require `std`. Configurations then build their sampling pools through a non-thread-safe
+//! cell, so they are not `Sync`.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[macro_use]
+mod trace;
+
+#[cfg(feature = "std")]
+mod affliction;
+#[cfg(feature = "std")]
+mod batch;
+#[cfg(feature = "blocklist")]
+mod blocklist;
+#[cfg(feature = "std")]
+mod constellation;
+#[cfg(feature = "std")]
+mod curator;
+#[cfg(feature = "std")]
+mod currency;
+#[cfg(feature = "std")]
+mod decoration;
+#[cfg(feature = "std")]
+mod diff;
+#[cfg(feature = "std")]
+mod email;
+#[cfg(feature = "std")]
+pub mod epithets;
+mod error;
+#[cfg(feature = "std")]
+mod evaluation;
+#[cfg(feature = "std")]
+mod evolution;
+mod explain;
+#[cfg(feature = "std")]
+mod export;
+#[cfg(feature = "fake")]
+mod faker;
+mod fit;
+#[cfg(feature = "std")]
+mod full_name;
+#[cfg(feature = "std")]
+mod gender;
+mod generator;
+#[cfg(feature = "std")]
+mod grammar;
+#[cfg(feature = "std")]
+mod guided;
+#[cfg(feature = "std")]
+mod hostname;
+#[cfg(feature = "std")]
+mod institution;
+#[cfg(feature = "std")]
+mod language;
+#[cfg(feature = "std")]
+mod markov;
+#[cfg(feature = "std")]
+mod material;
+#[cfg(feature = "std")]
+mod menu;
+#[cfg(feature = "std")]
+mod mixture;
+#[cfg(feature = "std")]
+mod morphology;
+#[cfg(feature = "std")]
+mod music;
+#[cfg(feature = "std")]
+mod name;
+#[cfg(feature = "std")]
+mod nobility;
+#[cfg(feature = "std")]
+mod normalize;
+#[cfg(feature = "std")]
+mod pack;
+mod passphrase;
+mod pattern;
+#[cfg(feature = "std")]
+mod persona;
+mod phonotactics;
+#[cfg(feature = "std")]
+mod pirate;
+#[cfg(feature = "std")]
+pub mod places;
+#[cfg(feature = "std")]
+mod population;
+#[cfg(feature = "std")]
+mod potion;
+pub mod presets;
+mod profile;
+#[cfg(feature = "std")]
+mod prophecy;
+#[cfg(feature = "std")]
+mod quality;
+#[cfg(feature = "std")]
+pub mod quick;
+#[cfg(feature = "std")]
+mod romanize;
+mod sampler;
+#[cfg(feature = "std")]
+mod screen;
+mod seeded;
+#[cfg(feature = "std")]
+mod short_code;
+#[cfg(feature = "std")]
+mod siblings;
+mod speech;
+#[cfg(feature = "std")]
+mod sports;
+mod stable;
+#[cfg(feature = "std")]
+mod storm;
+mod syllable;
+#[cfg(feature = "std")]
+mod syndicate;
+mod table;
+#[cfg(feature = "std")]
+mod template;
 mod totro;
+#[cfg(feature = "std")]
+mod unique;
+#[cfg(feature = "wasm")]
+mod wasm;
 
-pub use self::totro::Totro;
+#[cfg(feature = "std")]
+pub use self::affliction::{Affliction, AfflictionGenerator, AfflictionKind};
+#[cfg(feature = "std")]
+pub use self::batch::BatchLimits;
+#[cfg(feature = "std")]
+pub use self::constellation::{Constellation, SkyGenerator, Star};
+#[cfg(feature = "std")]
+pub use self::curator::Curator;
+#[cfg(feature = "std")]
+pub use self::currency::{Currency, CurrencyGenerator, Denomination};
+#[cfg(feature = "std")]
+pub use self::decoration::{Decoration, Decorator};
+#[cfg(feature = "std")]
+pub use self::diff::{Change, LanguageDiff, MergeStrategy, ProfileDiff};
+#[cfg(feature = "std")]
+pub use self::email::{local_part, EmailLocalParts, MAX_LOCAL_PART};
+#[cfg(feature = "std")]
+pub use self::epithets::{Epithet, EpithetForm, EpithetGenerator};
+pub use self::error::NominaeError;
+#[cfg(feature = "std")]
+pub use self::evaluation::{Evaluation, Evaluator};
+#[cfg(feature = "std")]
+pub use self::evolution::{Derivation, Evolution, Step};
+pub use self::explain::Explanation;
+#[cfg(feature = "std")]
+pub use self::export::ExportFormat;
+pub use self::fit::fit_to;
+#[cfg(feature = "std")]
+pub use self::full_name::{Family, FullName, FullNameGenerator, FullNameStyle};
+#[cfg(feature = "std")]
+pub use self::gender::{Gender, GenderDistribution, Gendered};
+pub use self::generator::{Filtered, GeneratorExt, NameGenerator};
+#[cfg(feature = "std")]
+pub use self::grammar::{Grammar, Phrase};
+#[cfg(feature = "std")]
+pub use self::guided::Guided;
+#[cfg(feature = "std")]
+pub use self::hostname::{dns_label, MAX_LABEL};
+#[cfg(feature = "std")]
+pub use self::institution::{Institution, InstitutionGenerator, InstitutionKind};
+#[cfg(feature = "std")]
+pub use self::language::{derive_adjective, Collective, Language, Plural, Possessive};
+#[cfg(feature = "std")]
+pub use self::markov::Markov;
+#[cfg(feature = "std")]
+pub use self::material::{Material, MaterialGenerator, MaterialKind};
+#[cfg(feature = "std")]
+pub use self::menu::{Attribution, DietaryTag, Dish, MenuGenerator};
+#[cfg(feature = "std")]
+pub use self::mixture::{Mixed, Mixture};
+#[cfg(feature = "std")]
+pub use self::morphology::{derive_demonym, derive_language_name};
+#[cfg(feature = "std")]
+pub use self::music::Discography;
+#[cfg(feature = "std")]
+pub use self::name::{Name, Origin};
+#[cfg(feature = "std")]
+pub use self::nobility::{Title, TitleChain, TitleGenerator};
+#[cfg(feature = "std")]
+pub use self::normalize::{eq_normalized, normalize};
+#[cfg(feature = "std")]
+pub use self::pack::{Pack, PackMetadata, PackRegistry, PACK_EXTENSION};
+pub use self::passphrase::{Passphrase, PassphraseGenerator};
+pub use self::pattern::Pattern;
+#[cfg(feature = "std")]
+pub use self::persona::{Persona, PersonaGenerator, CSV_HEADER};
+pub use self::phonotactics::{Adjacency, Constraint, TokenClass};
+#[cfg(feature = "std")]
+pub use self::pirate::{Pirate, PirateStyle};
+#[cfg(feature = "std")]
+pub use self::places::{Place, PlaceGenerator, PlaceKind};
+#[cfg(feature = "std")]
+pub use self::population::{Demographics, Population, Trend};
+#[cfg(feature = "std")]
+pub use self::potion::{Potion, PotionGenerator};
+pub use self::profile::ProfileVersion;
+#[cfg(feature = "std")]
+pub use self::prophecy::Prophecy;
+#[cfg(feature = "std")]
+pub use self::quality::score;
+#[cfg(feature = "std")]
+pub use self::romanize::{romanize, Romanization, Scheme};
+#[cfg(feature = "std")]
+pub use self::screen::{Flagged, Report, Screen, Violation};
+pub use self::seeded::Seeded;
+#[cfg(feature = "std")]
+pub use self::short_code::short_codes;
+#[cfg(feature = "std")]
+pub use self::siblings::Similarity;
+pub use self::speech::{Syllabified, IPA_ATTRIBUTE};
+#[cfg(feature = "std")]
+pub use self::sports::{LeagueGenerator, Team};
+pub use self::stable::{seed_from_bytes, seed_from_str, StableRng};
+#[cfg(feature = "std")]
+pub use self::storm::{StormSeries, StormState};
+pub use self::syllable::{Legality, Rhythm, Syllabic};
+#[cfg(feature = "std")]
+pub use self::syndicate::{Structure, SyndicateGenerator};
+pub use self::table::{
+    Attribute, Table, TableError, Token, AIW, BEW, BMW, BOW, EOW, MEW, MOW, NIW,
+};
+pub use self::totro::{LengthModel, LengthRange, Mode, Names, Overrides, Totro, TotroConfig};
+#[cfg(feature = "std")]
+pub use self::unique::UniqueNames;
+#[cfg(feature = "wasm")]
+pub use self::wasm::generate_totro;
@@ -0,0 +1,527 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Markov Chain Name Models
+
+use crate::error::NominaeError;
+use crate::stable::below;
+use rand::Rng;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Marks both the padding before a name and the end of a name within contexts.
+const BOUNDARY: char = '\0';
+
+/// Leading bytes of the compact serialized form.
+const MAGIC: &[u8; 4] = b"NMK1";
+
+/// Largest quantized count in the compact form.
+const QUANTUM: u32 = u8::MAX as u32;
+
+/// Longest name generated before sampling is cut off, in characters.
+const MAX_CHARS: usize = 32;
+
+/// Names sampled when conditioning on length before settling for the last one.
+const LENGTH_ATTEMPTS: usize = 100;
+
+/// A character-level Markov chain trained on example names.
+///
+/// The model counts which character follows each context of `order` characters. Training is
+/// incremental: [`Markov::update`] folds more names into the existing counts, so a live tool can
+/// keep learning from names its users approve without retraining from scratch.
+///
+/// ```rust
+/// use nominae::Markov;
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut model = Markov::train(2, &["Velmar", "Velda", "Maren", "Doran"]).with_length(4, 8);
+/// model.update(&["Veris"]);
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// println!("{}", model.generate(&mut rng));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Markov {
+    order: usize,
+    counts: BTreeMap<String, BTreeMap<char, u32>>,
+    names: BTreeSet<String>,
+    length: Option<(u8, u8)>,
+}
+
+impl Markov {
+    /// Create an untrained model with contexts of `order` characters.
+    pub fn new(order: usize) -> Markov {
+        assert!(order > 0, "order must be at least 1");
+        Markov {
+            order,
+            counts: BTreeMap::new(),
+            names: BTreeSet::new(),
+            length: None,
+        }
+    }
+
+    /// Train a model with contexts of `order` characters on `names`.
+    ///
+    /// Orders of 2 or 3 suit most corpora: lower orders wander away from the source language,
+    /// while higher orders need many names to avoid copying them outright.
+    pub fn train<I>(order: usize, names: I) -> Markov
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut model = Markov::new(order);
+        model.update(names);
+        model
+    }
+
+    /// Set the range of characters in generated names. `max` is exclusive unless it is equal to
+    /// `min`, as with [`TotroConfig::with_length`](crate::TotroConfig::with_length).
+    ///
+    /// Sampling is conditioned on the range rather than filtered afterwards: the end of a name
+    /// cannot be drawn before `min` characters, and a name must be able to end by its longest
+    /// allowed length.
+    ///
+    /// A range that is empty or starts at zero is reported by [`Markov::validate`].
+    pub fn with_length(mut self, min: u8, max: u8) -> Markov {
+        self.length = Some((min, max));
+        self
+    }
+
+    /// Check that the [length](Markov::with_length) range, if one was set, starts at one
+    /// character or more and has its minimum no greater than its maximum.
+    pub fn validate(&self) -> Result<(), NominaeError> {
+        match self.length {
+            Some((min, max)) if min > max => Err(NominaeError::InvalidRange {
+                min: min as usize,
+                max: max as usize,
+            }),
+            Some((0, _)) => Err(NominaeError::OutOfRange(
+                "minimum length 0 must be at least one character".into(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Range of characters in generated names, if one was set.
+    pub fn length(&self) -> Option<(u8, u8)> {
+        self.length
+    }
+
+    /// Number of characters of context the model conditions on.
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// Lowercased training names, in sorted order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+
+    /// Number of distinct contexts seen in training.
+    pub fn contexts(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Fold `names` into the model's counts, leaving everything learned so far in place.
+    ///
+    /// Names are lowercased and blank names are skipped. Training on the same name twice counts
+    /// it twice, which weights names designers approve repeatedly.
+    pub fn update<I>(&mut self, names: I)
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        for name in names {
+            let name = name.as_ref().trim().to_lowercase();
+            if name.is_empty() {
+                continue;
+            }
+            let mut context: Vec<char> = vec![BOUNDARY; self.order];
+            for next in name.chars().chain(Some(BOUNDARY)) {
+                let key: String = context.iter().collect();
+                *self.counts.entry(key).or_default().entry(next).or_insert(0) += 1;
+                context.remove(0);
+                context.push(next);
+            }
+            self.names.insert(name);
+        }
+    }
+
+    /// Drop transitions seen fewer than `min_count` times, and contexts left with none.
+    ///
+    /// Rare transitions are mostly noise from single training names, so pruning them shrinks
+    /// large models considerably. A name whose context was pruned away ends early.
+    pub fn prune(&mut self, min_count: u32) {
+        for next in self.counts.values_mut() {
+            next.retain(|_, count| *count >= min_count);
+        }
+        self.counts.retain(|_, next| !next.is_empty());
+    }
+
+    /// Serialize the model compactly for memory-constrained targets.
+    ///
+    /// Characters are interned into an alphabet so each context is stored as a few small
+    /// indices, and counts are quantized to a byte relative to the most frequent transition of
+    /// their context. Training names are not stored, so a decoded model can no longer tell
+    /// which names it was trained on.
+    pub fn to_compact(&self) -> Vec<u8> {
+        let alphabet = self.alphabet();
+        let index = |c: char| alphabet.binary_search(&c).unwrap() as u32;
+        let mut bytes = MAGIC.to_vec();
+        write_varint(&mut bytes, self.order as u32);
+        write_varint(&mut bytes, alphabet.len() as u32);
+        for &c in &alphabet {
+            write_varint(&mut bytes, c as u32);
+        }
+        write_varint(&mut bytes, self.counts.len() as u32);
+        for (context, next) in &self.counts {
+            for c in context.chars() {
+                write_varint(&mut bytes, index(c));
+            }
+            let max = next.values().copied().max().unwrap_or(1) as u64;
+            write_varint(&mut bytes, next.len() as u32);
+            for (&c, &count) in next {
+                write_varint(&mut bytes, index(c));
+                let quantized = (count as u64 * QUANTUM as u64 + max / 2) / max;
+                bytes.push(quantized.max(1) as u8);
+            }
+        }
+        bytes
+    }
+
+    /// Decode a model written by [`Markov::to_compact`].
+    pub fn from_compact(bytes: &[u8]) -> Result<Markov, NominaeError> {
+        let invalid = |message: &str| NominaeError::InvalidModel(message.to_owned());
+        let mut rest = bytes
+            .strip_prefix(&MAGIC[..])
+            .ok_or_else(|| invalid("missing header"))?;
+        let order = read_varint(&mut rest)? as usize;
+        if order == 0 {
+            return Err(invalid("order must be at least 1"));
+        }
+        let alphabet = (0..read_varint(&mut rest)?)
+            .map(|_| {
+                char::from_u32(read_varint(&mut rest)?).ok_or_else(|| invalid("invalid character"))
+            })
+            .collect::<Result<Vec<char>, NominaeError>>()?;
+        let letter = |rest: &mut &[u8]| {
+            let idx = read_varint(rest)? as usize;
+            alphabet
+                .get(idx)
+                .copied()
+                .ok_or_else(|| invalid("character index out of range"))
+        };
+        let mut counts = BTreeMap::new();
+        for _ in 0..read_varint(&mut rest)? {
+            let context = (0..order)
+                .map(|_| letter(&mut rest))
+                .collect::<Result<String, NominaeError>>()?;
+            let mut next = BTreeMap::new();
+            for _ in 0..read_varint(&mut rest)? {
+                let c = letter(&mut rest)?;
+                let (&count, tail) = rest.split_first().ok_or_else(|| invalid("truncated"))?;
+                rest = tail;
+                next.insert(c, count as u32);
+            }
+            counts.insert(context, next);
+        }
+        if !rest.is_empty() {
+            return Err(invalid("trailing bytes"));
+        }
+        Ok(Markov {
+            order,
+            counts,
+            names: BTreeSet::new(),
+            length: None,
+        })
+    }
+
+    /// Sorted characters seen in training, including the boundary marker.
+    fn alphabet(&self) -> Vec<char> {
+        self.counts
+            .iter()
+            .flat_map(|(context, next)| context.chars().chain(next.keys().copied()))
+            .collect::<BTreeSet<char>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Natural log of the probability the model gives `name`, and the number of transitions
+    /// scored including the end of the name.
+    ///
+    /// Counts are smoothed by adding `smoothing` to every character of the alphabet plus one
+    /// slot for characters never seen, so a positive `smoothing` keeps unseen transitions from
+    /// scoring negative infinity.
+    pub(crate) fn log_likelihood(&self, name: &str, smoothing: f64) -> (f64, usize) {
+        let scorer = self.scorer(smoothing);
+        let mut context: Vec<char> = vec![BOUNDARY; self.order];
+        let mut total = 0.0;
+        let mut transitions = 0;
+        for next in name.trim().to_lowercase().chars().chain(Some(BOUNDARY)) {
+            let key: String = context.iter().collect();
+            total += scorer.probability(&key, next).ln();
+            transitions += 1;
+            context.remove(0);
+            context.push(next);
+        }
+        (total, transitions)
+    }
+
+    /// Smoothed transition probabilities of this model, as used by
+    /// [`Markov::log_likelihood`].
+    pub(crate) fn scorer(&self, smoothing: f64) -> Scorer<'_> {
+        Scorer {
+            model: self,
+            smoothing,
+            symbols: (self.alphabet().len() + 1) as f64,
+        }
+    }
+
+    /// Whether `name` was one of the training names, ignoring case.
+    pub(crate) fn is_training_name(&self, name: &str) -> bool {
+        self.names.contains(&name.trim().to_lowercase())
+    }
+
+    /// Generate a capitalized name, or an empty one if the model is untrained.
+    ///
+    /// With a [length](Markov::with_length) set, a name the model cannot fit to it within 100
+    /// attempts is returned as last sampled.
+    pub fn generate<T: Rng + ?Sized>(&self, rng: &mut T) -> String {
+        let (shortest, longest) = match self.length {
+            Some((min, max)) if min < max => (min as usize, max as usize - 1),
+            Some((min, _)) => (min as usize, min as usize),
+            None => return capitalize(self.sample(0, MAX_CHARS, rng).0),
+        };
+        let mut name = String::new();
+        for _ in 0..LENGTH_ATTEMPTS {
+            let (candidate, ended) = self.sample(shortest, longest, rng);
+            name = candidate;
+            if ended {
+                break;
+            }
+        }
+        capitalize(name)
+    }
+
+    /// Sample a name of at most `longest` characters whose end is never drawn before
+    /// `shortest`, along with whether it ended within that range.
+    fn sample<T: Rng + ?Sized>(
+        &self,
+        shortest: usize,
+        longest: usize,
+        rng: &mut T,
+    ) -> (String, bool) {
+        let mut context: Vec<char> = vec![BOUNDARY; self.order];
+        let mut name = String::new();
+        for len in 0..=longest {
+            let key: String = context.iter().collect();
+            let counts = match self.counts.get(&key) {
+                Some(counts) => counts,
+                None => return (name, len >= shortest),
+            };
+            let next = if len == longest {
+                None
+            } else {
+                draw(counts, len >= shortest, rng)
+            };
+            match next {
+                Some(BOUNDARY) => return (name, true),
+                Some(next) => {
+                    name.push(next);
+                    context.remove(0);
+                    context.push(next);
+                }
+                None => return (name, len == longest && counts.contains_key(&BOUNDARY)),
+            }
+        }
+        (name, false)
+    }
+}
+
+/// Smoothed transition probabilities of a [`Markov`] model.
+pub(crate) struct Scorer<'a> {
+    model: &'a Markov,
+    smoothing: f64,
+    /// Number of possible next characters: the alphabet plus one slot for unseen ones.
+    symbols: f64,
+}
+
+impl Scorer<'_> {
+    /// Probability of `text` following `prefix`, and then of the name ending if `end` is set.
+    pub(crate) fn continuation(&self, prefix: &str, text: &str, end: bool) -> f64 {
+        let order = self.model.order;
+        let mut context: Vec<char> = vec![BOUNDARY; order];
+        context.extend(prefix.chars().flat_map(char::to_lowercase));
+        let ending = if end { Some(BOUNDARY) } else { None };
+        let mut probability = 1.0;
+        for next in text.chars().flat_map(char::to_lowercase).chain(ending) {
+            let key: String = context[context.len() - order..].iter().collect();
+            probability *= self.probability(&key, next);
+            context.push(next);
+        }
+        probability
+    }
+
+    /// Probability of `next` following `context`.
+    fn probability(&self, context: &str, next: char) -> f64 {
+        let (count, seen) = self.model.counts.get(context).map_or((0, 0), |counts| {
+            let seen: u64 = counts.values().map(|&count| count as u64).sum();
+            (counts.get(&next).copied().unwrap_or(0), seen)
+        });
+        let denominator = seen as f64 + self.smoothing * self.symbols;
+        if denominator > 0.0 {
+            (count as f64 + self.smoothing) / denominator
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Draw a character from `counts` with probability proportional to its count, leaving out the
+/// end of the name unless `end` is set.
+fn draw<T: Rng + ?Sized>(counts: &BTreeMap<char, u32>, end: bool, rng: &mut T) -> Option<char> {
+    let allowed = counts.iter().filter(|&(&next, _)| end || next != BOUNDARY);
+    let total: u64 = allowed.clone().map(|(_, &count)| count as u64).sum();
+    if total == 0 {
+        return None;
+    }
+    let mut roll = below(rng, total);
+    for (&next, &count) in allowed {
+        if roll < count as u64 {
+            return Some(next);
+        }
+        roll -= count as u64;
+    }
+    None
+}
+
+/// Append `value` as a little-endian base-128 varint.
+fn write_varint(bytes: &mut Vec<u8>, mut value: u32) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+/// Read a varint written by [`write_varint`], advancing `bytes` past it.
+fn read_varint(bytes: &mut &[u8]) -> Result<u32, NominaeError> {
+    let mut value: u32 = 0;
+    for shift in (0..35).step_by(7) {
+        let (&byte, rest) = bytes
+            .split_first()
+            .ok_or_else(|| NominaeError::InvalidModel("truncated".to_owned()))?;
+        *bytes = rest;
+        value |= ((byte & 0x7F) as u32)
+            .checked_shl(shift)
+            .ok_or_else(|| NominaeError::InvalidModel("varint overflow".to_owned()))?;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(NominaeError::InvalidModel("varint overflow".to_owned()))
+}
+
+fn capitalize(name: String) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Markov;
+    use crate::error::NominaeError;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_generate() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        assert_eq!(Markov::new(2).generate(&mut rng), "");
+        let mut model = Markov::new(3);
+        model.update(&["Velmar"]);
+        for _ in 0..10 {
+            assert_eq!(model.generate(&mut rng), "Velmar");
+        }
+    }
+
+    #[test]
+    fn test_update() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut model = Markov::new(2);
+        model.update(&["Velmar", "Velda"]);
+        let contexts = model.contexts();
+        model.update(vec!["Doran".to_owned(), " ".to_owned()]);
+        assert!(model.contexts() > contexts);
+        assert_eq!(
+            model.names().collect::<Vec<_>>(),
+            vec!["doran", "velda", "velmar"]
+        );
+        assert_eq!(model, Markov::train(2, &["Velmar", "Velda", "Doran"]));
+        for _ in 0..100 {
+            let name = model.generate(&mut rng).to_lowercase();
+            assert!(name.starts_with('v') || name.starts_with('d'), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_prune_and_compact() {
+        let mut model = Markov::new(2);
+        model.update(&["Velmar", "Velda", "Velmar", "Veris", "Maren"]);
+        let compact = model.to_compact();
+        let decoded = Markov::from_compact(&compact).unwrap();
+        assert_eq!(decoded.order(), 2);
+        assert_eq!(decoded.contexts(), model.contexts());
+        assert_eq!(decoded.to_compact(), compact);
+        let mut rng = SmallRng::seed_from_u64(0);
+        assert!(!decoded.generate(&mut rng).is_empty());
+        model.prune(2);
+        assert!(model.contexts() < decoded.contexts());
+        assert!(model.to_compact().len() < compact.len());
+        assert!(matches!(
+            Markov::from_compact(&compact[..compact.len() - 1]),
+            Err(NominaeError::InvalidModel(_))
+        ));
+        assert!(Markov::from_compact(b"nope").is_err());
+    }
+
+    #[test]
+    fn test_length() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut model = Markov::new(2);
+        model.update(&["Ka", "Kara", "Karama", "Ama", "Marak", "Ramakara"]);
+        let model = model.with_length(4, 7);
+        for _ in 0..200 {
+            let name = model.generate(&mut rng);
+            assert!((4..7).contains(&name.chars().count()), "{}", name);
+        }
+        let exact = model.clone().with_length(5, 5);
+        assert_eq!(exact.validate(), Ok(()));
+        for _ in 0..200 {
+            assert_eq!(exact.generate(&mut rng).chars().count(), 5);
+        }
+        assert_eq!(
+            model.clone().with_length(6, 3).validate(),
+            Err(NominaeError::InvalidRange { min: 6, max: 3 })
+        );
+        assert!(matches!(
+            model.with_length(0, 3).validate(),
+            Err(NominaeError::OutOfRange(_))
+        ));
+    }
+}
@@ -0,0 +1,187 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Color and Material Name Generation
+
+use crate::morphology::attach;
+use crate::totro::TotroConfig;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::fmt;
+
+/// Evocative English stems used for compound names such as "ashglass".
+const COMPOUND_STEMS: [&str; 10] = [
+    "ash", "frost", "storm", "moon", "ember", "dusk", "blood", "star", "sun", "shadow",
+];
+
+/// Category of crafting material, each with its own suffix morphology.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MaterialKind {
+    /// Pigments and dyes, such as "veldine" or "maruvian blue".
+    Pigment,
+    /// Minerals and gemstones, such as "veldrite" or "ashglass".
+    Mineral,
+    /// Metals and alloys, such as "orvium" or "duskiron".
+    Metal,
+    /// Fabrics, such as "maruvian silk".
+    Fabric,
+}
+
+impl MaterialKind {
+    /// Suffixes attached to an invented stem.
+    fn suffixes(self) -> &'static [&'static str] {
+        match self {
+            MaterialKind::Pigment => &["ine", "ilion", "ade", "een"],
+            MaterialKind::Mineral => &["ite", "ine", "yst", "ar", "spar"],
+            MaterialKind::Metal => &["ium", "um", "il", "os"],
+            MaterialKind::Fabric => &["ian", "ine", "ese"],
+        }
+    }
+
+    /// Words following an adjectival stem ("maruvian silk") or ending a compound ("ashglass").
+    fn heads(self) -> &'static [&'static str] {
+        match self {
+            MaterialKind::Pigment => &["blue", "red", "ochre", "green", "violet", "umber"],
+            MaterialKind::Mineral => &["glass", "stone", "quartz", "salt"],
+            MaterialKind::Metal => &["iron", "steel", "silver", "gold"],
+            MaterialKind::Fabric => &["silk", "velvet", "wool", "linen", "brocade"],
+        }
+    }
+}
+
+/// A generated material name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Material {
+    /// Category of the material.
+    pub kind: MaterialKind,
+    /// Invented or English stem the name was built from, such as "maruv".
+    pub stem: String,
+    /// Full material name, such as "maruvian silk".
+    pub name: String,
+}
+
+impl fmt::Display for Material {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/// Generator for invented pigment, mineral, metal and fabric names.
+///
+/// Names are built in one of three shapes: an invented stem with a category suffix
+/// ("veldrite"), an English compound ("ashglass"), or, for pigments and fabrics, an adjectival
+/// stem followed by a head word ("maruvian silk").
+///
+/// ```rust
+/// use nominae::{MaterialGenerator, MaterialKind};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let materials = MaterialGenerator::default();
+/// println!("{}", materials.generate(MaterialKind::Mineral, &mut rng));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct MaterialGenerator {
+    stems: TotroConfig,
+    compound: f64,
+}
+
+impl Default for MaterialGenerator {
+    fn default() -> MaterialGenerator {
+        MaterialGenerator {
+            stems: TotroConfig::alphabetic().with_length(2, 4),
+            compound: 0.25,
+        }
+    }
+}
+
+impl MaterialGenerator {
+    /// Set the configuration used to generate invented stems.
+    pub fn with_stems(mut self, stems: TotroConfig) -> MaterialGenerator {
+        self.stems = stems;
+        self
+    }
+
+    /// Set the probability of an English compound such as "ashglass".
+    pub fn with_compound(mut self, probability: f64) -> MaterialGenerator {
+        assert!(
+            (0.0..=1.0).contains(&probability),
+            "compound probability must be within 0..=1"
+        );
+        self.compound = probability;
+        self
+    }
+
+    /// Generate a material name of the given kind.
+    pub fn generate<T: Rng + ?Sized>(&self, kind: MaterialKind, rng: &mut T) -> Material {
+        let head = kind.heads().choose(rng).unwrap();
+        if rng.gen_bool(self.compound) {
+            let stem = COMPOUND_STEMS.choose(rng).unwrap();
+            return Material {
+                kind,
+                stem: stem.to_string(),
+                name: attach(stem, head),
+            };
+        }
+        let stem = self.stems.generate(rng).to_lowercase();
+        let suffix = kind.suffixes().choose(rng).unwrap();
+        let name = match kind {
+            MaterialKind::Pigment | MaterialKind::Fabric if rng.gen() => {
+                format!("{} {}", attach(&stem, "ian"), head)
+            }
+            _ => attach(&stem, suffix),
+        };
+        Material { kind, stem, name }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MaterialGenerator, MaterialKind};
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_generate() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let materials = MaterialGenerator::default().with_compound(0.0);
+        for _ in 0..50 {
+            let fabric = materials.generate(MaterialKind::Fabric, &mut rng);
+            assert_eq!(fabric.name, fabric.name.to_lowercase());
+            assert!(
+                !fabric.name.contains(' ') || fabric.name.contains("ian "),
+                "{}",
+                fabric
+            );
+            let metal = materials.generate(MaterialKind::Metal, &mut rng);
+            assert!(!metal.name.contains(' '), "{}", metal);
+        }
+    }
+
+    #[test]
+    fn test_compound() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mineral = MaterialGenerator::default()
+            .with_compound(1.0)
+            .generate(MaterialKind::Mineral, &mut rng);
+        assert!(
+            mineral.name.starts_with(mineral.stem.as_str()),
+            "{}",
+            mineral
+        );
+    }
+}
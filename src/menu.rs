@@ -0,0 +1,248 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Tavern Dish and Menu Generation
+
+use crate::totro::TotroConfig;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::fmt;
+
+/// Dietary facts about a dish, for gameplay hooks such as vegetarian characters or allergies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DietaryTag {
+    /// Contains meat.
+    Meat,
+    /// Contains fish or seafood.
+    Fish,
+    /// Contains milk, butter or cheese.
+    Dairy,
+    /// Contains eggs.
+    Egg,
+    /// Contains wheat or other glutenous grain.
+    Gluten,
+}
+
+use self::DietaryTag::*;
+
+const METHODS: [(&str, &[DietaryTag]); 12] = [
+    ("Smoked", &[]),
+    ("Roasted", &[]),
+    ("Salted", &[]),
+    ("Spiced", &[]),
+    ("Honeyed", &[]),
+    ("Stewed", &[]),
+    ("Pickled", &[]),
+    ("Charred", &[]),
+    ("Buttered", &[Dairy]),
+    ("Cheese-Crusted", &[Dairy]),
+    ("Battered", &[Egg, Gluten]),
+    ("Pan-Fried", &[]),
+];
+
+const INGREDIENTS: [(&str, &[DietaryTag]); 16] = [
+    ("Riverloaf", &[Gluten]),
+    ("Boar Ribs", &[Meat]),
+    ("Mutton", &[Meat]),
+    ("Venison", &[Meat]),
+    ("Goose", &[Meat]),
+    ("Eel", &[Fish]),
+    ("Trout", &[Fish]),
+    ("Oysters", &[Fish]),
+    ("Turnips", &[]),
+    ("Mushrooms", &[]),
+    ("Leeks", &[]),
+    ("Barley Porridge", &[Gluten]),
+    ("Oatcakes", &[Gluten]),
+    ("Curds", &[Dairy]),
+    ("Duck Eggs", &[Egg]),
+    ("Plums", &[]),
+];
+
+/// Who or where a dish is named after.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Attribution {
+    /// Named after a person, as in "Maren's Smoked Riverloaf".
+    Person(String),
+    /// Named after a place, as in "Smoked Riverloaf of Velmar".
+    Place(String),
+}
+
+/// A generated menu item.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dish {
+    /// Cooking method, such as "Smoked".
+    pub method: String,
+    /// Main ingredient, such as "Riverloaf".
+    pub ingredient: String,
+    /// Person or place the dish is named after.
+    pub attribution: Option<Attribution>,
+    /// Sorted dietary tags of the method and ingredient.
+    pub tags: Vec<DietaryTag>,
+}
+
+impl Dish {
+    /// Whether the dish contains `tag`.
+    pub fn has(&self, tag: DietaryTag) -> bool {
+        self.tags.contains(&tag)
+    }
+
+    /// Whether the dish contains neither meat nor fish.
+    pub fn is_vegetarian(&self) -> bool {
+        !self.has(Meat) && !self.has(Fish)
+    }
+
+    /// Whether the dish contains no animal products.
+    pub fn is_vegan(&self) -> bool {
+        self.is_vegetarian() && !self.has(Dairy) && !self.has(Egg)
+    }
+}
+
+impl fmt::Display for Dish {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.attribution {
+            Some(Attribution::Person(person)) => {
+                write!(f, "{}'s {} {}", person, self.method, self.ingredient)
+            }
+            Some(Attribution::Place(place)) => {
+                write!(f, "{} {} of {}", self.method, self.ingredient, place)
+            }
+            None => write!(f, "{} {}", self.method, self.ingredient),
+        }
+    }
+}
+
+/// Generator for tavern [`Dish`]es.
+///
+/// ```rust
+/// use nominae::MenuGenerator;
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// for dish in MenuGenerator::default().menu(5, &mut rng) {
+///     let tag = if dish.is_vegetarian() { " (v)" } else { "" };
+///     println!("{}{}", dish, tag);
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct MenuGenerator {
+    names: TotroConfig,
+    attribution: f64,
+}
+
+impl Default for MenuGenerator {
+    fn default() -> MenuGenerator {
+        MenuGenerator {
+            names: TotroConfig::alphabetic().with_length(2, 4),
+            attribution: 0.5,
+        }
+    }
+}
+
+impl MenuGenerator {
+    /// Set the configuration used to generate person and place names.
+    pub fn with_names(mut self, names: TotroConfig) -> MenuGenerator {
+        self.names = names;
+        self
+    }
+
+    /// Set the probability that a dish is named after a person or place.
+    pub fn with_attribution(mut self, probability: f64) -> MenuGenerator {
+        assert!(
+            (0.0..=1.0).contains(&probability),
+            "attribution probability must be within 0..=1"
+        );
+        self.attribution = probability;
+        self
+    }
+
+    /// Generate a single dish.
+    pub fn dish<T: Rng + ?Sized>(&self, rng: &mut T) -> Dish {
+        let ingredient = INGREDIENTS.choose(rng).unwrap();
+        self.cook(ingredient, rng)
+    }
+
+    /// Generate a menu of up to `n` dishes, each with a different main ingredient.
+    pub fn menu<T: Rng + ?Sized>(&self, n: usize, rng: &mut T) -> Vec<Dish> {
+        let ingredients: Vec<_> = INGREDIENTS.choose_multiple(rng, n).collect();
+        ingredients
+            .into_iter()
+            .map(|ingredient| self.cook(ingredient, rng))
+            .collect()
+    }
+
+    fn cook<T: Rng + ?Sized>(&self, ingredient: &(&str, &[DietaryTag]), rng: &mut T) -> Dish {
+        let method = METHODS.choose(rng).unwrap();
+        let attribution = if rng.gen_bool(self.attribution) {
+            let name = self.names.generate(rng);
+            Some(if rng.gen() {
+                Attribution::Person(name)
+            } else {
+                Attribution::Place(name)
+            })
+        } else {
+            None
+        };
+        let mut tags: Vec<DietaryTag> = method.1.iter().chain(ingredient.1).copied().collect();
+        tags.sort();
+        tags.dedup();
+        Dish {
+            method: method.0.to_owned(),
+            ingredient: ingredient.0.to_owned(),
+            attribution,
+            tags,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Attribution, DietaryTag, Dish, MenuGenerator};
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_dish() {
+        let mut dish = Dish {
+            method: "Smoked".into(),
+            ingredient: "Riverloaf".into(),
+            attribution: Some(Attribution::Person("Maren".into())),
+            tags: vec![DietaryTag::Gluten],
+        };
+        assert_eq!(dish.to_string(), "Maren's Smoked Riverloaf");
+        assert!(dish.is_vegan());
+        dish.attribution = Some(Attribution::Place("Velmar".into()));
+        dish.tags.push(DietaryTag::Dairy);
+        assert_eq!(dish.to_string(), "Smoked Riverloaf of Velmar");
+        assert!(dish.is_vegetarian() && !dish.is_vegan());
+    }
+
+    #[test]
+    fn test_menu() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let menu = MenuGenerator::default().menu(8, &mut rng);
+        assert_eq!(menu.len(), 8);
+        for dish in &menu {
+            assert_eq!(
+                menu.iter()
+                    .filter(|other| other.ingredient == dish.ingredient)
+                    .count(),
+                1
+            );
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Weighted Preset Mixtures
+
+use crate::stable::below;
+use crate::totro::TotroConfig;
+use rand::Rng;
+
+/// A name drawn from a [`Mixture`] along with the preset that produced it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mixed<'a> {
+    /// Label of the preset used.
+    pub preset: &'a str,
+    /// Position of the preset in the mixture, in the order presets were added.
+    pub index: usize,
+    /// The generated name.
+    pub name: String,
+}
+
+/// A composite generator that draws each name from one of several labelled presets, chosen by
+/// weight, so one generator can serve a mixed population.
+///
+/// ```rust
+/// use nominae::{presets, Mixture, TotroConfig};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let city = Mixture::default()
+///     .with_preset("townsfolk", TotroConfig::alphabetic(), 70)
+///     .with_preset("short", TotroConfig::alphabetic().with_length(2, 3), 20)
+///     .with_preset("pet", presets::pet(), 10);
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let resident = city.generate(&mut rng);
+/// println!("{} ({})", resident.name, resident.preset);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Mixture {
+    presets: Vec<(String, TotroConfig, u32)>,
+}
+
+impl Mixture {
+    /// Add a preset drawn with relative `weight`.
+    pub fn with_preset<S: Into<String>>(
+        mut self,
+        label: S,
+        config: TotroConfig,
+        weight: u32,
+    ) -> Mixture {
+        self.presets.push((label.into(), config, weight));
+        self
+    }
+
+    /// Labels of the presets, in the order they were added.
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.presets.iter().map(|(label, _, _)| label.as_str())
+    }
+
+    /// Configuration of the preset labelled `label`.
+    pub fn preset(&self, label: &str) -> Option<&TotroConfig> {
+        self.presets
+            .iter()
+            .find(|(name, _, _)| name == label)
+            .map(|(_, config, _)| config)
+    }
+
+    /// Generate a name from a preset chosen by the configured weights.
+    ///
+    /// Panics if no preset has a positive weight.
+    pub fn generate<T: Rng + ?Sized>(&self, rng: &mut T) -> Mixed<'_> {
+        let weights: Vec<u32> = self.presets.iter().map(|&(_, _, weight)| weight).collect();
+        self.generate_weighted(&weights, rng)
+    }
+
+    /// Generate a name using `weights` in place of the configured ones, one per preset, so a
+    /// single mixture can serve districts with different populations.
+    ///
+    /// Panics if `weights` does not have one entry per preset or none is positive.
+    pub fn generate_weighted<T: Rng + ?Sized>(&self, weights: &[u32], rng: &mut T) -> Mixed<'_> {
+        assert_eq!(
+            weights.len(),
+            self.presets.len(),
+            "one weight is required per preset"
+        );
+        let total: u64 = weights.iter().map(|&weight| weight as u64).sum();
+        assert!(total > 0, "at least one preset must have a positive weight");
+        let mut roll = below(rng, total);
+        let index = weights
+            .iter()
+            .position(|&weight| {
+                let hit = roll < weight as u64;
+                roll = roll.saturating_sub(weight as u64);
+                hit
+            })
+            .expect("roll is below the total weight");
+        let (label, config, _) = &self.presets[index];
+        Mixed {
+            preset: label,
+            index,
+            name: config.generate(rng),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Mixture;
+    use crate::totro::TotroConfig;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_generate() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mixture = Mixture::default()
+            .with_preset("long", TotroConfig::alphabetic().with_length(6, 6), 3)
+            .with_preset("short", TotroConfig::alphabetic().with_length(1, 1), 1);
+        let long = (0..4000)
+            .filter(|_| {
+                let mixed = mixture.generate(&mut rng);
+                assert_eq!(mixed.preset == "long", mixed.index == 0);
+                mixed.preset == "long"
+            })
+            .count();
+        assert!((2800..3200).contains(&long), "{}", long);
+        assert_eq!(mixture.labels().collect::<Vec<_>>(), vec!["long", "short"]);
+    }
+
+    #[test]
+    fn test_generate_weighted() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mixture = Mixture::default()
+            .with_preset("a", TotroConfig::default(), 1)
+            .with_preset("b", TotroConfig::default(), 1);
+        for _ in 0..100 {
+            assert_eq!(mixture.generate_weighted(&[0, 5], &mut rng).preset, "b");
+        }
+        assert!(mixture.preset("a").is_some() && mixture.preset("c").is_none());
+    }
+}
@@ -0,0 +1,203 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Suffix Morphology
+
+/// English place-name elements whose inhabitants take "-er" ("Ashland" gives "Ashlander").
+const AGENT_ELEMENTS: [&str; 7] = ["land", "hold", "dale", "moor", "fall", "haven", "mouth"];
+
+/// English place-name elements whose inhabitants take "-ian" ("Ashport" gives "Ashportian").
+const RELATIONAL_ELEMENTS: [&str; 11] = [
+    "port", "ford", "ton", "wick", "ham", "burg", "stead", "field", "wood", "gate", "bridge",
+];
+
+/// Check whether `c` is a vowel letter.
+pub(crate) fn is_vowel(c: char) -> bool {
+    matches!(c.to_ascii_lowercase(), 'a' | 'e' | 'i' | 'o' | 'u' | 'y')
+}
+
+/// Attach `suffix` to `stem`, dropping a final stem vowel when the suffix starts with a vowel
+/// ("velda" + "ite" = "veldite") and merging a doubled consonant at the seam
+/// ("velmar" + "rite" = "velmarite").
+pub(crate) fn attach(stem: &str, suffix: &str) -> String {
+    let mut output = stem.to_owned();
+    let first = match suffix.chars().next() {
+        Some(first) => first,
+        None => return output,
+    };
+    if let Some(last) = output.chars().last() {
+        let elide = is_vowel(first) && is_vowel(last) && output.chars().count() > 2;
+        let doubled = !is_vowel(first) && last.eq_ignore_ascii_case(&first);
+        if elide || doubled {
+            output.pop();
+        }
+    }
+    output.push_str(suffix);
+    output
+}
+
+/// Latin-style genitive of a proper noun ("Velmara" becomes "Velmarae", "Orus" becomes
+/// "Ori").
+pub(crate) fn genitive(name: &str) -> String {
+    if name.ends_with('a') {
+        format!("{}e", name)
+    } else if name.ends_with("us") || name.ends_with("um") {
+        format!("{}i", &name[..name.len() - 2])
+    } else if name.ends_with("is") {
+        name.to_owned()
+    } else if name.ends_with(is_vowel) {
+        format!("{}nis", name)
+    } else {
+        format!("{}is", name)
+    }
+}
+
+/// English plural of a noun, adding "es" after sibilants ("velmash" becomes "velmashes").
+pub(crate) fn plural(name: &str) -> String {
+    if name.ends_with('s') || name.ends_with("sh") || name.ends_with('x') {
+        format!("{}es", name)
+    } else {
+        format!("{}s", name)
+    }
+}
+
+/// Name for the people of `place` ("Velmar" becomes "Velmari", "Ashport" becomes "Ashportian").
+///
+/// Compounds ending in an English element take "-er" or "-ian" as English place names do.
+/// Otherwise the suffix follows the stem's last letter: "-n" after a final "a", "-an" after
+/// another vowel, "-i" after a single soft consonant, and "-ian" after anything harsher.
+pub fn derive_demonym(place: &str) -> String {
+    if let Some(element) = element(place) {
+        let suffix = if AGENT_ELEMENTS.contains(&element) {
+            "er"
+        } else {
+            "ian"
+        };
+        return attach(place, suffix);
+    }
+    match place.chars().last() {
+        Some('a') | Some('A') => format!("{}n", place),
+        Some('y') | Some('Y') => attach(place, "ian"),
+        Some(last) if is_vowel(last) => attach(place, "an"),
+        Some(_) if soft_ending(place) => format!("{}i", place),
+        Some(_) => attach(place, "ian"),
+        None => String::new(),
+    }
+}
+
+/// Name for the language spoken by `culture` ("Velmar" becomes "Velmari", "Velda" becomes
+/// "Veldese", "Ashland" becomes "Ashlandish").
+pub fn derive_language_name(culture: &str) -> String {
+    if element(culture).is_some() {
+        return attach(culture, "ish");
+    }
+    match culture.chars().last() {
+        Some(_) if soft_ending(culture) => format!("{}i", culture),
+        Some(_) => attach(culture, "ese"),
+        None => String::new(),
+    }
+}
+
+/// Adjective form of `name` using the first of `suffixes` that joins it cleanly ("Velmar" with
+/// "ic" becomes "Velmaric", with "ine" becomes "Velmarine").
+///
+/// A suffix is passed over when it would repeat the stem's final consonant ("Velmin" + "ine")
+/// or put "-ic" after a hard c, k, g or q. The last suffix is used when none join cleanly.
+pub(crate) fn adjective<S: AsRef<str>>(name: &str, suffixes: &[S]) -> String {
+    let last = name.chars().last().map(|c| c.to_ascii_lowercase());
+    let clean = |suffix: &str| {
+        let consonant = suffix.chars().find(|&c| !is_vowel(c));
+        match last {
+            Some(last) if is_vowel(last) => true,
+            Some(last) if Some(last) == consonant => false,
+            Some(last) => !(suffix.starts_with("ic") && "ckgq".contains(last)),
+            None => true,
+        }
+    };
+    let suffix = suffixes
+        .iter()
+        .map(AsRef::as_ref)
+        .find(|suffix| clean(suffix))
+        .or_else(|| suffixes.last().map(AsRef::as_ref))
+        .unwrap_or("");
+    attach(name, suffix)
+}
+
+/// English place-name element that `name` is a compound of, if any.
+fn element(name: &str) -> Option<&'static str> {
+    let lower = name.to_lowercase();
+    AGENT_ELEMENTS
+        .iter()
+        .chain(RELATIONAL_ELEMENTS.iter())
+        .copied()
+        .find(|element| lower.len() > element.len() && lower.ends_with(element))
+}
+
+/// Whether `name` ends in a vowel followed by a single soft consonant, as in "Velmar".
+fn soft_ending(name: &str) -> bool {
+    let mut letters = name.chars().rev().map(|c| c.to_ascii_lowercase());
+    match (letters.next(), letters.next()) {
+        (Some(last), Some(previous)) => "lmnrs".contains(last) && is_vowel(previous),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{adjective, attach, derive_demonym, derive_language_name, genitive};
+
+    #[test]
+    fn test_attach() {
+        assert_eq!(attach("velda", "ite"), "veldite");
+        assert_eq!(attach("velmar", "rite"), "velmarite");
+        assert_eq!(attach("ash", "glass"), "ashglass");
+        assert_eq!(attach("io", "ite"), "ioite");
+        assert_eq!(attach("stem", ""), "stem");
+    }
+
+    #[test]
+    fn test_genitive() {
+        assert_eq!(genitive("Velmara"), "Velmarae");
+        assert_eq!(genitive("Orus"), "Ori");
+        assert_eq!(genitive("Draconis"), "Draconis");
+        assert_eq!(genitive("Velmar"), "Velmaris");
+    }
+
+    #[test]
+    fn test_demonym_and_language() {
+        assert_eq!(derive_demonym("Velmar"), "Velmari");
+        assert_eq!(derive_demonym("Ashport"), "Ashportian");
+        assert_eq!(derive_demonym("Ashland"), "Ashlander");
+        assert_eq!(derive_demonym("Velda"), "Veldan");
+        assert_eq!(derive_demonym("Orio"), "Orian");
+        assert_eq!(derive_demonym("Tessaly"), "Tessalian");
+        assert_eq!(derive_demonym("Karak"), "Karakian");
+        assert_eq!(derive_language_name("Velmar"), "Velmari");
+        assert_eq!(derive_language_name("Velda"), "Veldese");
+        assert_eq!(derive_language_name("Karak"), "Karakese");
+        assert_eq!(derive_language_name("Ashland"), "Ashlandish");
+    }
+
+    #[test]
+    fn test_adjective() {
+        assert_eq!(adjective("Velmar", &["ic", "ine"]), "Velmaric");
+        assert_eq!(adjective("Velmar", &["ine", "ic"]), "Velmarine");
+        assert_eq!(adjective("Velmin", &["ine", "ic"]), "Velminic");
+        assert_eq!(adjective("Karak", &["ic", "ian"]), "Karakian");
+        assert_eq!(adjective("Velda", &["ic"]), "Veldic");
+        assert_eq!(adjective("Karak", &["ic"]), "Karakic");
+    }
+}
@@ -0,0 +1,155 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Band and Album Name Generation
+
+use crate::grammar::{Grammar, Phrase};
+use crate::totro::TotroConfig;
+use rand::Rng;
+
+/// Generator for band names and album titles, such as "The Velvet Ravens" or "Ash and Silence".
+///
+/// The underlying [`Grammar`] starts from the `band` and `album` symbols. Band names mix
+/// definite-article plurals ("The Hollow Saints"), abstract noun pairs ("Smoke and Mirrors") and
+/// generated proper nouns ("Velmar & the Wolves"). Any rule (`adjective`, `plural`, `abstract`)
+/// can be extended with more alternatives.
+///
+/// ```rust
+/// use nominae::Discography;
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let music = Discography::default();
+/// println!("{} - {}", music.band(&mut rng), music.album(&mut rng));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Discography {
+    grammar: Grammar,
+}
+
+impl Default for Discography {
+    fn default() -> Discography {
+        let grammar = Grammar::default()
+            .rule(
+                "band",
+                &[
+                    "The {adjective} {plural}",
+                    "The {plural}",
+                    "{abstract} and {abstract}",
+                    "{abstract} & {abstract}",
+                    "{name}",
+                    "{name} {plural}",
+                    "{name} & the {plural}",
+                    "{adjective} {abstract}",
+                ],
+            )
+            .rule(
+                "album",
+                &[
+                    "{abstract} in {name}",
+                    "Songs for {name}",
+                    "The {abstract} Sessions",
+                    "{adjective} {abstract}",
+                    "Live at {name}",
+                    "{abstract} of the {plural}",
+                    "Return to {name}",
+                ],
+            )
+            .rule(
+                "adjective",
+                &[
+                    "Velvet", "Hollow", "Electric", "Broken", "Silver", "Crimson", "Howling",
+                    "Midnight", "Paper", "Burning", "Quiet",
+                ],
+            )
+            .rule(
+                "plural",
+                &[
+                    "Ravens", "Saints", "Wolves", "Lanterns", "Kings", "Ghosts", "Engines",
+                    "Sparrows", "Thieves", "Tides",
+                ],
+            )
+            .rule(
+                "abstract",
+                &[
+                    "Ash", "Silence", "Smoke", "Mirrors", "Glory", "Ruin", "Honey", "Thunder",
+                    "Dust", "Static", "Devotion", "Rust",
+                ],
+            );
+        Discography { grammar }
+    }
+}
+
+impl Discography {
+    /// Set the configuration used to generate proper nouns.
+    pub fn with_names(mut self, names: TotroConfig) -> Discography {
+        self.grammar = self.grammar.with_names(names);
+        self
+    }
+
+    /// Add alternatives to one of the music grammar's rules.
+    pub fn rule(mut self, symbol: &str, alternatives: &[&str]) -> Discography {
+        self.grammar = self.grammar.rule(symbol, alternatives);
+        self
+    }
+
+    /// Grammar used to generate band names and album titles.
+    pub fn grammar(&self) -> &Grammar {
+        &self.grammar
+    }
+
+    /// Generate a band name.
+    pub fn band<T: Rng + ?Sized>(&self, rng: &mut T) -> Phrase {
+        self.grammar.generate("band", rng)
+    }
+
+    /// Generate an album title.
+    pub fn album<T: Rng + ?Sized>(&self, rng: &mut T) -> Phrase {
+        self.grammar.generate("album", rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Discography;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_band_and_album() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let music = Discography::default();
+        for _ in 0..50 {
+            for phrase in [music.band(&mut rng), music.album(&mut rng)] {
+                assert!(!phrase.text.contains('{'), "{}", phrase);
+                assert!(phrase
+                    .text
+                    .split(' ')
+                    .all(|word| !word.starts_with(char::is_lowercase)
+                        || ["and", "in", "for", "at", "of", "the", "to"].contains(&word)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_rule() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let music = Discography::default().rule("band", &["The {plural}"]);
+        assert_eq!(music.grammar().alternatives("band").unwrap().len(), 9);
+        assert!(!music.band(&mut rng).text.is_empty());
+    }
+}
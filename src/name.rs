@@ -0,0 +1,258 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Language-Aware Names
+
+use crate::epithets::Epithet;
+use crate::language::{Collective, Language};
+use crate::profile::ProfileVersion;
+use crate::stable::StableRng;
+use crate::totro::TotroConfig;
+use rand::SeedableRng;
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+
+/// A generated name together with the [`Language`] it belongs to.
+///
+/// Prose templates should derive word forms through the name rather than hard-coding English
+/// rules onto culture-specific names.
+///
+/// ```rust
+/// use nominae::{Collective, Language, Name, Possessive};
+/// use std::sync::Arc;
+///
+/// let language = Arc::new(Language::default().with_possessive(Possessive::Preposition("of".into())));
+/// let name = Name::in_language("Velmar", &language);
+/// assert_eq!(format!("the sword {}", name.possessive()), "the sword of Velmar");
+/// assert_eq!(Name::new("Velmar").possessive(), "Velmar's");
+/// assert_eq!(Name::new("Velmar").collective(&Collective::Family), "the Velmars");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Name {
+    text: String,
+    language: Arc<Language>,
+    origin: Option<Origin>,
+    epithet: Option<Epithet>,
+}
+
+/// Everything needed to generate a name again, so it can be stored as a few numbers instead
+/// of as text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Origin {
+    /// Seed of the [`StableRng`] the name was drawn from.
+    pub seed: u64,
+    /// Profile version of the configuration that generated the name.
+    pub version: ProfileVersion,
+    /// [`TotroConfig::fingerprint`] of the configuration that generated the name.
+    pub fingerprint: u64,
+}
+
+impl TotroConfig {
+    /// Generate a name from its own `seed`, recording its [`Origin`] so it can be regenerated.
+    ///
+    /// ```rust
+    /// use nominae::{Name, TotroConfig};
+    ///
+    /// let name = TotroConfig::default().generate_name(7);
+    /// let origin = name.origin().unwrap();
+    /// assert_eq!(origin.seed, 7);
+    /// assert_eq!(name.regenerate(), Some(name));
+    /// ```
+    pub fn generate_name(&self, seed: u64) -> Name {
+        let mut name = Name::new(self.generate(&mut StableRng::seed_from_u64(seed)));
+        name.origin = Some(Origin {
+            seed,
+            version: self.version(),
+            fingerprint: self.fingerprint(),
+        });
+        name
+    }
+}
+
+impl Name {
+    /// Create a name in the default [`Language`].
+    pub fn new<S: Into<String>>(text: S) -> Name {
+        static DEFAULT: OnceLock<Arc<Language>> = OnceLock::new();
+        Name::in_language(text, DEFAULT.get_or_init(Default::default))
+    }
+
+    /// Create a name belonging to `language`.
+    pub fn in_language<S: Into<String>>(text: S, language: &Arc<Language>) -> Name {
+        Name {
+            text: text.into(),
+            language: Arc::clone(language),
+            origin: None,
+            epithet: None,
+        }
+    }
+
+    /// The name followed by `epithet` wherever it is displayed, as in "Velmar the Bold".
+    pub fn with_epithet(mut self, epithet: Epithet) -> Name {
+        self.epithet = Some(epithet);
+        self
+    }
+
+    /// Epithet the name is displayed with, if any.
+    pub fn epithet(&self) -> Option<&Epithet> {
+        self.epithet.as_ref()
+    }
+
+    /// Where the name came from, if it was generated by [`TotroConfig::generate_name`].
+    pub fn origin(&self) -> Option<Origin> {
+        self.origin
+    }
+
+    /// Generate the name again from its origin, when it came from the default configuration of
+    /// its profile version.
+    ///
+    /// Names from customized configurations need that configuration, through
+    /// [`regenerate_with`](Name::regenerate_with).
+    pub fn regenerate(&self) -> Option<Name> {
+        let origin = self.origin?;
+        self.regenerate_with(&TotroConfig::versioned(origin.version))
+    }
+
+    /// Generate the name again from its origin with `config`, or `None` when the name has no
+    /// origin or `config` is not the configuration that generated it.
+    ///
+    /// The epithet and language are not part of the origin, so they are carried over from this
+    /// name.
+    pub fn regenerate_with(&self, config: &TotroConfig) -> Option<Name> {
+        let origin = self.origin?;
+        if config.fingerprint() != origin.fingerprint {
+            return None;
+        }
+        let mut name = config.generate_name(origin.seed);
+        name.language = Arc::clone(&self.language);
+        name.epithet = self.epithet.clone();
+        Some(name)
+    }
+
+    /// The name as written, without any epithet.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Language the name belongs to.
+    pub fn language(&self) -> &Language {
+        &self.language
+    }
+
+    /// The name as rendered in `language`, such as a document written by a neighboring culture:
+    /// respelled for that language's sounds and derived with its morphology from then on.
+    ///
+    /// ```rust
+    /// use nominae::{Language, Name};
+    /// use std::sync::Arc;
+    ///
+    /// let northern = Arc::new(
+    ///     Language::default()
+    ///         .with_respelling("c", "k")
+    ///         .with_respelling("th", "t")
+    ///         .with_respelling("w", "v")
+    ///         .with_respelling("y", "i"),
+    /// );
+    /// let name = Name::new("Cathwyn").render_in(&northern);
+    /// assert_eq!(name.text(), "Katvin");
+    /// ```
+    pub fn render_in(&self, language: &Arc<Language>) -> Name {
+        Name {
+            text: language.respell(&self.text),
+            language: Arc::clone(language),
+            origin: None,
+            epithet: self.epithet.clone(),
+        }
+    }
+
+    /// Possessive form following the name's language, such as "Velmar's" or "of Velmar".
+    pub fn possessive(&self) -> String {
+        self.language.derive_possessive(&self.text)
+    }
+
+    /// Adjective form following the name's language, such as "Velmaric".
+    pub fn adjective(&self) -> String {
+        self.language.derive_adjective(&self.text)
+    }
+
+    /// The name with casing following its language, such as "Karl von Velmar".
+    pub fn capitalized(&self) -> String {
+        self.language.capitalize(&self.text)
+    }
+
+    /// Collective form following the name's language, such as "the Velmars" or "House Velmar's
+    /// banners".
+    pub fn collective(&self, style: &Collective) -> String {
+        self.language.derive_collective(&self.text, style)
+    }
+}
+
+impl AsRef<str> for Name {
+    fn as_ref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.epithet {
+            Some(epithet) => f.write_str(&epithet.attach(&self.text)),
+            None => f.write_str(&self.text),
+        }
+    }
+}
+
+impl From<Name> for String {
+    fn from(name: Name) -> String {
+        name.text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Name;
+    use crate::epithets::{Epithet, EpithetForm};
+    use crate::totro::TotroConfig;
+
+    #[test]
+    fn test_regenerate() {
+        let config = TotroConfig::alphabetic().with_length(3, 5);
+        let name = config.generate_name(11);
+        assert_eq!(name.regenerate_with(&config), Some(name.clone()));
+        assert_eq!(name.regenerate(), None);
+        assert_eq!(
+            name.regenerate_with(&config.clone().with_length(3, 6)),
+            None
+        );
+        assert_eq!(Name::new("Velmar").regenerate(), None);
+        let titled = name.with_epithet(Epithet {
+            form: EpithetForm::Title,
+            text: "the Bold".into(),
+        });
+        assert_eq!(titled.regenerate_with(&config), Some(titled.clone()));
+    }
+
+    #[test]
+    fn test_epithet() {
+        let epithet = Epithet {
+            form: EpithetForm::Title,
+            text: "Bane of Serpents".into(),
+        };
+        let name = Name::new("Velmar").with_epithet(epithet);
+        assert_eq!(name.to_string(), "Velmar, Bane of Serpents");
+        assert_eq!(name.text(), "Velmar");
+        assert_eq!(name.possessive(), "Velmar's");
+    }
+}
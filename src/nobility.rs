@@ -0,0 +1,260 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Noble Title Chain Generation
+
+use crate::template::{expand, placeholders};
+use crate::totro::TotroConfig;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::BTreeMap;
+use std::fmt;
+
+const RANKS: [&str; 7] = [
+    "Duke of {realm}",
+    "Duchess of {realm}",
+    "Count of {realm}",
+    "Baron of {realm}",
+    "Marquess of {realm}",
+    "Earl of {realm}",
+    "Prince of {realm}",
+];
+
+const OFFICES: [&str; 8] = [
+    "Warden of the {direction}march",
+    "Protector of {realm}",
+    "Lord of {holding}",
+    "Keeper of the {holding} Gate",
+    "Shield of {realm}",
+    "Defender of the {direction}ern Reach",
+    "High Steward of {holding}",
+    "Master of the {direction} Fleet",
+];
+
+const DIRECTIONS: [&str; 4] = ["North", "East", "South", "West"];
+
+const ORDINALS: [&str; 8] = [
+    "Second", "Third", "Fourth", "Fifth", "Sixth", "Seventh", "Eighth", "Ninth",
+];
+
+/// A single title in a [`TitleChain`], such as "Duke of Velmar".
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Title {
+    /// The template the title was expanded from, such as "Duke of {realm}".
+    pub template: String,
+    /// The expanded title text.
+    pub text: String,
+    /// Placeholder values used by this title, in order of appearance.
+    pub bindings: Vec<(String, String)>,
+}
+
+/// A stacked list of aristocratic titles, such as
+/// "Duke of Velmar, Warden of the Eastmarch, Third of His Name".
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TitleChain {
+    /// Titles from most to least important.
+    pub titles: Vec<Title>,
+    /// Regnal ordinal, such as "Third of His Name".
+    pub ordinal: Option<String>,
+}
+
+impl fmt::Display for TitleChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        let parts = self
+            .titles
+            .iter()
+            .map(|title| title.text.as_str())
+            .chain(self.ordinal.as_deref());
+        for part in parts {
+            if !first {
+                f.write_str(", ")?;
+            }
+            f.write_str(part)?;
+            first = false;
+        }
+        Ok(())
+    }
+}
+
+/// Generator for [`TitleChain`]s.
+///
+/// Titles are templates with `{placeholder}` slots. Within one chain every placeholder is bound
+/// once, so "Duke of {realm}" and "Protector of {realm}" name the same realm. Placeholders are
+/// resolved from explicit bindings first, then `{direction}` picks a compass direction, and
+/// anything else is bound to a generated place name.
+///
+/// ```rust
+/// use nominae::TitleGenerator;
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let titles = TitleGenerator::default().bind("realm", "Velmar");
+/// println!("{}", titles.generate(&mut rng));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TitleGenerator {
+    ranks: Vec<String>,
+    offices: Vec<String>,
+    places: TotroConfig,
+    bindings: BTreeMap<String, String>,
+    max_offices: usize,
+    ordinal: f64,
+    ordinal_suffix: String,
+}
+
+impl Default for TitleGenerator {
+    fn default() -> TitleGenerator {
+        TitleGenerator {
+            ranks: RANKS.iter().map(|&rank| rank.to_owned()).collect(),
+            offices: OFFICES.iter().map(|&office| office.to_owned()).collect(),
+            places: TotroConfig::alphabetic().with_length(2, 4),
+            bindings: BTreeMap::new(),
+            max_offices: 2,
+            ordinal: 0.3,
+            ordinal_suffix: "of His Name".to_owned(),
+        }
+    }
+}
+
+impl TitleGenerator {
+    /// Replace the rank templates, one of which leads every chain.
+    pub fn with_ranks<S: Into<String>>(mut self, ranks: Vec<S>) -> TitleGenerator {
+        assert!(!ranks.is_empty(), "at least one rank is required");
+        self.ranks = ranks.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Replace the office templates that follow the rank.
+    pub fn with_offices<S: Into<String>>(mut self, offices: Vec<S>) -> TitleGenerator {
+        self.offices = offices.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the most offices that may follow the rank.
+    pub fn with_max_offices(mut self, max_offices: usize) -> TitleGenerator {
+        self.max_offices = max_offices;
+        self
+    }
+
+    /// Set the configuration used to generate place names for unbound placeholders.
+    pub fn with_places(mut self, places: TotroConfig) -> TitleGenerator {
+        self.places = places;
+        self
+    }
+
+    /// Set the probability that a chain ends with a regnal ordinal, and the text following it.
+    pub fn with_ordinal(mut self, probability: f64, suffix: &str) -> TitleGenerator {
+        assert!(
+            (0.0..=1.0).contains(&probability),
+            "ordinal probability must be within 0..=1"
+        );
+        self.ordinal = probability;
+        self.ordinal_suffix = suffix.to_owned();
+        self
+    }
+
+    /// Bind `placeholder` to a fixed value instead of generating one.
+    pub fn bind(mut self, placeholder: &str, value: &str) -> TitleGenerator {
+        self.bindings
+            .insert(placeholder.to_owned(), value.to_owned());
+        self
+    }
+
+    /// Generate a title chain.
+    pub fn generate<T: Rng + ?Sized>(&self, rng: &mut T) -> TitleChain {
+        let mut templates = vec![self.ranks.choose(rng).unwrap().as_str()];
+        let count = rng.gen_range(0..=self.max_offices.min(self.offices.len()));
+        templates.extend(self.offices.choose_multiple(rng, count).map(String::as_str));
+
+        let mut bound = self.bindings.clone();
+        let titles = templates
+            .into_iter()
+            .map(|template| {
+                for name in placeholders(template) {
+                    if !bound.contains_key(name) {
+                        let value = self.resolve(name, rng);
+                        bound.insert(name.to_owned(), value);
+                    }
+                }
+                Title {
+                    template: template.to_owned(),
+                    text: expand(template, |name| bound[name].clone()),
+                    bindings: placeholders(template)
+                        .into_iter()
+                        .map(|name| (name.to_owned(), bound[name].clone()))
+                        .collect(),
+                }
+            })
+            .collect();
+        let ordinal = if rng.gen_bool(self.ordinal) {
+            let ordinal = ORDINALS.choose(rng).unwrap();
+            Some(format!("{} {}", ordinal, self.ordinal_suffix))
+        } else {
+            None
+        };
+        TitleChain { titles, ordinal }
+    }
+
+    fn resolve<T: Rng + ?Sized>(&self, placeholder: &str, rng: &mut T) -> String {
+        match placeholder {
+            "direction" => DIRECTIONS.choose(rng).unwrap().to_string(),
+            _ => self.places.generate(rng),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TitleChain, TitleGenerator};
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_bindings() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let generator = TitleGenerator::default()
+            .with_ranks(vec!["Duke of {realm}"])
+            .with_offices(vec![
+                "Protector of {realm}",
+                "Warden of the {direction}march",
+            ])
+            .with_max_offices(2)
+            .with_ordinal(0.0, "of Her Name")
+            .bind("realm", "Velmar");
+        for _ in 0..20 {
+            let chain = generator.generate(&mut rng);
+            assert_eq!(chain.titles[0].text, "Duke of Velmar");
+            assert_eq!(
+                chain.titles[0].bindings,
+                vec![("realm".to_owned(), "Velmar".to_owned())]
+            );
+            assert!(chain.ordinal.is_none());
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let chain: TitleChain = TitleGenerator::default()
+            .with_ordinal(1.0, "of His Name")
+            .generate(&mut rng);
+        let text = chain.to_string();
+        assert!(text.ends_with("of His Name"), "{}", text);
+        assert_eq!(text.matches(", ").count(), chain.titles.len());
+    }
+}
@@ -0,0 +1,111 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Name Normalization
+
+/// Canonical comparison form of `name`, so that spellings a reader would take for the same name
+/// compare equal.
+///
+/// The name is case folded, accented Latin letters are folded to their base letters ("é" to
+/// "e", "ß" to "ss", "æ" to "ae"), and apostrophes, hyphens, spaces and other separators are
+/// dropped so particles join the name they belong to ("al-Rashid", "Al Rashid" and "Alrashid"
+/// all normalize to "alrashid"). Normalizing is idempotent: a normalized name normalizes to
+/// itself.
+///
+/// ```rust
+/// use nominae::{eq_normalized, normalize};
+///
+/// assert_eq!(normalize("D'Vára"), "dvara");
+/// assert!(eq_normalized("Jean-Luc", "jean luc"));
+/// assert!(!eq_normalized("Velmar", "Velmor"));
+/// ```
+pub fn normalize(name: &str) -> String {
+    let mut output = String::with_capacity(name.len());
+    for c in name.chars().flat_map(char::to_lowercase) {
+        match fold(c) {
+            Some(folded) => output.push_str(folded),
+            None if c.is_alphanumeric() => output.push(c),
+            None => {}
+        }
+    }
+    output
+}
+
+/// Whether `a` and `b` have the same [`normalize`]d form.
+pub fn eq_normalized(a: &str, b: &str) -> bool {
+    normalize(a) == normalize(b)
+}
+
+/// Lowercase `text` and fold accented Latin letters, keeping every other character.
+pub(crate) fn fold_letters(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    for c in text.chars().flat_map(char::to_lowercase) {
+        match fold(c) {
+            Some(folded) => output.push_str(folded),
+            None => output.push(c),
+        }
+    }
+    output
+}
+
+/// Base letters of a lowercase accented Latin letter, if `c` is one.
+fn fold(c: char) -> Option<&'static str> {
+    Some(match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ĵ' => "j",
+        'ķ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'œ' => "oe",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' => "s",
+        'ß' => "ss",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'þ' => "th",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{eq_normalized, fold_letters, normalize};
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("Ærøskøbing"), "aeroskobing");
+        assert_eq!(normalize("al-Rashid"), normalize("Al Rashid"));
+        assert_eq!(normalize("O’Malley"), "omalley");
+        assert_eq!(normalize("STRAẞE"), "strasse");
+        assert_eq!(normalize("Ωmega"), "ωmega");
+        for name in ["D'Vára", "Jean-Luc Picard", "Ærøskøbing"].iter() {
+            assert_eq!(normalize(&normalize(name)), normalize(name));
+        }
+        assert!(eq_normalized("Velmar", "VELMAR"));
+        assert_eq!(fold_letters("D'Vára"), "d'vara");
+    }
+}
@@ -0,0 +1,338 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Language Packs
+//!
+//! A language pack is a plain text file describing a naming language: display metadata for
+//! menus, followed by its vowel and consonant tables.
+//!
+//! ```text
+//! # Lines starting with '#' are comments.
+//! [pack]
+//! id = velmari
+//! name = Velmari
+//! description = Soft names of the river cities
+//! author = Hans W. Uhlig
+//! locale = en
+//!
+//! [vowels]
+//! a 12
+//! ae 2 me
+//!
+//! [consonants]
+//! v 4
+//! l 6
+//! r 5 me
+//! ```
+//!
+//! Each table line is a token, an optional weight (1 by default) and an optional set of the
+//! positions `b`, `m` and `e` (beginning, middle and end) it may appear at, all by default.
+//! Only `id` is required in the `[pack]` section; `name` defaults to the id and `locale`, the
+//! language the display strings are written in, defaults to `en`.
+
+use crate::error::NominaeError;
+use crate::table::{Table, Token, BOW, EOW, MOW};
+use crate::totro::TotroConfig;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// File extension of language packs found by [`PackRegistry::load_dir`].
+pub const PACK_EXTENSION: &str = "pack";
+
+/// Human-readable description of a [`Pack`], for populating menus.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PackMetadata {
+    /// Stable identifier, such as "velmari".
+    pub id: String,
+    /// Display name, such as "Velmari".
+    pub name: String,
+    /// One-line description of the names the pack produces.
+    pub description: String,
+    /// Who wrote the pack.
+    pub author: String,
+    /// Locale the display strings are written in, such as "en" or "de-AT".
+    pub locale: String,
+}
+
+/// A naming language loaded from the pack format described in this module.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pack {
+    metadata: PackMetadata,
+    config: TotroConfig,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Section {
+    None,
+    Pack,
+    Vowels,
+    Consonants,
+}
+
+impl Pack {
+    /// Parse a pack from its text.
+    pub fn parse(text: &str) -> Result<Pack, NominaeError> {
+        let mut section = Section::None;
+        let mut fields = BTreeMap::new();
+        let mut vowels = Vec::new();
+        let mut consonants = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            let error = |message: String| NominaeError::Pack {
+                line: Some(index + 1),
+                message,
+            };
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('[') && line.ends_with(']') {
+                section = match &line[1..line.len() - 1] {
+                    "pack" => Section::Pack,
+                    "vowels" => Section::Vowels,
+                    "consonants" => Section::Consonants,
+                    other => return Err(error(format!("unknown section {:?}", other))),
+                };
+                continue;
+            }
+            match section {
+                Section::None => return Err(error("expected a section header".to_owned())),
+                Section::Pack => {
+                    let (key, value) = match line.find('=') {
+                        Some(equals) => (line[..equals].trim(), line[equals + 1..].trim()),
+                        None => return Err(error(format!("expected key = value, got {:?}", line))),
+                    };
+                    if !["id", "name", "description", "author", "locale"].contains(&key) {
+                        return Err(error(format!("unknown key {:?}", key)));
+                    }
+                    fields.insert(key, value.to_owned());
+                }
+                Section::Vowels => vowels.push(parse_token(line).map_err(error)?),
+                Section::Consonants => consonants.push(parse_token(line).map_err(error)?),
+            }
+        }
+        let mut field = |key: &str| fields.remove(key).filter(|value| !value.is_empty());
+        let id = field("id").ok_or_else(|| NominaeError::Pack {
+            line: None,
+            message: "missing id".to_owned(),
+        })?;
+        let metadata = PackMetadata {
+            name: field("name").unwrap_or_else(|| id.clone()),
+            description: field("description").unwrap_or_default(),
+            author: field("author").unwrap_or_default(),
+            locale: field("locale").unwrap_or_else(|| "en".to_owned()),
+            id,
+        };
+        let table = |tokens, kind: &str| {
+            Table::new(tokens).map_err(|error| NominaeError::Pack {
+                line: None,
+                message: format!("{}: {}", kind, error),
+            })
+        };
+        let config = TotroConfig::new(table(vowels, "vowels")?, table(consonants, "consonants")?);
+        Ok(Pack { metadata, config })
+    }
+
+    /// Load a pack from a file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Pack, NominaeError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|error| NominaeError::Pack {
+            line: None,
+            message: format!("{}: {}", path.display(), error),
+        })?;
+        Pack::parse(&text)
+    }
+
+    /// Display metadata of the pack.
+    pub fn metadata(&self) -> &PackMetadata {
+        &self.metadata
+    }
+
+    /// Configuration generating names in the pack's language.
+    pub fn config(&self) -> &TotroConfig {
+        &self.config
+    }
+}
+
+impl FromStr for Pack {
+    type Err = NominaeError;
+
+    fn from_str(text: &str) -> Result<Pack, NominaeError> {
+        Pack::parse(text)
+    }
+}
+
+/// Parse a table line of the form `text [weight] [positions]`.
+fn parse_token(line: &str) -> Result<Token, String> {
+    let mut words = line.split_whitespace();
+    let text = words.next().unwrap_or_default();
+    let mut weight = 1;
+    let mut flags = BOW | MOW | EOW;
+    for word in words {
+        if let Ok(parsed) = word.parse() {
+            weight = parsed;
+        } else if word.chars().all(|c| "bme".contains(c)) {
+            flags = word.chars().fold(0, |flags, c| {
+                flags
+                    | match c {
+                        'b' => BOW,
+                        'm' => MOW,
+                        _ => EOW,
+                    }
+            });
+        } else {
+            return Err(format!(
+                "invalid weight or positions {:?} for {:?}",
+                word, text
+            ));
+        }
+    }
+    Ok(Token::owned(text, flags, weight))
+}
+
+/// The language packs installed for a game, keyed by id.
+///
+/// ```rust
+/// use nominae::{Pack, PackRegistry};
+///
+/// let mut registry = PackRegistry::default();
+/// registry.install(
+///     "[pack]\nid = stone\nname = Stonetongue\n[vowels]\na\no\n[consonants]\nk\nr\n"
+///         .parse::<Pack>()
+///         .unwrap(),
+/// );
+/// for metadata in registry.metadata() {
+///     println!("{} ({})", metadata.name, metadata.locale);
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PackRegistry {
+    packs: BTreeMap<String, Pack>,
+}
+
+impl PackRegistry {
+    /// Load every `.pack` file in `dir`, failing on the first pack that does not parse.
+    pub fn load_dir<P: AsRef<Path>>(dir: P) -> Result<PackRegistry, NominaeError> {
+        let dir = dir.as_ref();
+        let io_error = |error: std::io::Error| NominaeError::Pack {
+            line: None,
+            message: format!("{}: {}", dir.display(), error),
+        };
+        let mut registry = PackRegistry::default();
+        for entry in fs::read_dir(dir).map_err(io_error)? {
+            let path = entry.map_err(io_error)?.path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == PACK_EXTENSION)
+            {
+                registry.install(Pack::load(&path)?);
+            }
+        }
+        Ok(registry)
+    }
+
+    /// Install `pack`, returning any previously installed pack with the same id.
+    pub fn install(&mut self, pack: Pack) -> Option<Pack> {
+        self.packs.insert(pack.metadata.id.clone(), pack)
+    }
+
+    /// Installed pack with the given id.
+    pub fn get(&self, id: &str) -> Option<&Pack> {
+        self.packs.get(id)
+    }
+
+    /// Metadata of every installed pack, ordered by id.
+    pub fn metadata(&self) -> impl Iterator<Item = &PackMetadata> {
+        self.packs.values().map(Pack::metadata)
+    }
+
+    /// Number of installed packs.
+    pub fn len(&self) -> usize {
+        self.packs.len()
+    }
+
+    /// Whether no packs are installed.
+    pub fn is_empty(&self) -> bool {
+        self.packs.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Pack, PackRegistry};
+    use crate::error::NominaeError;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+    use std::fs;
+
+    const STONE: &str = "\
+# Dwarvish names
+[pack]
+id = stone
+name = Steinzunge
+description = Namen aus den Tiefen
+author = Hans W. Uhlig
+locale = de
+
+[vowels]
+a 3
+o
+
+[consonants]
+k 2
+r 1 me
+d bm
+";
+
+    #[test]
+    fn test_parse() {
+        let pack: Pack = STONE.parse().unwrap();
+        let metadata = pack.metadata();
+        assert_eq!(metadata.id, "stone");
+        assert_eq!(metadata.name, "Steinzunge");
+        assert_eq!(metadata.locale, "de");
+        let mut rng = SmallRng::seed_from_u64(0);
+        let name = pack.config().generate(&mut rng);
+        assert!(name.chars().all(|c| "AaOoKkRrDd".contains(c)), "{}", name);
+        assert_eq!(
+            Pack::parse("[pack]\nid = x\n[runes]\n"),
+            Err(NominaeError::Pack {
+                line: Some(3),
+                message: "unknown section \"runes\"".to_owned()
+            })
+        );
+        assert_eq!(
+            Pack::parse("[pack]\nname = x\n").unwrap_err().to_string(),
+            "invalid pack: missing id"
+        );
+    }
+
+    #[test]
+    fn test_load_dir() {
+        let dir = std::env::temp_dir().join(format!("nominae-packs-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("stone.pack"), STONE).unwrap();
+        fs::write(dir.join("readme.txt"), "not a pack").unwrap();
+        let registry = PackRegistry::load_dir(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        let registry = registry.unwrap();
+        assert_eq!(registry.len(), 1);
+        let names: Vec<_> = registry.metadata().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["Steinzunge"]);
+        assert!(registry.get("stone").is_some());
+    }
+}
@@ -0,0 +1,190 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Identity Passphrases
+
+use crate::stable::{seed_from_str, StableRng};
+use crate::totro::TotroConfig;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use rand::{Rng, SeedableRng};
+
+/// A generated passphrase: its words followed by a checksum word.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Passphrase {
+    words: Vec<String>,
+    checksum: String,
+    separator: char,
+}
+
+impl Passphrase {
+    /// The generated words, without the checksum word.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// The word derived from the others, which catches a mistyped or missing word.
+    pub fn checksum(&self) -> &str {
+        &self.checksum
+    }
+}
+
+/// Displays every word, the checksum last, joined by the separator.
+impl fmt::Display for Passphrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for word in &self.words {
+            write!(f, "{}{}", word, self.separator)?;
+        }
+        f.write_str(&self.checksum)
+    }
+}
+
+/// Generator of pronounceable recovery or identity phrases such as "velar-onik-surra-miel",
+/// an alternative to fixed word lists like BIP39.
+///
+/// Each phrase is a fixed number of generated words followed by a checksum word, generated
+/// with [`StableRng`] seeded from the words before it. [`verify`](PassphraseGenerator::verify)
+/// recomputes the checksum, so a phrase copied down wrong is caught before it is used. Phrases
+/// only verify with the configuration they were generated with.
+///
+/// ```rust
+/// use nominae::PassphraseGenerator;
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let phrases = PassphraseGenerator::default();
+/// let phrase = phrases.generate(&mut rng).to_string();
+/// assert!(phrases.verify(&phrase));
+/// assert!(!phrases.verify(&phrase.replacen('-', "x-", 1)));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PassphraseGenerator {
+    config: TotroConfig,
+    words: usize,
+    separator: char,
+}
+
+impl Default for PassphraseGenerator {
+    fn default() -> PassphraseGenerator {
+        PassphraseGenerator {
+            config: TotroConfig::alphabetic().with_length(2, 5),
+            words: 3,
+            separator: '-',
+        }
+    }
+}
+
+impl PassphraseGenerator {
+    /// Set the configuration words are generated with.
+    ///
+    /// Its tokens must not contain the separator, or phrases could not be split back into
+    /// words.
+    pub fn with_config(mut self, config: TotroConfig) -> PassphraseGenerator {
+        self.config = config;
+        self
+    }
+
+    /// Set the number of words before the checksum word.
+    pub fn with_words(mut self, words: usize) -> PassphraseGenerator {
+        assert!(words > 0, "passphrases need at least one word");
+        self.words = words;
+        self
+    }
+
+    /// Set the character words are joined with.
+    pub fn with_separator(mut self, separator: char) -> PassphraseGenerator {
+        self.separator = separator;
+        self
+    }
+
+    /// Number of words before the checksum word.
+    pub fn words(&self) -> usize {
+        self.words
+    }
+
+    /// Character words are joined with.
+    pub fn separator(&self) -> char {
+        self.separator
+    }
+
+    /// Generate a passphrase.
+    pub fn generate<T: Rng + ?Sized>(&self, rng: &mut T) -> Passphrase {
+        let words: Vec<String> = (0..self.words)
+            .map(|_| self.config.generate(rng).to_lowercase())
+            .collect();
+        Passphrase {
+            checksum: self.checksum(&words),
+            words,
+            separator: self.separator,
+        }
+    }
+
+    /// Check that `phrase` has the right number of words and ends in their checksum word,
+    /// ignoring case and surrounding whitespace.
+    pub fn verify(&self, phrase: &str) -> bool {
+        let words: Vec<String> = phrase
+            .trim()
+            .split(self.separator)
+            .map(str::to_lowercase)
+            .collect();
+        match words.split_last() {
+            Some((checksum, words)) if words.len() == self.words => {
+                *checksum == self.checksum(words)
+            }
+            _ => false,
+        }
+    }
+
+    /// Checksum word of `words`.
+    fn checksum(&self, words: &[String]) -> String {
+        let mut rng = StableRng::seed_from_u64(seed_from_str(&words.join(" ")));
+        self.config.generate(&mut rng).to_lowercase()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PassphraseGenerator;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_verify() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let phrases = PassphraseGenerator::default().with_words(4);
+        let phrase = phrases.generate(&mut rng);
+        assert_eq!(phrase.words().len(), 4);
+        let text = phrase.to_string();
+        assert_eq!(text.split('-').count(), 5);
+        assert!(phrases.verify(&text));
+        assert!(phrases.verify(&format!(" {} ", text.to_uppercase())));
+        let (words, checksum) = text.rsplit_once('-').unwrap();
+        assert!(!phrases.verify(words));
+        assert!(!phrases.verify(&format!("{}-{}x", words, checksum)));
+        let swapped = phrase.words()[1..]
+            .iter()
+            .chain(&phrase.words()[..1])
+            .map(|word| word.as_str())
+            .collect::<Vec<_>>()
+            .join("-");
+        assert!(!phrases.verify(&format!("{}-{}", swapped, checksum)));
+        assert!(!PassphraseGenerator::default().verify(&text));
+    }
+}
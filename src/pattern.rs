@@ -0,0 +1,212 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Name Shape Patterns
+//!
+//! A pattern spells out the shape of a name one token at a time: `C` is a consonant token, `V`
+//! is a vowel token, and any other character is copied through as written. `"CVC'CVC"` makes
+//! names such as "Kor'dan", and `"CV-CVV-C"` names such as "Ta-rei-n". Apostrophes, hyphens and
+//! spaces split a pattern into parts, and tokens are chosen as if each part were a word of its
+//! own, so each part opens on a token that may begin a word and closes on one that may end it.
+//! Other uppercase letters are reserved for future token classes and are rejected.
+
+use crate::error::NominaeError;
+use crate::table::{AIW, BMW, MEW, MOW};
+use crate::totro::TotroConfig;
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::str::FromStr;
+use rand::Rng;
+
+/// One element of a compiled [`Pattern`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Item {
+    /// A token drawn from the vowel or consonant table at the given position flags.
+    Slot { vowel: bool, position: u8 },
+    /// A character copied through unchanged.
+    Literal(char),
+}
+
+/// A name shape compiled against the tables of a [`TotroConfig`].
+///
+/// Only the tables of the configuration are used; its length and other options do not apply,
+/// since the pattern fixes the shape of every name.
+///
+/// ```rust
+/// use nominae::{Pattern, TotroConfig};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let pattern = Pattern::compile("CVC'CVC", TotroConfig::alphabetic()).unwrap();
+/// let name = pattern.generate(&mut rng);
+/// assert_eq!(name.matches('\'').count(), 1);
+/// assert!("CVX".parse::<Pattern>().is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pattern {
+    source: String,
+    items: Vec<Item>,
+    config: TotroConfig,
+}
+
+impl Pattern {
+    /// Compile `pattern` to draw its tokens from the tables of `config`.
+    pub fn compile(pattern: &str, config: TotroConfig) -> Result<Pattern, NominaeError> {
+        let mut items = Vec::with_capacity(pattern.len());
+        // Whether the current part has a slot yet.
+        let mut open = false;
+        for (index, c) in pattern.chars().enumerate() {
+            match c {
+                'C' | 'V' => {
+                    let position = if open { MOW } else { BMW };
+                    open = true;
+                    items.push(Item::Slot {
+                        vowel: c == 'V',
+                        position,
+                    });
+                }
+                _ if c.is_uppercase() => {
+                    return Err(NominaeError::InvalidPattern(format!(
+                        "unknown token class {:?} at character {}",
+                        c,
+                        index + 1
+                    )))
+                }
+                _ => {
+                    if !c.is_alphanumeric() && open {
+                        close(&mut items);
+                        open = false;
+                    }
+                    items.push(Item::Literal(c));
+                }
+            }
+        }
+        if open {
+            close(&mut items);
+        }
+        if !items.iter().any(|item| matches!(item, Item::Slot { .. })) {
+            return Err(NominaeError::InvalidPattern(format!(
+                "{:?} has no C or V tokens",
+                pattern
+            )));
+        }
+        Ok(Pattern {
+            source: pattern.to_owned(),
+            items,
+            config,
+        })
+    }
+
+    /// The pattern as written.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Configuration whose tables tokens are drawn from.
+    pub fn config(&self) -> &TotroConfig {
+        &self.config
+    }
+
+    /// Generate a name of this shape, with its first letter capitalized.
+    pub fn generate<T: Rng + ?Sized>(&self, rng: &mut T) -> String {
+        let (vowel_pools, consonant_pools) = self.config.pools();
+        let mut name = String::new();
+        for item in &self.items {
+            match *item {
+                Item::Slot { vowel, position } => {
+                    let (table, pools) = if vowel {
+                        (self.config.vowels(), vowel_pools)
+                    } else {
+                        (self.config.consonants(), consonant_pools)
+                    };
+                    let pool = pools
+                        .get(position)
+                        .expect("validated tables cover every position");
+                    name.push_str(table.tokens()[pool.sample(rng)].text());
+                }
+                Item::Literal(c) => name.push(c),
+            }
+        }
+        if let Some(first) = name.get_mut(0..1) {
+            first.make_ascii_uppercase();
+        }
+        name
+    }
+}
+
+/// Mark the last slot of the current part as ending a word.
+fn close(items: &mut [Item]) {
+    if let Some(Item::Slot { position, .. }) = items
+        .iter_mut()
+        .rev()
+        .find(|item| matches!(item, Item::Slot { .. }))
+    {
+        *position = if *position == BMW { AIW } else { MEW };
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = NominaeError;
+
+    /// Compile a pattern against the default tables.
+    fn from_str(pattern: &str) -> Result<Pattern, NominaeError> {
+        Pattern::compile(pattern, TotroConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Item, Pattern};
+    use crate::error::NominaeError;
+    use crate::table::{Table, Token, AIW, BMW, MEW, MOW};
+    use crate::totro::TotroConfig;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_compile() {
+        let pattern: Pattern = "CVC-V".parse().unwrap();
+        let positions: Vec<_> = pattern
+            .items
+            .iter()
+            .map(|item| match item {
+                Item::Slot { position, .. } => *position,
+                Item::Literal(_) => 0,
+            })
+            .collect();
+        assert_eq!(positions, vec![BMW, MOW, MEW, 0, AIW]);
+        assert_eq!(
+            "CVQ".parse::<Pattern>(),
+            Err(NominaeError::InvalidPattern(
+                "unknown token class 'Q' at character 3".into()
+            ))
+        );
+        assert!("--".parse::<Pattern>().is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let vowels = Table::new(vec![Token::new("a", AIW)]).unwrap();
+        let consonants = Table::new(vec![Token::new("k", AIW)]).unwrap();
+        let pattern = Pattern::compile("CVC'CVC", TotroConfig::new(vowels, consonants)).unwrap();
+        assert_eq!(pattern.generate(&mut rng), "Kak'kak");
+        assert_eq!(pattern.source(), "CVC'CVC");
+    }
+}
@@ -0,0 +1,209 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Test Data Persona Generation
+
+use crate::email::local_part;
+use crate::stable::{below, mix, StableRng};
+use crate::totro::TotroConfig;
+use rand::SeedableRng;
+use std::io::{self, Write};
+
+/// Domain used for email-style handles, reserved for documentation and testing by RFC 2606.
+const DOMAIN: &str = "example.com";
+
+/// Column names written by [`PersonaGenerator::write_csv`].
+pub const CSV_HEADER: [&str; 6] = [
+    "given",
+    "surname",
+    "full_name",
+    "username",
+    "handle",
+    "city",
+];
+
+/// A fake person for test datasets.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Persona {
+    /// Given name, such as "Essa".
+    pub given: String,
+    /// Surname, such as "Drake".
+    pub surname: String,
+    /// Login-style username, such as "edrake42".
+    pub username: String,
+    /// Email-style handle, such as "essa.drake@example.com".
+    pub handle: String,
+    /// Home city.
+    pub city: String,
+}
+
+impl Persona {
+    /// Given name and surname, such as "Essa Drake".
+    pub fn full_name(&self) -> String {
+        format!("{} {}", self.given, self.surname)
+    }
+}
+
+/// Generator for rows of [`Persona`] test data.
+///
+/// Every row is generated from its own seed derived from the generator seed and the row index,
+/// so row 1000 can be regenerated without generating the rows before it, and adding rows to a
+/// dataset never changes the existing ones.
+///
+/// ```rust
+/// use nominae::PersonaGenerator;
+///
+/// let personas = PersonaGenerator::default().with_seed(7);
+/// assert_eq!(personas.row(1000), personas.rows(1001).pop().unwrap());
+///
+/// let mut csv = Vec::new();
+/// personas.write_csv(3, &mut csv).unwrap();
+/// assert_eq!(String::from_utf8(csv).unwrap().lines().count(), 4);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PersonaGenerator {
+    given: TotroConfig,
+    surname: TotroConfig,
+    city: TotroConfig,
+    seed: u64,
+}
+
+impl Default for PersonaGenerator {
+    fn default() -> PersonaGenerator {
+        PersonaGenerator {
+            given: TotroConfig::alphabetic().with_length(2, 4),
+            surname: TotroConfig::alphabetic().with_length(2, 5),
+            city: TotroConfig::alphabetic().with_length(2, 4),
+            seed: 0,
+        }
+    }
+}
+
+impl PersonaGenerator {
+    /// Set the configuration used for given names.
+    pub fn with_given(mut self, config: TotroConfig) -> PersonaGenerator {
+        self.given = config;
+        self
+    }
+
+    /// Set the configuration used for surnames.
+    pub fn with_surname(mut self, config: TotroConfig) -> PersonaGenerator {
+        self.surname = config;
+        self
+    }
+
+    /// Set the configuration used for city names.
+    pub fn with_city(mut self, config: TotroConfig) -> PersonaGenerator {
+        self.city = config;
+        self
+    }
+
+    /// Set the dataset seed that row seeds are derived from.
+    pub fn with_seed(mut self, seed: u64) -> PersonaGenerator {
+        self.seed = seed;
+        self
+    }
+
+    /// Generate the persona at `index`.
+    pub fn row(&self, index: u64) -> Persona {
+        let mut rng = StableRng::seed_from_u64(row_seed(self.seed, index));
+        let given = self.given.generate(&mut rng);
+        let surname = self.surname.generate(&mut rng);
+        let city = self.city.generate(&mut rng);
+        let initial: String = given.chars().take(1).collect();
+        let username = format!(
+            "{}{}{:02}",
+            initial.to_lowercase(),
+            surname.to_lowercase(),
+            below(&mut rng, 100)
+        );
+        let handle = format!("{}@{}", local_part(&given, &surname), DOMAIN);
+        Persona {
+            given,
+            surname,
+            username,
+            handle,
+            city,
+        }
+    }
+
+    /// Generate the first `n` personas.
+    pub fn rows(&self, n: usize) -> Vec<Persona> {
+        (0..n as u64).map(|index| self.row(index)).collect()
+    }
+
+    /// Write the first `n` personas to `writer` as CSV with a [`CSV_HEADER`] row.
+    pub fn write_csv<W: Write>(&self, n: usize, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "{}", CSV_HEADER.join(","))?;
+        for index in 0..n as u64 {
+            let persona = self.row(index);
+            let fields = [
+                persona.given.as_str(),
+                persona.surname.as_str(),
+                &persona.full_name(),
+                persona.username.as_str(),
+                persona.handle.as_str(),
+                persona.city.as_str(),
+            ];
+            let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+            writeln!(writer, "{}", fields.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+/// Mix the dataset seed and row index into an independent row seed (SplitMix64 finalizer).
+fn row_seed(seed: u64, index: u64) -> u64 {
+    mix(seed ^ index.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+}
+
+/// Quote `field` if it contains a comma, quote or line break.
+pub(crate) fn csv_field(field: &str) -> String {
+    if field.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{csv_field, PersonaGenerator};
+
+    #[test]
+    fn test_row_determinism() {
+        let personas = PersonaGenerator::default().with_seed(42);
+        let rows = personas.rows(50);
+        assert_eq!(rows[37], personas.row(37));
+        assert_ne!(rows[0], rows[1]);
+        assert_ne!(rows[0], PersonaGenerator::default().with_seed(43).row(0));
+        for persona in &rows {
+            assert!(persona.handle.ends_with("@example.com"));
+            assert!(persona.username.chars().all(|c| c.is_ascii_alphanumeric()));
+        }
+    }
+
+    #[test]
+    fn test_csv() {
+        let mut csv = Vec::new();
+        PersonaGenerator::default().write_csv(2, &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "given,surname,full_name,username,handle,city");
+        assert_eq!(lines[1].split(',').count(), 6);
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+}
@@ -0,0 +1,247 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Phonotactic Constraints
+//!
+//! Rules on the letters of a name, such as "no triple vowels", enforced while a
+//! [`TotroConfig`](crate::TotroConfig) chooses each token rather than by rejecting finished
+//! names. Letters are classed as vowels ("a", "e", "i", "o", "u" and "y") or consonants; other
+//! characters, such as apostrophes, belong to neither and break up runs of both.
+//!
+//! [`Adjacency`] rules work on whole tokens instead, governing which token may directly follow
+//! which, such as "'qu' is always followed by a vowel token".
+
+use alloc::string::String;
+
+/// A rule the letters of a generated name must follow.
+///
+/// ```rust
+/// use nominae::{Constraint, TotroConfig};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let config = TotroConfig::default()
+///     .with_constraint(Constraint::MaxVowelRun(2))
+///     .with_constraint(Constraint::NoEnding("q".into()));
+/// let name = config.generate(&mut rng);
+/// assert!(Constraint::MaxVowelRun(2).admits(&name.to_lowercase(), true));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Constraint {
+    /// At most this many vowels in a row, so `MaxVowelRun(2)` rules out "eau".
+    MaxVowelRun(usize),
+    /// At most this many consonants in a row, ruling out pile-ups like "srkrk".
+    MaxConsonantRun(usize),
+    /// Never contain this string anywhere. Comparison ignores case.
+    NoSequence(String),
+    /// Never end with this string. Comparison ignores case.
+    NoEnding(String),
+}
+
+impl Constraint {
+    /// Whether `name`, lowercased, follows this rule. Endings are only checked when the name is
+    /// `complete`.
+    pub fn admits(&self, name: &str, complete: bool) -> bool {
+        match self {
+            Constraint::MaxVowelRun(max) => longest_run(name, is_vowel) <= *max,
+            Constraint::MaxConsonantRun(max) => longest_run(name, is_consonant) <= *max,
+            Constraint::NoSequence(sequence) => !name.contains(sequence.as_str()),
+            Constraint::NoEnding(ending) => !complete || !name.ends_with(ending.as_str()),
+        }
+    }
+
+    /// This rule with its strings lowercased.
+    pub(crate) fn lowercased(self) -> Constraint {
+        match self {
+            Constraint::NoSequence(sequence) => Constraint::NoSequence(sequence.to_lowercase()),
+            Constraint::NoEnding(ending) => Constraint::NoEnding(ending.to_lowercase()),
+            constraint => constraint,
+        }
+    }
+}
+
+/// Tokens an [`Adjacency`] rule applies to.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TokenClass {
+    /// The token with exactly this text, from either table.
+    Text(String),
+    /// Any token of the vowel table.
+    Vowel,
+    /// Any token of the consonant table.
+    Consonant,
+    /// Any vowel token of more than one character, such as "ae".
+    VowelBlend,
+    /// Any consonant token of more than one character, such as "str".
+    ConsonantBlend,
+}
+
+impl TokenClass {
+    /// Whether the token with `text`, taken from the vowel table if `vowel`, is of this class.
+    pub fn matches(&self, text: &str, vowel: bool) -> bool {
+        let blend = || text.chars().nth(1).is_some();
+        match self {
+            TokenClass::Text(expected) => text == expected,
+            TokenClass::Vowel => vowel,
+            TokenClass::Consonant => !vowel,
+            TokenClass::VowelBlend => vowel && blend(),
+            TokenClass::ConsonantBlend => !vowel && blend(),
+        }
+    }
+}
+
+/// A rule on which tokens may directly follow which, enforced while each token is chosen.
+///
+/// ```rust
+/// use nominae::{Adjacency, TokenClass, TotroConfig};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let config = TotroConfig::default()
+///     .with_adjacency(Adjacency::Forbid(TokenClass::VowelBlend, TokenClass::Text("y".into())));
+/// let name = config.generate(&mut rng);
+/// # let _ = name;
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Adjacency {
+    /// A token of the first class is never directly followed by one of the second.
+    Forbid(TokenClass, TokenClass),
+    /// A token of the first class is always directly followed by one of the second, and so
+    /// never ends a name.
+    Require(TokenClass, TokenClass),
+}
+
+impl Adjacency {
+    /// Whether `next` may directly follow `previous`, each given as its text and whether it is a
+    /// vowel token. A `next` of `None` stands for the end of the name.
+    pub(crate) fn admits(&self, previous: (&str, bool), next: Option<(&str, bool)>) -> bool {
+        let (first, second) = match self {
+            Adjacency::Forbid(first, second) | Adjacency::Require(first, second) => (first, second),
+        };
+        if !first.matches(previous.0, previous.1) {
+            return true;
+        }
+        let followed = next.is_some_and(|(text, vowel)| second.matches(text, vowel));
+        match self {
+            Adjacency::Forbid(..) => !followed,
+            Adjacency::Require(..) => followed,
+        }
+    }
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y')
+}
+
+fn is_consonant(c: char) -> bool {
+    c.is_alphabetic() && !is_vowel(c)
+}
+
+/// Length of the longest run of characters in `name` matching `class`.
+fn longest_run(name: &str, class: fn(char) -> bool) -> usize {
+    let (mut longest, mut run) = (0, 0);
+    for c in name.chars() {
+        run = if class(c) { run + 1 } else { 0 };
+        longest = longest.max(run);
+    }
+    longest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Adjacency, Constraint, TokenClass};
+    use crate::totro::TotroConfig;
+    use crate::{Mode, Syllabic, Table, Token, AIW};
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_admits() {
+        assert!(!Constraint::MaxVowelRun(2).admits("beaum", false));
+        assert!(Constraint::MaxVowelRun(2).admits("bea'um", false));
+        assert!(!Constraint::MaxConsonantRun(2).admits("srkrk", false));
+        assert!(Constraint::NoEnding("q".into()).admits("iraq", false));
+        assert!(!Constraint::NoEnding("q".into()).admits("iraq", true));
+        assert!(!Constraint::NoSequence("rk".into()).admits("ark", false));
+    }
+
+    #[test]
+    fn test_adjacency() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let quiet = Adjacency::Forbid(TokenClass::Text("a".into()), TokenClass::Consonant);
+        let vowels = Table::new(vec![Token::new("a", AIW), Token::new("o", AIW)]).unwrap();
+        let consonants = Table::new(vec![Token::new("k", AIW), Token::new("t", AIW)]).unwrap();
+        let config = TotroConfig::new(vowels, consonants)
+            .with_length(3, 3)
+            .with_vowel_start(1.0)
+            .with_adjacency(quiet);
+        // Choosing "a" first would leave nothing to follow it.
+        for _ in 0..50 {
+            assert!(config.generate(&mut rng).starts_with('O'));
+        }
+
+        let rule = Adjacency::Require(TokenClass::Text("qu".into()), TokenClass::Vowel);
+        assert!(rule.admits(("qu", false), Some(("a", true))));
+        assert!(!rule.admits(("qu", false), Some(("r", false))));
+        assert!(!rule.admits(("qu", false), None));
+        assert!(rule.admits(("k", false), None));
+        let syllabic = TotroConfig::default()
+            .with_adjacency(rule)
+            .with_adjacency(Adjacency::Forbid(
+                TokenClass::VowelBlend,
+                TokenClass::Text("y".into()),
+            ))
+            .with_mode(Mode::Syllabic(Syllabic::default().with_coda(0.8)));
+        for _ in 0..200 {
+            let name = syllabic.generate(&mut rng).to_lowercase();
+            if let Some(at) = name.find("qu") {
+                assert!(
+                    name[at + 2..].starts_with(|c| "aeiouy'".contains(c)),
+                    "{}",
+                    name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_enforced() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let constraints = [
+            Constraint::MaxVowelRun(2),
+            Constraint::MaxConsonantRun(2),
+            Constraint::NoEnding("e".into()),
+        ];
+        let alternating = constraints
+            .iter()
+            .cloned()
+            .fold(TotroConfig::default(), TotroConfig::with_constraint);
+        let syllabic = alternating
+            .clone()
+            .with_mode(Mode::Syllabic(Syllabic::default().with_coda(0.6)));
+        for config in [alternating, syllabic].iter() {
+            for _ in 0..200 {
+                let name = config.generate(&mut rng).to_lowercase();
+                assert!(
+                    constraints.iter().all(|rule| rule.admits(&name, true)),
+                    "{}",
+                    name
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,264 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Pirate Identity Style Pack
+
+use crate::totro::TotroConfig;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::fmt;
+
+const NICKNAMES: [&str; 14] = [
+    "Black",
+    "One-Eyed",
+    "Mad",
+    "Red",
+    "Bloody",
+    "Calico",
+    "Peg-Leg",
+    "Iron",
+    "Salty",
+    "Gentleman",
+    "Long",
+    "Cutthroat",
+    "Old",
+    "Silver",
+];
+
+const EPITHETS: [&str; 10] = [
+    "Terror of the Shoals",
+    "Scourge of the Seven Seas",
+    "Bane of the Navy",
+    "Lord of the Reef",
+    "Wolf of the Waves",
+    "Widow of the Deep",
+    "Master of Tides",
+    "Curse of the Coast",
+    "Queen of the Brine",
+    "Butcher of the Bay",
+];
+
+const SHIP_ADJECTIVES: [&str; 14] = [
+    "Crimson", "Black", "Salty", "Wailing", "Drowned", "Golden", "Silent", "Howling", "Iron",
+    "Jolly", "Cursed", "Storm", "Scarlet", "Sunken",
+];
+
+const SHIP_NOUNS: [&str; 14] = [
+    "Gull",
+    "Revenge",
+    "Maiden",
+    "Serpent",
+    "Kraken",
+    "Widow",
+    "Gale",
+    "Fortune",
+    "Dagger",
+    "Tide",
+    "Corsair",
+    "Albatross",
+    "Siren",
+    "Anchor",
+];
+
+/// A structured pirate identity.
+///
+/// Displays as "Mad Essa Drake, Terror of the Shoals, of the Crimson Gull", leaving out the
+/// nickname and epithet when absent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pirate {
+    /// Nickname placed before the given name, such as "One-Eyed".
+    pub nickname: Option<String>,
+    /// Given name.
+    pub given: String,
+    /// Surname.
+    pub surname: String,
+    /// Sea-themed epithet, such as "Terror of the Shoals".
+    pub epithet: Option<String>,
+    /// Ship the pirate sails on, such as "Crimson Gull".
+    pub ship: String,
+}
+
+impl fmt::Display for Pirate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(nickname) = &self.nickname {
+            write!(f, "{} ", nickname)?;
+        }
+        write!(f, "{} {}", self.given, self.surname)?;
+        if let Some(epithet) = &self.epithet {
+            write!(f, ", {},", epithet)?;
+        }
+        write!(f, " of the {}", self.ship)
+    }
+}
+
+/// Style pack generating [`Pirate`] identities and the ships they sail on.
+///
+/// ```rust
+/// use nominae::PirateStyle;
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let style = PirateStyle::default();
+/// let ship = style.ship(&mut rng);
+/// for pirate in style.crew(&ship, 3, &mut rng) {
+///     println!("{}", pirate);
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PirateStyle {
+    given: TotroConfig,
+    surname: TotroConfig,
+    nickname: f64,
+    epithet: f64,
+}
+
+impl Default for PirateStyle {
+    fn default() -> PirateStyle {
+        PirateStyle {
+            given: TotroConfig::alphabetic().with_length(2, 4),
+            surname: TotroConfig::alphabetic().with_length(2, 4),
+            nickname: 0.5,
+            epithet: 0.25,
+        }
+    }
+}
+
+impl PirateStyle {
+    /// Set the configuration used for given names.
+    pub fn with_given(mut self, config: TotroConfig) -> PirateStyle {
+        self.given = config;
+        self
+    }
+
+    /// Set the configuration used for surnames.
+    pub fn with_surname(mut self, config: TotroConfig) -> PirateStyle {
+        self.surname = config;
+        self
+    }
+
+    /// Set the probability that a pirate has a nickname.
+    pub fn with_nickname(mut self, probability: f64) -> PirateStyle {
+        assert!(
+            (0.0..=1.0).contains(&probability),
+            "nickname probability must be within 0..=1"
+        );
+        self.nickname = probability;
+        self
+    }
+
+    /// Set the probability that a pirate has an epithet.
+    pub fn with_epithet(mut self, probability: f64) -> PirateStyle {
+        assert!(
+            (0.0..=1.0).contains(&probability),
+            "epithet probability must be within 0..=1"
+        );
+        self.epithet = probability;
+        self
+    }
+
+    /// Generate a ship name such as "Crimson Gull".
+    pub fn ship<T: Rng + ?Sized>(&self, rng: &mut T) -> String {
+        let adjective = SHIP_ADJECTIVES.choose(rng).unwrap();
+        let noun = SHIP_NOUNS.choose(rng).unwrap();
+        format!("{} {}", adjective, noun)
+    }
+
+    /// Generate a pirate sailing on a freshly generated ship.
+    pub fn generate<T: Rng + ?Sized>(&self, rng: &mut T) -> Pirate {
+        let ship = self.ship(rng);
+        self.sailor(ship, rng)
+    }
+
+    /// Generate `n` pirates who all sail on `ship`, with distinct nicknames so the crew stays
+    /// distinguishable.
+    pub fn crew<T: Rng + ?Sized>(&self, ship: &str, n: usize, rng: &mut T) -> Vec<Pirate> {
+        let mut crew: Vec<Pirate> = Vec::with_capacity(n);
+        for _ in 0..n {
+            let mut pirate = self.sailor(ship.to_owned(), rng);
+            if crew
+                .iter()
+                .any(|other| other.nickname.is_some() && other.nickname == pirate.nickname)
+            {
+                pirate.nickname = None;
+            }
+            crew.push(pirate);
+        }
+        crew
+    }
+
+    fn sailor<T: Rng + ?Sized>(&self, ship: String, rng: &mut T) -> Pirate {
+        let nickname = if rng.gen_bool(self.nickname) {
+            NICKNAMES.choose(rng).map(|&nickname| nickname.to_owned())
+        } else {
+            None
+        };
+        let given = self.given.generate(rng);
+        let surname = self.surname.generate(rng);
+        let epithet = if rng.gen_bool(self.epithet) {
+            EPITHETS.choose(rng).map(|&epithet| epithet.to_owned())
+        } else {
+            None
+        };
+        Pirate {
+            nickname,
+            given,
+            surname,
+            epithet,
+            ship,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Pirate, PirateStyle};
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_display() {
+        let mut pirate = Pirate {
+            nickname: Some("Mad".into()),
+            given: "Essa".into(),
+            surname: "Drake".into(),
+            epithet: None,
+            ship: "Crimson Gull".into(),
+        };
+        assert_eq!(pirate.to_string(), "Mad Essa Drake of the Crimson Gull");
+        pirate.nickname = None;
+        pirate.epithet = Some("Terror of the Shoals".into());
+        assert_eq!(
+            pirate.to_string(),
+            "Essa Drake, Terror of the Shoals, of the Crimson Gull"
+        );
+    }
+
+    #[test]
+    fn test_crew() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let style = PirateStyle::default().with_nickname(1.0);
+        let crew = style.crew("Sunken Siren", 20, &mut rng);
+        assert!(crew.iter().all(|pirate| pirate.ship == "Sunken Siren"));
+        for pirate in crew.iter().filter(|pirate| pirate.nickname.is_some()) {
+            let same = crew
+                .iter()
+                .filter(|other| other.nickname == pirate.nickname)
+                .count();
+            assert_eq!(same, 1);
+        }
+    }
+}
@@ -0,0 +1,203 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Place Name Generation
+
+use crate::generator::NameGenerator;
+use crate::morphology::attach;
+use crate::stable::below;
+use crate::template::expand;
+use crate::totro::TotroConfig;
+use rand::Rng;
+use std::fmt;
+
+/// Kind of geographic feature being named.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PlaceKind {
+    /// Towns and cities ("Velmarburg", "Port Essa").
+    Settlement,
+    /// Rivers and streams ("the Velmar River", "Essabrook").
+    River,
+    /// Mountains and hills ("Mount Velmar", "Essaberg").
+    Mountain,
+    /// Lakes and tarns ("Lake Velmar", "Essamere").
+    Lake,
+    /// Regions and realms ("Velmarshire", "the Essa Marches").
+    Region,
+}
+
+impl PlaceKind {
+    /// Every kind, in declaration order.
+    pub const ALL: [PlaceKind; 5] = [
+        PlaceKind::Settlement,
+        PlaceKind::River,
+        PlaceKind::Mountain,
+        PlaceKind::Lake,
+        PlaceKind::Region,
+    ];
+
+    /// Forms of this kind of name: a leading `-` marks a suffix joined to the root as a word
+    /// ending, anything else is a template with the root in place of `{root}`.
+    fn forms(self) -> &'static [&'static str] {
+        match self {
+            PlaceKind::Settlement => &[
+                "-burg",
+                "-ford",
+                "-heim",
+                "-ton",
+                "-wick",
+                "-stead",
+                "-holm",
+                "-by",
+                "-haven",
+                "Port {root}",
+                "Fort {root}",
+            ],
+            PlaceKind::River => &[
+                "the {root} River",
+                "the {root}",
+                "-brook",
+                "-water",
+                "-bourne",
+            ],
+            PlaceKind::Mountain => &[
+                "Mount {root}",
+                "{root} Peak",
+                "{root} Crag",
+                "-berg",
+                "the {root} Spires",
+            ],
+            PlaceKind::Lake => &["Lake {root}", "-mere", "{root} Tarn", "-loch"],
+            PlaceKind::Region => &[
+                "-shire",
+                "-mark",
+                "-land",
+                "-vale",
+                "the {root} Marches",
+                "the {root} Reach",
+            ],
+        }
+    }
+}
+
+impl fmt::Display for PlaceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PlaceKind::Settlement => "settlement",
+            PlaceKind::River => "river",
+            PlaceKind::Mountain => "mountain",
+            PlaceKind::Lake => "lake",
+            PlaceKind::Region => "region",
+        })
+    }
+}
+
+/// A generated place name along with the root it was built on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Place {
+    /// Kind of feature named.
+    pub kind: PlaceKind,
+    /// Generated root, such as "Velmar".
+    pub root: String,
+    /// Full name, such as "Velmarburg".
+    pub name: String,
+}
+
+impl fmt::Display for Place {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/// Generator for [`Place`] names, layering geographic morphology on roots from any
+/// [`NameGenerator`].
+///
+/// ```rust
+/// use nominae::{Markov, PlaceGenerator, PlaceKind};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let places = PlaceGenerator::default();
+/// for kind in PlaceKind::ALL.iter() {
+///     println!("{}: {}", kind, places.generate(*kind, &mut rng));
+/// }
+///
+/// let mut model = Markov::new(2);
+/// model.update(&["Velmar", "Essaly", "Korrin", "Maren"]);
+/// let river = PlaceGenerator::default()
+///     .with_roots(model)
+///     .generate(PlaceKind::River, &mut rng);
+/// println!("{} (from {})", river, river.root);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlaceGenerator<G = TotroConfig> {
+    roots: G,
+}
+
+impl Default for PlaceGenerator {
+    fn default() -> PlaceGenerator {
+        PlaceGenerator {
+            roots: TotroConfig::alphabetic().with_length(2, 4),
+        }
+    }
+}
+
+impl<G: NameGenerator> PlaceGenerator<G> {
+    /// Build names on roots from `roots`, such as a trained [`Markov`](crate::Markov) model.
+    pub fn with_roots<H: NameGenerator>(self, roots: H) -> PlaceGenerator<H> {
+        PlaceGenerator { roots }
+    }
+
+    /// Generator of the roots names are built on.
+    pub fn roots(&self) -> &G {
+        &self.roots
+    }
+
+    /// Generate a name for a feature of `kind`.
+    pub fn generate<T: Rng + ?Sized>(&self, kind: PlaceKind, mut rng: &mut T) -> Place {
+        let root = self.roots.generate(&mut rng);
+        let forms = kind.forms();
+        let form = forms[below(rng, forms.len() as u64) as usize];
+        let name = match form.strip_prefix('-') {
+            Some(suffix) => attach(&root, suffix),
+            None => expand(form, |_| root.clone()),
+        };
+        Place { kind, root, name }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PlaceGenerator, PlaceKind};
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_generate() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let places = PlaceGenerator::default();
+        for kind in PlaceKind::ALL.iter().copied() {
+            for _ in 0..50 {
+                let place = places.generate(kind, &mut rng);
+                assert_eq!(place.kind, kind);
+                let stem = &place.root[..place.root.len() - 1];
+                assert!(place.name.contains(stem), "{} from {}", place, place.root);
+                assert_ne!(place.name, place.root);
+            }
+        }
+    }
+}
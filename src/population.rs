@@ -0,0 +1,406 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Name Popularity Simulation
+
+use crate::gender::{Gender, GenderDistribution};
+use crate::normalize::normalize;
+use crate::stable::{below, fraction, StableRng};
+use crate::totro::TotroConfig;
+use rand::{Rng, SeedableRng};
+use std::collections::HashSet;
+
+/// Share of its base popularity a name keeps when far out of fashion.
+const TREND_FLOOR: f64 = 0.05;
+
+/// The rise and fall of a name's popularity over birth years, shaped as a bell curve.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Trend {
+    /// Birth year at which the name is most fashionable.
+    pub peak: i32,
+    /// Number of years over which popularity falls off around the peak.
+    pub width: f64,
+}
+
+impl Trend {
+    /// Multiplier applied to a name's base popularity for people born in `year`.
+    pub fn factor(&self, year: i32) -> f64 {
+        let distance = (year - self.peak) as f64 / self.width;
+        TREND_FLOOR + (1.0 - TREND_FLOOR) * (-distance * distance).exp()
+    }
+}
+
+/// A pool of names with Zipf-like popularity, for assigning names to a simulated population.
+///
+/// Real populations share a small number of common names and a long tail of rare ones. A
+/// `Population` ranks its names and gives the name at rank `r` a weight of `1 / r^s`, so
+/// sampling "a person's name" repeats common names realistically instead of giving every
+/// person a unique name.
+///
+/// ```rust
+/// use nominae::{Population, TotroConfig};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let population = Population::generate(&TotroConfig::default(), 50, &mut rng);
+/// for _ in 0..10 {
+///     println!("{}", population.sample(&mut rng));
+/// }
+/// ```
+///
+/// Populations can also follow naming fashions: [`Population::with_trends`] gives every name a
+/// seeded [`Trend`] so that elders carry old-fashioned names and children carry trendy ones.
+///
+/// ```rust
+/// use nominae::{Population, TotroConfig};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let population = Population::generate(&TotroConfig::default(), 50, &mut rng)
+///     .with_trends(7, 1900, 2000);
+/// println!("grandmother: {}", population.sample_born(1930, &mut rng));
+/// println!("grandchild: {}", population.sample_born(1995, &mut rng));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Population {
+    names: Vec<String>,
+    exponent: f64,
+    cumulative: Vec<f64>,
+    trends: Vec<Trend>,
+}
+
+impl Population {
+    /// Zipf exponent used unless another is provided.
+    pub const DEFAULT_EXPONENT: f64 = 1.0;
+
+    /// Create a population from names ordered from most to least popular.
+    pub fn new(names: Vec<String>) -> Population {
+        let mut population = Population {
+            names,
+            exponent: Population::DEFAULT_EXPONENT,
+            cumulative: Vec::new(),
+            trends: Vec::new(),
+        };
+        population.rebuild();
+        population
+    }
+
+    /// Create a population of `size` distinct names generated from `config`.
+    ///
+    /// Fewer names are returned if `config` cannot produce `size` distinct names in a
+    /// reasonable number of attempts.
+    pub fn generate<T: Rng + ?Sized>(config: &TotroConfig, size: usize, rng: &mut T) -> Population {
+        let mut seen = HashSet::with_capacity(size);
+        let mut names = Vec::with_capacity(size);
+        let mut attempts = size.saturating_mul(100);
+        while names.len() < size && attempts > 0 {
+            let name = config.generate(rng);
+            if seen.insert(normalize(&name)) {
+                names.push(name);
+            } else {
+                event!(trace, %name, "rejected duplicate name");
+            }
+            attempts -= 1;
+        }
+        if names.len() < size {
+            event!(
+                warn,
+                wanted = size,
+                found = names.len(),
+                "ran out of distinct names"
+            );
+        }
+        Population::new(names)
+    }
+
+    /// Set the Zipf exponent. Larger exponents concentrate the population on fewer names and
+    /// `0.0` makes every name equally likely.
+    pub fn with_exponent(mut self, exponent: f64) -> Population {
+        assert!(exponent >= 0.0, "exponent must not be negative");
+        self.exponent = exponent;
+        self.rebuild();
+        self
+    }
+
+    /// Give every name a trend peaking at a year between `from` and `to`, derived from `seed` so
+    /// the same seed always produces the same fashions on every platform.
+    pub fn with_trends(mut self, seed: u64, from: i32, to: i32) -> Population {
+        assert!(
+            from <= to,
+            "from must be less than or equal to to: {} <= {}",
+            from,
+            to
+        );
+        let mut rng = StableRng::seed_from_u64(seed);
+        let years = (i64::from(to) - i64::from(from)) as u64 + 1;
+        self.trends = self
+            .names
+            .iter()
+            .map(|_| Trend {
+                peak: (i64::from(from) + below(&mut rng, years) as i64) as i32,
+                width: 5.0 + fraction(&mut rng) * 25.0,
+            })
+            .collect();
+        self
+    }
+
+    /// Trend followed by `name`, if trends have been assigned.
+    pub fn trend(&self, name: &str) -> Option<Trend> {
+        let rank = self.names.iter().position(|other| other == name)?;
+        self.trends.get(rank).copied()
+    }
+
+    /// Zipf exponent of this population.
+    pub fn exponent(&self) -> f64 {
+        self.exponent
+    }
+
+    /// Names ordered from most to least popular.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Fraction of the population expected to carry `name`.
+    pub fn popularity(&self, name: &str) -> Option<f64> {
+        let rank = self.names.iter().position(|other| other == name)?;
+        let total = *self.cumulative.last()?;
+        let previous = if rank == 0 {
+            0.0
+        } else {
+            self.cumulative[rank - 1]
+        };
+        Some((self.cumulative[rank] - previous) / total)
+    }
+
+    /// Sample the name of a person in the population.
+    ///
+    /// Panics if the population has no names.
+    pub fn sample<T: Rng + ?Sized>(&self, rng: &mut T) -> &str {
+        let total = *self.cumulative.last().expect("population has no names");
+        let roll = fraction(rng) * total;
+        let rank = self.cumulative.partition_point(|&weight| weight <= roll);
+        &self.names[rank.min(self.names.len() - 1)]
+    }
+
+    /// Fraction of people born in `year` expected to carry `name`. Without trends this is the
+    /// same as [`Population::popularity`].
+    pub fn popularity_born(&self, name: &str, year: i32) -> Option<f64> {
+        let rank = self.names.iter().position(|other| other == name)?;
+        let weights = self.weights_born(year);
+        let total: f64 = weights.iter().sum();
+        Some(weights[rank] / total)
+    }
+
+    /// Sample the name of a person born in `year`. Without trends this is the same as
+    /// [`Population::sample`].
+    ///
+    /// Panics if the population has no names.
+    pub fn sample_born<T: Rng + ?Sized>(&self, year: i32, rng: &mut T) -> &str {
+        if self.trends.is_empty() {
+            return self.sample(rng);
+        }
+        let weights = self.weights_born(year);
+        let mut roll = fraction(rng) * weights.iter().sum::<f64>();
+        for (name, weight) in self.names.iter().zip(weights) {
+            if roll < weight {
+                return name;
+            }
+            roll -= weight;
+        }
+        self.names.last().expect("population has no names")
+    }
+
+    /// Popularity weight of each name, in rank order, for people born in `year`.
+    fn weights_born(&self, year: i32) -> Vec<f64> {
+        (0..self.names.len())
+            .map(|rank| {
+                let base = ((rank + 1) as f64).powf(-self.exponent);
+                match self.trends.get(rank) {
+                    Some(trend) => base * trend.factor(year),
+                    None => base,
+                }
+            })
+            .collect()
+    }
+
+    fn rebuild(&mut self) {
+        let mut total = 0.0;
+        let exponent = self.exponent;
+        self.cumulative = (1..=self.names.len())
+            .map(|rank| {
+                total += (rank as f64).powf(-exponent);
+                total
+            })
+            .collect();
+    }
+}
+
+/// A population split by gender, with a pool of names for each gender.
+///
+/// People are sampled by first drawing their gender from a [`GenderDistribution`] and then a
+/// name from that gender's pool, generated in the gender's own style.
+///
+/// ```rust
+/// use nominae::{Demographics, Gender, GenderDistribution, TotroConfig};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let distribution = GenderDistribution::default()
+///     .with_style(Gender::Neutral, TotroConfig::alphabetic().with_length(2, 3));
+/// let demographics = Demographics::generate(distribution, 30, &mut rng);
+/// for _ in 0..5 {
+///     let (gender, name) = demographics.sample(&mut rng);
+///     println!("{} ({})", name, gender);
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Demographics {
+    distribution: GenderDistribution,
+    populations: Vec<(Gender, Population)>,
+}
+
+impl Demographics {
+    /// Generate a pool of up to `size` distinct names for each gender `distribution` can draw.
+    pub fn generate<T: Rng + ?Sized>(
+        distribution: GenderDistribution,
+        size: usize,
+        rng: &mut T,
+    ) -> Demographics {
+        let populations = distribution
+            .genders()
+            .map(|gender| {
+                let population = Population::generate(distribution.style(gender), size, rng);
+                (gender, population)
+            })
+            .collect();
+        Demographics {
+            distribution,
+            populations,
+        }
+    }
+
+    /// Distribution genders are drawn from.
+    pub fn distribution(&self) -> &GenderDistribution {
+        &self.distribution
+    }
+
+    /// Pool of names for `gender`, if the distribution can draw it.
+    pub fn population(&self, gender: Gender) -> Option<&Population> {
+        self.populations
+            .iter()
+            .find(|(other, _)| *other == gender)
+            .map(|(_, population)| population)
+    }
+
+    /// Sample the gender and name of a person.
+    ///
+    /// Panics if the distribution has no weight or a pool has no names.
+    pub fn sample<T: Rng + ?Sized>(&self, rng: &mut T) -> (Gender, &str) {
+        let gender = self.distribution.sample(rng);
+        let population = self
+            .population(gender)
+            .expect("every drawable gender has a population");
+        (gender, population.sample(rng))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Demographics, Population, Trend};
+    use crate::gender::{Gender, GenderDistribution};
+    use crate::TotroConfig;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_popularity() {
+        let population = Population::new(vec!["Ana".into(), "Bel".into(), "Cor".into()]);
+        assert!((population.popularity("Ana").unwrap() - 6.0 / 11.0).abs() < 1e-9);
+        assert!((population.popularity("Cor").unwrap() - 2.0 / 11.0).abs() < 1e-9);
+        assert_eq!(population.popularity("Dov"), None);
+        let flat = population.with_exponent(0.0);
+        assert!((flat.popularity("Bel").unwrap() - 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let population = Population::generate(&TotroConfig::default(), 100, &mut rng);
+        assert_eq!(population.names().len(), 100);
+        let mut counts = HashMap::new();
+        for _ in 0..10_000 {
+            *counts.entry(population.sample(&mut rng)).or_insert(0) += 1;
+        }
+        let first = counts[population.names()[0].as_str()];
+        let tenth = counts[population.names()[9].as_str()];
+        assert!(first > tenth * 5, "{} vs {}", first, tenth);
+    }
+
+    #[test]
+    fn test_trends() {
+        let trend = Trend {
+            peak: 1950,
+            width: 10.0,
+        };
+        assert!((trend.factor(1950) - 1.0).abs() < 1e-9);
+        assert!(trend.factor(1960) > trend.factor(1990));
+
+        let mut rng = SmallRng::seed_from_u64(0);
+        let population =
+            Population::generate(&TotroConfig::default(), 40, &mut rng).with_trends(3, 1900, 2000);
+        let early = population
+            .names()
+            .iter()
+            .min_by_key(|name| population.trend(name).unwrap().peak)
+            .unwrap();
+        let old = population.popularity_born(early, 1900).unwrap();
+        let new = population.popularity_born(early, 2000).unwrap();
+        assert!(old > new, "{} vs {}", old, new);
+        assert_eq!(population.clone().with_trends(3, 1900, 2000), population);
+        // Seeded trends are drawn from StableRng, so they are the same on every platform.
+        let pinned = Population::new(vec!["Ana".into()]).with_trends(3, 1900, 2000);
+        let trend = pinned.trend("Ana").unwrap();
+        assert_eq!(trend.peak, 1905);
+        assert!((trend.width - 21.197_938_654_981_705).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_demographics() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let short = TotroConfig::alphabetic().with_length(1, 1);
+        let distribution = GenderDistribution::default()
+            .with_weight(Gender::Masculine, 0)
+            .with_style(Gender::Neutral, short.clone());
+        let demographics = Demographics::generate(distribution, 20, &mut rng);
+        assert!(demographics.population(Gender::Masculine).is_none());
+        for _ in 0..200 {
+            let (gender, name) = demographics.sample(&mut rng);
+            assert_ne!(gender, Gender::Masculine);
+            let pool = demographics.population(gender).unwrap();
+            assert!(pool.names().iter().any(|other| other == name));
+        }
+        let neutral = demographics.population(Gender::Neutral).unwrap();
+        assert!(
+            neutral.names().iter().all(|name| name.len() <= 3),
+            "{:?}",
+            neutral
+        );
+    }
+}
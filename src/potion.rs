@@ -0,0 +1,154 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Potion and Alchemical Concoction Name Generation
+
+use crate::morphology::attach;
+use crate::totro::TotroConfig;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::fmt;
+
+const VESSELS: [&str; 10] = [
+    "Tincture",
+    "Elixir",
+    "Draught",
+    "Philter",
+    "Tonic",
+    "Essence",
+    "Brew",
+    "Distillate",
+    "Salve",
+    "Cordial",
+];
+
+const QUALIFIERS: [&str; 14] = [
+    "Hollow", "Silver", "Waking", "Bitter", "Endless", "Veiled", "Burning", "Quiet", "Second",
+    "Moonlit", "Unbroken", "Ashen", "Distant", "Lucid",
+];
+
+/// A generated potion name bound to the effect it was named for.
+///
+/// Displays as "Tincture of Hollow Sight".
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Potion {
+    /// Kind of concoction, such as "Tincture".
+    pub vessel: String,
+    /// Generated mystical qualifier, such as "Hollow" or "Maruvian".
+    pub qualifier: String,
+    /// Effect keyword supplied by the caller, such as "Sight".
+    pub effect: String,
+}
+
+impl fmt::Display for Potion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} of {} {}", self.vessel, self.qualifier, self.effect)
+    }
+}
+
+/// Generator for effect-linked [`Potion`] names.
+///
+/// The caller supplies the effect keyword, so crafting systems can keep the name and the
+/// mechanical effect in step, and the generator adds the vessel and a mystical qualifier. The
+/// qualifier is drawn from a fixed list or, with a configurable probability, derived from an
+/// invented name ("Elixir of Maruvian Strength").
+///
+/// ```rust
+/// use nominae::PotionGenerator;
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let potion = PotionGenerator::default().generate("Sight", &mut rng);
+/// assert_eq!(potion.effect, "Sight");
+/// println!("{}", potion);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PotionGenerator {
+    names: TotroConfig,
+    invented: f64,
+}
+
+impl Default for PotionGenerator {
+    fn default() -> PotionGenerator {
+        PotionGenerator {
+            names: TotroConfig::alphabetic().with_length(2, 4),
+            invented: 0.25,
+        }
+    }
+}
+
+impl PotionGenerator {
+    /// Set the configuration used to generate invented qualifiers.
+    pub fn with_names(mut self, names: TotroConfig) -> PotionGenerator {
+        self.names = names;
+        self
+    }
+
+    /// Set the probability that the qualifier is derived from an invented name.
+    pub fn with_invented(mut self, probability: f64) -> PotionGenerator {
+        assert!(
+            (0.0..=1.0).contains(&probability),
+            "invented probability must be within 0..=1"
+        );
+        self.invented = probability;
+        self
+    }
+
+    /// Generate a potion name for `effect`.
+    pub fn generate<T: Rng + ?Sized>(&self, effect: &str, rng: &mut T) -> Potion {
+        let vessel = VESSELS.choose(rng).unwrap();
+        let qualifier = if rng.gen_bool(self.invented) {
+            attach(&self.names.generate(rng), "ian")
+        } else {
+            QUALIFIERS.choose(rng).unwrap().to_string()
+        };
+        Potion {
+            vessel: vessel.to_string(),
+            qualifier,
+            effect: effect.to_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Potion, PotionGenerator};
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_display() {
+        let potion = Potion {
+            vessel: "Tincture".into(),
+            qualifier: "Hollow".into(),
+            effect: "Sight".into(),
+        };
+        assert_eq!(potion.to_string(), "Tincture of Hollow Sight");
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let generator = PotionGenerator::default().with_invented(1.0);
+        for _ in 0..20 {
+            let potion = generator.generate("Strength", &mut rng);
+            assert_eq!(potion.effect, "Strength");
+            assert!(potion.qualifier.ends_with("ian"), "{}", potion);
+            assert!(potion.to_string().ends_with(" Strength"));
+        }
+    }
+}
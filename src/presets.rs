@@ -0,0 +1,304 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Curated Totro Configurations
+//!
+//! ```rust
+//! use nominae::presets;
+//! use rand::SeedableRng;
+//! use rand::rngs::SmallRng;
+//!
+//! let mut rng = SmallRng::seed_from_u64(0);
+//! println!("{}", presets::pet().generate(&mut rng));
+//! for theme in presets::Theme::ALL.iter() {
+//!     println!("{}: {}", theme, theme.config().generate(&mut rng));
+//! }
+//! ```
+
+use crate::table::{Table, AIW, BMW, EOW, MEW, MOW};
+use crate::totro::TotroConfig;
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Consonants that make a pleasant, soft ending for pet names.
+const SOFT_ENDINGS: [&str; 5] = ["l", "m", "n", "s", "sh"];
+
+/// Vowels favored for the cute endings typical of pet names ("Bella", "Milo", "Poppy", "Rosie").
+const PET_VOWELS: [(&str, u32); 4] = [("a", 40), ("o", 30), ("y", 20), ("ie", 20)];
+
+/// Short, soft names for pets and companions ("Milo", "Bosa", "Tuffy").
+///
+/// Names are one or two syllables, usually open on a consonant, and may only end on a vowel or
+/// a soft consonant. Repeated tokens are left allowed since pet names commonly double sounds.
+pub fn pet() -> TotroConfig {
+    let mut config = TotroConfig::alphabetic()
+        .with_length(2, 4)
+        .with_vowel_start(0.2);
+    let harsh: Vec<(String, u8)> = config
+        .consonants()
+        .tokens()
+        .iter()
+        .filter(|token| token.allows(EOW) && !SOFT_ENDINGS.contains(&token.text()))
+        .map(|token| (token.text().to_owned(), token.flags() & BMW))
+        .collect();
+    for (text, flags) in harsh {
+        config
+            .consonants_mut()
+            .set_flags(&text, flags)
+            .expect("soft endings remain for the end of words");
+    }
+    for &(text, weight) in PET_VOWELS.iter() {
+        config
+            .vowels_mut()
+            .set_weight(text, weight)
+            .expect("pet vowels are in the built-in table");
+    }
+    config
+}
+
+/// Fantasy cultures with a curated configuration each.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Theme {
+    /// Flowing, vowel-rich names ("Laeriel", "Thalion").
+    Elvish,
+    /// Short, guttural names closed on heavy consonants ("Durgrim", "Borin").
+    Dwarvish,
+    /// Harsh, clipped names ("Grukzog", "Urzhak").
+    Orcish,
+    /// Long, sibilant names ("Szythrax", "Vaeshiss").
+    Draconic,
+}
+
+impl Theme {
+    /// Every theme, in declaration order.
+    pub const ALL: [Theme; 4] = [
+        Theme::Elvish,
+        Theme::Dwarvish,
+        Theme::Orcish,
+        Theme::Draconic,
+    ];
+
+    /// Configuration generating names in this theme.
+    pub fn config(self) -> TotroConfig {
+        match self {
+            Theme::Elvish => elvish(),
+            Theme::Dwarvish => dwarvish(),
+            Theme::Orcish => orcish(),
+            Theme::Draconic => draconic(),
+        }
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Theme::Elvish => "elvish",
+            Theme::Dwarvish => "dwarvish",
+            Theme::Orcish => "orcish",
+            Theme::Draconic => "draconic",
+        })
+    }
+}
+
+const ELVISH_VOWELS: [(&str, u8, u32); 10] = [
+    ("a", AIW, 30),
+    ("e", AIW, 30),
+    ("i", AIW, 25),
+    ("o", AIW, 10),
+    ("ae", AIW, 8),
+    ("ie", MEW, 6),
+    ("ia", MEW, 6),
+    ("ea", BMW, 5),
+    ("ei", MOW, 4),
+    ("y", MEW, 3),
+];
+
+const ELVISH_CONSONANTS: [(&str, u8, u32); 14] = [
+    ("l", AIW, 30),
+    ("n", AIW, 25),
+    ("r", AIW, 20),
+    ("s", AIW, 12),
+    ("th", AIW, 10),
+    ("v", BMW, 10),
+    ("m", BMW, 8),
+    ("f", BMW, 6),
+    ("d", BMW, 6),
+    ("c", BMW, 4),
+    ("g", BMW, 4),
+    ("ll", MOW, 6),
+    ("nd", MEW, 6),
+    ("ndil", MEW, 3),
+];
+
+const DWARVISH_VOWELS: [(&str, u8, u32); 6] = [
+    ("a", AIW, 25),
+    ("o", AIW, 25),
+    ("u", AIW, 20),
+    ("i", AIW, 10),
+    ("e", BMW, 8),
+    ("ai", MOW, 3),
+];
+
+const DWARVISH_CONSONANTS: [(&str, u8, u32); 17] = [
+    ("d", AIW, 15),
+    ("g", AIW, 12),
+    ("k", AIW, 10),
+    ("r", AIW, 15),
+    ("b", BMW, 12),
+    ("th", AIW, 8),
+    ("n", MEW, 12),
+    ("m", MEW, 6),
+    ("l", MOW, 6),
+    ("z", MOW, 3),
+    ("dr", BMW, 6),
+    ("gr", BMW, 6),
+    ("br", BMW, 6),
+    ("rm", MEW, 5),
+    ("rn", MEW, 6),
+    ("ld", MEW, 4),
+    ("rim", MEW, 5),
+];
+
+const ORCISH_VOWELS: [(&str, u8, u32); 5] = [
+    ("u", AIW, 30),
+    ("a", AIW, 30),
+    ("o", AIW, 25),
+    ("i", MOW, 5),
+    ("au", MOW, 3),
+];
+
+const ORCISH_CONSONANTS: [(&str, u8, u32); 15] = [
+    ("g", AIW, 20),
+    ("k", AIW, 18),
+    ("z", AIW, 12),
+    ("r", AIW, 12),
+    ("sh", AIW, 10),
+    ("b", BMW, 8),
+    ("m", BMW, 5),
+    ("gr", BMW, 10),
+    ("kr", BMW, 8),
+    ("zh", BMW, 6),
+    ("gh", MEW, 8),
+    ("zg", MOW, 6),
+    ("rz", MEW, 5),
+    ("kk", MOW, 4),
+    ("th", MEW, 4),
+];
+
+const DRACONIC_VOWELS: [(&str, u8, u32); 8] = [
+    ("a", AIW, 25),
+    ("i", AIW, 20),
+    ("y", MOW, 12),
+    ("ae", AIW, 10),
+    ("o", AIW, 10),
+    ("u", BMW, 5),
+    ("ei", MOW, 5),
+    ("ia", MEW, 4),
+];
+
+const DRACONIC_CONSONANTS: [(&str, u8, u32); 15] = [
+    ("s", AIW, 20),
+    ("sh", AIW, 15),
+    ("z", AIW, 15),
+    ("th", AIW, 15),
+    ("r", AIW, 15),
+    ("x", MEW, 12),
+    ("v", BMW, 10),
+    ("k", BMW, 8),
+    ("n", MEW, 8),
+    ("rh", BMW, 8),
+    ("sz", BMW, 5),
+    ("ss", MEW, 10),
+    ("thr", BMW, 6),
+    ("rax", MEW, 6),
+    ("ith", MEW, 4),
+];
+
+fn themed(
+    vowels: &[(&'static str, u8, u32)],
+    consonants: &[(&'static str, u8, u32)],
+) -> TotroConfig {
+    TotroConfig::new(Table::from_entries(vowels), Table::from_entries(consonants))
+}
+
+/// Flowing elvish names of two to four syllables that favor liquids and open vowels.
+pub fn elvish() -> TotroConfig {
+    themed(&ELVISH_VOWELS, &ELVISH_CONSONANTS)
+        .with_length(5, 9)
+        .with_vowel_start(0.3)
+}
+
+/// Guttural dwarvish names of one or two syllables, usually closed on a heavy consonant.
+pub fn dwarvish() -> TotroConfig {
+    themed(&DWARVISH_VOWELS, &DWARVISH_CONSONANTS)
+        .with_length(3, 6)
+        .with_vowel_start(0.1)
+}
+
+/// Harsh orcish names of one or two syllables built from stops and buzzing consonants.
+pub fn orcish() -> TotroConfig {
+    themed(&ORCISH_VOWELS, &ORCISH_CONSONANTS)
+        .with_length(3, 6)
+        .with_vowel_start(0.2)
+}
+
+/// Long, sibilant draconic names of three or four syllables.
+pub fn draconic() -> TotroConfig {
+    themed(&DRACONIC_VOWELS, &DRACONIC_CONSONANTS)
+        .with_length(6, 9)
+        .with_vowel_start(0.15)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pet, Theme, SOFT_ENDINGS};
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_pet() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let config = pet();
+        for _ in 0..500 {
+            let name = config.generate(&mut rng).to_ascii_lowercase();
+            assert!(name.len() <= 9, "{}", name);
+            let soft = name.ends_with(|c| "aeiouy".contains(c))
+                || SOFT_ENDINGS.iter().any(|ending| name.ends_with(ending));
+            assert!(soft, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_themes() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for theme in Theme::ALL.iter() {
+            let config = theme.config();
+            config.vowels().validate().unwrap();
+            config.consonants().validate().unwrap();
+            let names = config.preview(20);
+            assert!(names.iter().all(|name| !name.is_empty()), "{}", theme);
+            config.generate(&mut rng);
+        }
+        let orcish = Theme::Orcish.config();
+        for _ in 0..200 {
+            let name = orcish.generate(&mut rng).to_ascii_lowercase();
+            assert!(!name.contains(|c| "lefv".contains(c)), "{}", name);
+        }
+        assert_eq!(Theme::Draconic.to_string(), "draconic");
+    }
+}
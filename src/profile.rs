@@ -0,0 +1,113 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Versioned Generation Profiles
+
+use crate::error::NominaeError;
+use alloc::borrow::ToOwned;
+use core::fmt;
+use core::str::FromStr;
+
+/// A frozen revision of the built-in tables and generation algorithm.
+///
+/// Improvements to either ship as a new version while earlier versions keep producing exactly
+/// the names they always have, so a save file can record the version its world was generated
+/// with and regenerate it forever. New configurations use [`ProfileVersion::LATEST`].
+///
+/// ```rust
+/// use nominae::{ProfileVersion, StableRng, TotroConfig};
+/// use rand::SeedableRng;
+///
+/// let saved: ProfileVersion = "v1".parse().unwrap();
+/// let config = TotroConfig::versioned(saved);
+/// let name = config.generate(&mut StableRng::seed_from_u64(42));
+/// assert_eq!(config.version().to_string(), "v1");
+/// # let _ = name;
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum ProfileVersion {
+    /// The tables and algorithm of the first stable release.
+    V1,
+}
+
+impl ProfileVersion {
+    /// The version used by default configurations.
+    pub const LATEST: ProfileVersion = ProfileVersion::V1;
+
+    /// Every version, oldest first.
+    pub const ALL: [ProfileVersion; 1] = [ProfileVersion::V1];
+
+    /// Number of this version, as written in save files.
+    pub fn number(self) -> u32 {
+        match self {
+            ProfileVersion::V1 => 1,
+        }
+    }
+
+    /// Version numbered `number`, if this release knows it.
+    pub fn from_number(number: u32) -> Option<ProfileVersion> {
+        ProfileVersion::ALL
+            .iter()
+            .copied()
+            .find(|version| version.number() == number)
+    }
+}
+
+impl Default for ProfileVersion {
+    fn default() -> ProfileVersion {
+        ProfileVersion::LATEST
+    }
+}
+
+impl fmt::Display for ProfileVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "v{}", self.number())
+    }
+}
+
+impl FromStr for ProfileVersion {
+    type Err = NominaeError;
+
+    /// Parse a version written as `v1` or `1`.
+    fn from_str(text: &str) -> Result<ProfileVersion, NominaeError> {
+        let digits = text.strip_prefix('v').unwrap_or(text);
+        digits
+            .parse()
+            .ok()
+            .and_then(ProfileVersion::from_number)
+            .ok_or_else(|| NominaeError::UnknownProfile(text.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProfileVersion;
+    use crate::error::NominaeError;
+
+    #[test]
+    fn test_parse() {
+        for &version in ProfileVersion::ALL.iter() {
+            assert_eq!(version.to_string().parse(), Ok(version));
+            assert_eq!(version.number().to_string().parse(), Ok(version));
+        }
+        assert_eq!(
+            "v0".parse::<ProfileVersion>(),
+            Err(NominaeError::UnknownProfile("v0".into()))
+        );
+        assert_eq!(ProfileVersion::default(), ProfileVersion::LATEST);
+    }
+}
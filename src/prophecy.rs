@@ -0,0 +1,142 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Prophecy and Omen Phrase Generation
+
+use crate::grammar::{Grammar, Phrase};
+use crate::totro::TotroConfig;
+use rand::Rng;
+
+/// Generator for short prophetic phrases embedding generated proper nouns, such as
+/// "When Velra's star falls, the Ashen King wakes."
+///
+/// The underlying [`Grammar`] starts from the `prophecy` symbol and can be extended with more
+/// alternatives for any of its rules (`omen`, `doom`, `color`, `ruler`, `creature`, `relic`).
+///
+/// ```rust
+/// use nominae::Prophecy;
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let prophecy = Prophecy::default().generate(&mut rng);
+/// println!("{} (names: {:?})", prophecy, prophecy.names);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Prophecy {
+    grammar: Grammar,
+}
+
+impl Default for Prophecy {
+    fn default() -> Prophecy {
+        let grammar = Grammar::default()
+            .rule(
+                "prophecy",
+                &[
+                    "When {omen}, {doom}.",
+                    "Before {omen}, {doom}.",
+                    "{doom} on the day {omen}.",
+                    "Beware: when {omen}, {doom}.",
+                    "Only when {omen} will {doom_future}.",
+                ],
+            )
+            .rule(
+                "omen",
+                &[
+                    "{name}'s star falls",
+                    "the {color} moon rises over {name}",
+                    "the last {creature} of {name} dies",
+                    "{name} drinks from the {color} well",
+                    "the rivers of {name} run {color}",
+                    "the {relic} of {name} is found",
+                ],
+            )
+            .rule(
+                "doom",
+                &[
+                    "the {color} {ruler} wakes",
+                    "{name} shall fall",
+                    "the {creature} of {name} returns",
+                    "the heir of {name} rises",
+                    "the {color} {relic} breaks",
+                ],
+            )
+            .rule(
+                "doom_future",
+                &[
+                    "the {color} {ruler} wake",
+                    "{name} fall",
+                    "the {creature} of {name} return",
+                ],
+            )
+            .rule(
+                "color",
+                &[
+                    "Ashen", "Crimson", "Silver", "Hollow", "Black", "Pale", "Golden",
+                ],
+            )
+            .rule("ruler", &["King", "Queen", "Tyrant", "Prophet", "Warden"])
+            .rule("creature", &["wolf", "dragon", "serpent", "raven", "lion"])
+            .rule("relic", &["crown", "blade", "chalice", "throne", "horn"]);
+        Prophecy { grammar }
+    }
+}
+
+impl Prophecy {
+    /// Set the configuration used to generate proper nouns.
+    pub fn with_names(mut self, names: TotroConfig) -> Prophecy {
+        self.grammar = self.grammar.with_names(names);
+        self
+    }
+
+    /// Add alternatives to one of the prophecy grammar's rules.
+    pub fn rule(mut self, symbol: &str, alternatives: &[&str]) -> Prophecy {
+        self.grammar = self.grammar.rule(symbol, alternatives);
+        self
+    }
+
+    /// Grammar used to generate prophecies.
+    pub fn grammar(&self) -> &Grammar {
+        &self.grammar
+    }
+
+    /// Generate a prophecy.
+    pub fn generate<T: Rng + ?Sized>(&self, rng: &mut T) -> Phrase {
+        self.grammar.generate("prophecy", rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Prophecy;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_prophecy() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let prophecy = Prophecy::default();
+        for _ in 0..50 {
+            let phrase = prophecy.generate(&mut rng);
+            assert!(phrase.text.ends_with('.'), "{}", phrase);
+            assert!(!phrase.text.contains('{'), "{}", phrase);
+            assert!(phrase
+                .names
+                .iter()
+                .all(|name| phrase.text.contains(name.as_str())));
+        }
+    }
+}
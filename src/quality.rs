@@ -0,0 +1,153 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Name Quality Heuristics
+//!
+//! Rough measures of how a name reads, for picking the best of a batch of candidates. They
+//! look only at letters, so they judge names from any generator the same way.
+
+use crate::evaluation::closeness;
+use crate::morphology::is_vowel;
+use crate::normalize::normalize;
+
+/// Share of vowels among the letters of the easiest names to read.
+const IDEAL_VOWEL_SHARE: f64 = 0.42;
+
+/// Consonant pairs common in English-like names. Other pairs of adjacent consonants, such as
+/// "kp" or "vz", count against a name.
+const CLUSTERS: [&str; 60] = [
+    "bl", "br", "ch", "ck", "cl", "cr", "ct", "dr", "fl", "fr", "gh", "gl", "gr", "kl", "kr", "ld",
+    "lf", "lk", "ll", "lm", "ln", "lp", "ls", "lt", "lv", "mb", "mm", "mp", "nc", "nd", "ng", "nk",
+    "nn", "ns", "nt", "ph", "pl", "pr", "rb", "rc", "rd", "rg", "rk", "rl", "rm", "rn", "rr", "rs",
+    "rt", "sc", "sh", "sk", "sl", "sm", "sn", "sp", "ss", "st", "th", "tr",
+];
+
+/// Taken off the score for each pair of adjacent consonants not among [`CLUSTERS`].
+const UNCOMMON_CLUSTER: f64 = 0.1;
+
+/// How much the closeness of a candidate to the names already picked counts against its
+/// pronounceability when ranking.
+const VARIETY: f64 = 0.5;
+
+/// Score from 0 (unreadable) to 1 of how pronounceable `name` is, for ranking or rejecting
+/// generated names.
+///
+/// Starts from how close the share of vowels is to that of easily read names, and takes off
+/// for runs of more than two consonants or vowels ("srkr", "aeio"), tripled letters and
+/// pairs of consonants rarely seen together ("kp"). Only letters count, compared in
+/// [`normalize`]d form.
+///
+/// ```rust
+/// use nominae::score;
+///
+/// assert!(score("Velmar") > 0.8);
+/// assert!(score("Velmar") > score("Xkpvt"));
+/// ```
+pub fn score(name: &str) -> f32 {
+    let letters: Vec<char> = normalize(name)
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .collect();
+    let uncommon = letters
+        .windows(2)
+        .filter(|pair| !is_vowel(pair[0]) && !is_vowel(pair[1]))
+        .filter(|pair| {
+            let pair: String = pair.iter().collect();
+            !CLUSTERS.contains(&pair.as_str())
+        })
+        .count();
+    (pronounceability(&letters) - UNCOMMON_CLUSTER * uncommon as f64).max(0.0) as f32
+}
+
+/// Score from 0 to 1 of how easily the lowercase `letters` of a name are read aloud, from
+/// their balance of vowels and consonants and any overlong runs or tripled letters.
+fn pronounceability(letters: &[char]) -> f64 {
+    if letters.is_empty() {
+        return 0.0;
+    }
+    let vowels = letters.iter().filter(|&&c| is_vowel(c)).count();
+    let share = vowels as f64 / letters.len() as f64;
+    let mut score = 1.0 - (share - IDEAL_VOWEL_SHARE).abs() / (1.0 - IDEAL_VOWEL_SHARE);
+    let mut run = 0;
+    for (idx, &c) in letters.iter().enumerate() {
+        let same_class = idx > 0 && is_vowel(c) == is_vowel(letters[idx - 1]);
+        run = if same_class { run + 1 } else { 1 };
+        if run > 2 {
+            score -= if is_vowel(c) { 0.2 } else { 0.25 };
+        }
+        if idx > 1 && c == letters[idx - 1] && c == letters[idx - 2] {
+            score -= 0.3;
+        }
+    }
+    score.max(0.0)
+}
+
+/// Up to `k` of `candidates`, best first, trading [`score`] against variety so the picks are
+/// not all near-copies of the single best name.
+///
+/// Each pick is the candidate scoring highest once its closeness to the closest name already
+/// picked is taken off. Candidates with the same [`normalize`]d form as a pick are dropped.
+pub(crate) fn rank(candidates: Vec<String>, k: usize) -> Vec<String> {
+    let mut pool: Vec<(String, Vec<char>, f64)> = candidates
+        .into_iter()
+        .map(|name| {
+            let chars = normalize(&name).chars().collect();
+            let score = f64::from(score(&name));
+            (name, chars, score)
+        })
+        .collect();
+    let mut picked: Vec<(String, Vec<char>)> = Vec::with_capacity(k);
+    while picked.len() < k && !pool.is_empty() {
+        let value = |(_, chars, score): &(String, Vec<char>, f64)| {
+            let nearest = picked
+                .iter()
+                .map(|(_, other)| closeness(chars, other))
+                .fold(0.0, f64::max);
+            score - VARIETY * nearest
+        };
+        let best = (0..pool.len())
+            .max_by(|&a, &b| value(&pool[a]).total_cmp(&value(&pool[b])).then(b.cmp(&a)))
+            .expect("pool is not empty");
+        let (name, chars, _) = pool.swap_remove(best);
+        pool.retain(|(_, other, _)| *other != chars);
+        picked.push((name, chars));
+    }
+    picked.into_iter().map(|(name, _)| name).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{rank, score};
+
+    #[test]
+    fn test_score() {
+        assert!(score("Velmar") > 0.8);
+        assert!(score("Velmar") > score("Srkrk"));
+        assert!(score("Aeiou") < score("Aleo"));
+        assert!(score("Vellla") < score("Vella"));
+        assert!(score("Vekpar") < score("Vekrar"));
+        assert_eq!(score("'-"), 0.0);
+    }
+
+    #[test]
+    fn test_rank() {
+        let candidates = ["Velmara", "velmara", "Velmari", "Srkrk", "Dorn"];
+        let ranked = rank(candidates.iter().map(|&name| name.to_owned()).collect(), 3);
+        // "Dorn" reads less easily than "Velmari" but is no copy of "Velmara".
+        assert_eq!(ranked, vec!["Velmara", "Dorn", "Velmari"]);
+        assert_eq!(rank(vec!["Velmar".into()], 3), vec!["Velmar"]);
+    }
+}
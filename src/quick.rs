@@ -0,0 +1,157 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! One-Call Generators
+//!
+//! Ergonomic entry points that bundle several generators behind a single seeded call, for
+//! tooling that wants results without configuring anything.
+//!
+//! ```rust
+//! use nominae::quick;
+//!
+//! let bundle = quick::gm_bundle(7);
+//! assert_eq!(bundle, quick::gm_bundle(7));
+//! println!("{}", bundle);
+//! ```
+
+use crate::grammar::Grammar;
+use crate::persona::PersonaGenerator;
+use crate::stable::{below, StableRng};
+use crate::syndicate::{Structure, SyndicateGenerator};
+use crate::totro::TotroConfig;
+use rand::SeedableRng;
+use std::fmt;
+
+/// Structures a bundle's faction is drawn from.
+const STRUCTURES: [Structure; 4] = [
+    Structure::StreetGang,
+    Structure::Cartel,
+    Structure::Family,
+    Structure::Cult,
+];
+
+/// Names a game master might need at short notice during a session.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GmBundle {
+    /// Five full names of people, such as "Essa Drake".
+    pub people: Vec<String>,
+    /// Three place names, such as "Velmar".
+    pub places: Vec<String>,
+    /// Two tavern names, such as "The Drowsy Boar".
+    pub taverns: Vec<String>,
+    /// One faction name, such as "The Velmar Cartel".
+    pub faction: String,
+}
+
+impl fmt::Display for GmBundle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sections = [
+            ("People", &self.people[..]),
+            ("Places", &self.places[..]),
+            ("Taverns", &self.taverns[..]),
+            ("Faction", std::slice::from_ref(&self.faction)),
+        ];
+        for (idx, (title, names)) in sections.iter().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "{}:", title)?;
+            for name in names.iter() {
+                writeln!(f, "  {}", name)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Generate a [`GmBundle`] of five people, three places, two taverns and a faction.
+///
+/// A seed always gives the same bundle, so it can be noted down to recall a session's names
+/// later.
+pub fn gm_bundle(seed: u64) -> GmBundle {
+    let mut rng = StableRng::seed_from_u64(seed);
+    let people = PersonaGenerator::default()
+        .with_seed(seed)
+        .rows(5)
+        .iter()
+        .map(|persona| persona.full_name())
+        .collect();
+    let places = TotroConfig::alphabetic().with_length(2, 5);
+    let places = (0..3).map(|_| places.generate(&mut rng)).collect();
+    let taverns = taverns();
+    let taverns = (0..2)
+        .map(|_| taverns.generate("tavern", &mut rng).text)
+        .collect();
+    let structure = STRUCTURES[below(&mut rng, STRUCTURES.len() as u64) as usize];
+    let faction = SyndicateGenerator::default()
+        .generate(structure, &mut rng)
+        .text;
+    GmBundle {
+        people,
+        places,
+        taverns,
+        faction,
+    }
+}
+
+/// Grammar for tavern names.
+fn taverns() -> Grammar {
+    Grammar::default()
+        .rule(
+            "tavern",
+            &[
+                "The {adjective} {beast}",
+                "The {beast} and {object}",
+                "{name}'s Rest",
+                "The {adjective} {object}",
+            ],
+        )
+        .rule(
+            "adjective",
+            &[
+                "Drowsy", "Gilded", "Prancing", "Rusty", "Laughing", "Crooked", "Salty",
+            ],
+        )
+        .rule(
+            "beast",
+            &[
+                "Boar", "Pony", "Griffin", "Stag", "Dragon", "Goose", "Hound",
+            ],
+        )
+        .rule(
+            "object",
+            &["Tankard", "Anchor", "Lantern", "Crown", "Kettle", "Barrel"],
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::gm_bundle;
+
+    #[test]
+    fn test_gm_bundle() {
+        let bundle = gm_bundle(0);
+        assert_eq!(bundle.people.len(), 5);
+        assert_eq!(bundle.places.len(), 3);
+        assert_eq!(bundle.taverns.len(), 2);
+        assert!(!bundle.faction.is_empty() && !bundle.faction.contains('{'));
+        assert_eq!(bundle, gm_bundle(0));
+        assert_ne!(bundle, gm_bundle(1));
+        let text = bundle.to_string();
+        assert!(text.starts_with("People:\n  "));
+        assert!(text.contains(&format!("Faction:\n  {}\n", bundle.faction)));
+    }
+}
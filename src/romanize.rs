@@ -0,0 +1,230 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Romanization Variants
+
+use std::fmt;
+
+/// A sound with more than one common spelling, or a letter spelled the same in every scheme.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Sound {
+    Letter(char),
+    /// "k", "c" or "ck".
+    K,
+    /// "ks" or "x".
+    Ks,
+    /// "f" or "ph".
+    F,
+    /// "sh" or "sch".
+    Sh,
+    /// A "y" sound between a consonant and a vowel, spelled "y", "j" or "i".
+    Glide,
+}
+
+/// Spelling conventions a name can be romanized with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Scheme {
+    /// Plain phonetic spelling, used as the canonical form ("Katya", "Sasha", "Filip").
+    Plain,
+    /// Germanic spelling ("Katja", "Sascha", "Filip").
+    Germanic,
+    /// Latinate spelling ("Catia", "Sasha", "Philip").
+    Latinate,
+}
+
+impl Scheme {
+    /// Every scheme, in declaration order.
+    pub const ALL: [Scheme; 3] = [Scheme::Plain, Scheme::Germanic, Scheme::Latinate];
+}
+
+impl fmt::Display for Scheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Scheme::Plain => "plain",
+            Scheme::Germanic => "germanic",
+            Scheme::Latinate => "latinate",
+        })
+    }
+}
+
+/// Plausible spellings of one underlying name, for showing the same character's name the way
+/// different in-world documents would write it.
+///
+/// ```rust
+/// use nominae::{romanize, Scheme};
+///
+/// let katja = romanize("Katja");
+/// assert_eq!(katja.canonical(), "Katya");
+/// assert_eq!(katja.spelling(Scheme::Latinate), "Catia");
+/// assert_eq!(katja.variants().collect::<Vec<_>>(), vec!["Katya", "Katja", "Catia"]);
+/// assert_eq!(romanize("Catya").canonical(), "Katya");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Romanization {
+    spellings: Vec<(Scheme, String)>,
+}
+
+impl Romanization {
+    /// The canonical spelling, which is the same for every spelling of the name.
+    pub fn canonical(&self) -> &str {
+        self.spelling(Scheme::Plain)
+    }
+
+    /// The spelling under `scheme`.
+    pub fn spelling(&self, scheme: Scheme) -> &str {
+        self.spellings
+            .iter()
+            .find(|(other, _)| *other == scheme)
+            .map(|(_, spelling)| spelling.as_str())
+            .expect("every scheme is spelled")
+    }
+
+    /// Distinct spellings, starting with the canonical one.
+    pub fn variants(&self) -> impl Iterator<Item = &str> {
+        self.spellings
+            .iter()
+            .enumerate()
+            .filter(move |(idx, (_, spelling))| {
+                self.spellings[..*idx]
+                    .iter()
+                    .all(|(_, earlier)| earlier != spelling)
+            })
+            .map(|(_, (_, spelling))| spelling.as_str())
+    }
+}
+
+impl fmt::Display for Romanization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, variant) in self.variants().enumerate() {
+            if idx > 0 {
+                f.write_str("/")?;
+            }
+            f.write_str(variant)?;
+        }
+        Ok(())
+    }
+}
+
+/// Spell `name` in every [`Scheme`].
+///
+/// Letters are read as the sounds they most plausibly spell, so "Katja", "Katya" and "Catia"
+/// are read the same way, then written out again under each scheme's conventions. Letters
+/// without alternative spellings are kept as they are, and a capitalized name stays capitalized.
+pub fn romanize(name: &str) -> Romanization {
+    let capitalized = name.starts_with(char::is_uppercase);
+    let sounds = sounds(&name.to_lowercase());
+    let spellings = Scheme::ALL
+        .iter()
+        .map(|&scheme| {
+            let mut spelling = spell(&sounds, scheme);
+            if capitalized {
+                if let Some(first) = spelling.chars().next() {
+                    let upper: String = first.to_uppercase().collect();
+                    spelling.replace_range(..first.len_utf8(), &upper);
+                }
+            }
+            (scheme, spelling)
+        })
+        .collect();
+    Romanization { spellings }
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+fn is_front(c: Option<char>) -> bool {
+    matches!(c, Some('e') | Some('i') | Some('y'))
+}
+
+/// Read lowercase `text` as sounds.
+fn sounds(text: &str) -> Vec<Sound> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut sounds = Vec::with_capacity(chars.len());
+    let mut idx = 0;
+    while idx < chars.len() {
+        let at = |offset: usize| chars.get(idx + offset).copied();
+        let glide = idx
+            .checked_sub(1)
+            .is_some_and(|previous| chars[previous].is_alphabetic() && !is_vowel(chars[previous]));
+        let (sound, width) = match (chars[idx], at(1), at(2)) {
+            ('s', Some('c'), Some('h')) => (Sound::Sh, 3),
+            ('s', Some('h'), _) => (Sound::Sh, 2),
+            ('p', Some('h'), _) => (Sound::F, 2),
+            ('k', Some('s'), _) => (Sound::Ks, 2),
+            ('x', _, _) => (Sound::Ks, 1),
+            ('c', Some('k'), _) => (Sound::K, 2),
+            ('k', _, _) => (Sound::K, 1),
+            ('c', next, _) if !is_front(next) && next != Some('h') => (Sound::K, 1),
+            ('f', _, _) => (Sound::F, 1),
+            ('i', Some(next), _) | ('j', Some(next), _) | ('y', Some(next), _)
+                if glide && is_vowel(next) =>
+            {
+                (Sound::Glide, 1)
+            }
+            (c, _, _) => (Sound::Letter(c), 1),
+        };
+        sounds.push(sound);
+        idx += width;
+    }
+    sounds
+}
+
+/// Write `sounds` out under `scheme`.
+fn spell(sounds: &[Sound], scheme: Scheme) -> String {
+    let mut spelling = String::with_capacity(sounds.len() + 2);
+    for (idx, sound) in sounds.iter().enumerate() {
+        let next = match sounds.get(idx + 1) {
+            Some(Sound::Letter(c)) => Some(*c),
+            Some(Sound::Glide) => Some('i'),
+            _ => None,
+        };
+        match (sound, scheme) {
+            (Sound::Letter(c), _) => spelling.push(*c),
+            (Sound::K, Scheme::Latinate) if !is_front(next) => spelling.push('c'),
+            (Sound::K, _) => spelling.push('k'),
+            (Sound::Ks, Scheme::Plain) => spelling.push_str("ks"),
+            (Sound::Ks, _) => spelling.push('x'),
+            (Sound::F, Scheme::Latinate) => spelling.push_str("ph"),
+            (Sound::F, _) => spelling.push('f'),
+            (Sound::Sh, Scheme::Germanic) => spelling.push_str("sch"),
+            (Sound::Sh, _) => spelling.push_str("sh"),
+            (Sound::Glide, Scheme::Plain) => spelling.push('y'),
+            (Sound::Glide, Scheme::Germanic) => spelling.push('j'),
+            (Sound::Glide, Scheme::Latinate) => spelling.push('i'),
+        }
+    }
+    spelling
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{romanize, Scheme};
+
+    #[test]
+    fn test_romanize() {
+        for name in ["Katja", "Katya", "Catia"].iter() {
+            assert_eq!(romanize(name).to_string(), "Katya/Katja/Catia");
+        }
+        let sasha = romanize("Sascha");
+        assert_eq!(sasha.canonical(), "Sasha");
+        assert_eq!(sasha.spelling(Scheme::Germanic), "Sascha");
+        assert_eq!(romanize("Philip").canonical(), "Filip");
+        assert_eq!(romanize("Alex").to_string(), "Aleks/Alex");
+        assert_eq!(romanize("Cecil").canonical(), "Cecil");
+        assert_eq!(romanize("Doran").to_string(), "Doran");
+    }
+}
@@ -0,0 +1,261 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Weighted Token Sampling
+//!
+//! Tables are sampled through pools prebuilt for each position of a word, so the hot path
+//! neither rejects tokens that cannot be placed nor scans the table for every token. Weighted
+//! selection uses Walker's alias method with integer arithmetic only, which keeps it O(1) and
+//! independent of floating point behavior.
+
+use crate::table::{Table, AIW, BMW, MEW, MOW};
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use core::cell::OnceCell as OnceLock;
+use core::fmt;
+use rand::Rng;
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+
+/// One in fixed point: a bucket whose threshold is `ONE` always keeps its own entry.
+const ONE: u64 = 1 << 32;
+
+/// Walker's alias table over a list of weights.
+///
+/// Entries are labelled by caller-chosen `u32`s, so a pool can sample table indices directly.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Alias {
+    buckets: Vec<Bucket>,
+}
+
+/// One equally likely bucket of an [`Alias`] table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Bucket {
+    /// Chance out of [`ONE`] that the bucket yields `own` rather than `alias`.
+    threshold: u64,
+    own: u32,
+    alias: u32,
+}
+
+impl Alias {
+    /// Build an alias table over `(label, weight)` entries, or `None` if no weight is positive.
+    pub(crate) fn new(entries: &[(u32, u32)]) -> Option<Alias> {
+        let total: u128 = entries.iter().map(|&(_, weight)| weight as u128).sum();
+        if total == 0 {
+            return None;
+        }
+        let n = entries.len() as u128;
+        // Each bucket holds `total` units of probability; entry i brings `weight * n` of them.
+        let mut scaled: Vec<u128> = entries
+            .iter()
+            .map(|&(_, weight)| weight as u128 * n)
+            .collect();
+        let mut buckets: Vec<Bucket> = entries
+            .iter()
+            .map(|&(label, _)| Bucket {
+                threshold: ONE,
+                own: label,
+                alias: label,
+            })
+            .collect();
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) =
+            (0..entries.len()).partition(|&idx| scaled[idx] < total);
+        while let (Some(&under), Some(&over)) = (small.last(), large.last()) {
+            small.pop();
+            buckets[under].threshold = ((scaled[under] << 32) / total) as u64;
+            buckets[under].alias = entries[over].0;
+            scaled[over] -= total - scaled[under];
+            if scaled[over] < total {
+                large.pop();
+                small.push(over);
+            }
+        }
+        Some(Alias { buckets })
+    }
+
+    /// Select a label with probability proportional to its weight.
+    ///
+    /// A single 64-bit draw picks the bucket from its high half and decides between the bucket
+    /// and its alias with the low half.
+    #[inline]
+    pub(crate) fn sample<T: Rng + ?Sized>(&self, rng: &mut T) -> u32 {
+        let bits = rng.next_u64();
+        let bucket = &self.buckets[(((bits >> 32) * self.buckets.len() as u64) >> 32) as usize];
+        if bits & (ONE - 1) < bucket.threshold {
+            bucket.own
+        } else {
+            bucket.alias
+        }
+    }
+}
+
+/// Tokens of a table that may be placed at one position of a word.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Pool {
+    /// Indices of the pooled tokens in the table.
+    indices: Vec<u32>,
+    alias: Alias,
+}
+
+impl Pool {
+    fn new(table: &Table, position: u8) -> Option<Pool> {
+        let entries: Vec<(u32, u32)> = table
+            .tokens()
+            .iter()
+            .enumerate()
+            .filter(|(_, token)| token.allows(position))
+            .map(|(idx, token)| (idx as u32, token.weight()))
+            .collect();
+        let indices = entries.iter().map(|&(idx, _)| idx).collect();
+        Alias::new(&entries).map(|alias| Pool { indices, alias })
+    }
+
+    /// Indices of the pooled tokens in the table.
+    pub(crate) fn indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.indices.iter().map(|&idx| idx as usize)
+    }
+
+    /// Select the table index of a pooled token, weighted by token weight.
+    #[inline]
+    pub(crate) fn sample<T: Rng + ?Sized>(&self, rng: &mut T) -> usize {
+        self.alias.sample(rng) as usize
+    }
+}
+
+/// Pools for the beginning, middle and end of words, and for single-token words, indexed
+/// directly by position flags.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Pools {
+    pools: [Option<Pool>; 8],
+}
+
+impl Pools {
+    pub(crate) fn new(table: &Table) -> Pools {
+        let mut pools: [Option<Pool>; 8] = Default::default();
+        for &position in [BMW, MOW, MEW, AIW].iter() {
+            pools[position as usize] = Pool::new(table, position);
+        }
+        Pools { pools }
+    }
+
+    /// Pool for `position`, which must be `BMW`, `MOW`, `MEW` or `AIW`.
+    ///
+    /// Falls back to the beginning of words when no token can form a word on its own.
+    #[inline]
+    pub(crate) fn get(&self, position: u8) -> Option<&Pool> {
+        match self.pools[position as usize].as_ref() {
+            None if position == AIW => self.pools[BMW as usize].as_ref(),
+            pool => pool,
+        }
+    }
+
+    /// Pool of tokens that may form a word on their own, without any fallback.
+    #[inline]
+    pub(crate) fn alone(&self) -> Option<&Pool> {
+        self.pools[AIW as usize].as_ref()
+    }
+}
+
+/// Lazily built pools for a vowel and a consonant table.
+///
+/// The cache is derived data: it compares equal to any other cache and must be reset whenever
+/// either table changes.
+#[derive(Clone, Default)]
+pub(crate) struct Cache(OnceLock<(Pools, Pools)>);
+
+impl Cache {
+    /// Pools for `vowels` and `consonants`, building them on first use.
+    pub(crate) fn get(&self, vowels: &Table, consonants: &Table) -> &(Pools, Pools) {
+        self.0
+            .get_or_init(|| (Pools::new(vowels), Pools::new(consonants)))
+    }
+}
+
+impl PartialEq for Cache {
+    fn eq(&self, _: &Cache) -> bool {
+        true
+    }
+}
+
+impl fmt::Debug for Cache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Cache")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Alias, Pools};
+    use crate::table::{Table, Token, AIW, BMW, BOW, EOW, MEW, MOW};
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_alias() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let alias = Alias::new(&[(0, 1), (1, 0), (2, 3), (3, 6)]).unwrap();
+        let mut counts = [0usize; 4];
+        for _ in 0..100_000 {
+            counts[alias.sample(&mut rng) as usize] += 1;
+        }
+        assert_eq!(counts[1], 0);
+        for (&count, &expected) in counts.iter().zip([10_000, 0, 30_000, 60_000].iter()) {
+            assert!(
+                (count as i64 - expected as i64).abs() < 1_500,
+                "{:?}",
+                counts
+            );
+        }
+        let skewed: Vec<(u32, u32)> = (0..5_000).map(|idx| (idx, 5_000 / (idx + 1))).collect();
+        let alias = Alias::new(&skewed).unwrap();
+        let mut counts = [0usize; 2];
+        for _ in 0..100_000 {
+            match alias.sample(&mut rng) {
+                0 => counts[0] += 1,
+                4_999 => counts[1] += 1,
+                _ => {}
+            }
+        }
+        let total: usize = skewed.iter().map(|&(_, weight)| weight as usize).sum();
+        let expected = 100_000 * 5_000 / total;
+        assert!(
+            (counts[0] as i64 - expected as i64).abs() < 500,
+            "{:?}",
+            counts
+        );
+        assert!(counts[1] < 20, "{:?}", counts);
+        assert!(Alias::new(&[]).is_none());
+        assert!(Alias::new(&[(0, 0), (1, 0)]).is_none());
+    }
+
+    #[test]
+    fn test_pools() {
+        let table = Table::new(vec![
+            Token::new("a", BOW | MOW),
+            Token::new("e", MOW | EOW),
+            Token::new("o", EOW),
+        ])
+        .unwrap();
+        let pools = Pools::new(&table);
+        let indices = |position| pools.get(position).unwrap().indices().collect::<Vec<_>>();
+        // "o" never allows the middle of a word, so it is never drawn.
+        assert_eq!(indices(BMW), vec![0]);
+        assert_eq!(indices(MOW), vec![0, 1]);
+        assert_eq!(indices(MEW), vec![1]);
+        assert_eq!(indices(AIW), vec![0]);
+        assert!(pools.alone().is_none());
+    }
+}
@@ -0,0 +1,301 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Name Screening
+//!
+//! A [`Screen`] holds the rules a name must pass before it is shown to players: blocked words
+//! it may not contain, reserved names it may not be, protected names it may not resemble, and
+//! the forbidden beginnings and endings of a [`TotroConfig`]. The same screen checks names as
+//! they are generated and audits lists of names that have already shipped.
+
+use crate::evaluation::closeness;
+use crate::normalize::normalize;
+use crate::totro::TotroConfig;
+use std::fmt;
+
+/// A rule a name broke.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Violation {
+    /// The name contains a blocked word.
+    Blocked(String),
+    /// The name is a reserved name.
+    Reserved(String),
+    /// The name closely resembles a protected name, with closeness from 0 to 1.
+    Similar {
+        /// The protected name it resembles.
+        name: String,
+        /// One minus the edit distance between the normalized names over the longer length.
+        closeness: f64,
+    },
+    /// The name begins with a forbidden string.
+    ForbiddenBeginning(String),
+    /// The name ends with a forbidden string.
+    ForbiddenEnding(String),
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::Blocked(word) => write!(f, "contains blocked word {:?}", word),
+            Violation::Reserved(name) => write!(f, "is reserved as {:?}", name),
+            Violation::Similar { name, closeness } => {
+                write!(f, "resembles {:?} ({:.0}% alike)", name, closeness * 100.0)
+            }
+            Violation::ForbiddenBeginning(text) => write!(f, "begins with {:?}", text),
+            Violation::ForbiddenEnding(text) => write!(f, "ends with {:?}", text),
+        }
+    }
+}
+
+/// A name in a [`Report`] along with every rule it broke.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Flagged {
+    /// Position of the name in the checked list.
+    pub index: usize,
+    /// The name as it appeared in the list.
+    pub name: String,
+    /// Rules the name broke, in the order they were checked.
+    pub violations: Vec<Violation>,
+}
+
+/// Result of screening a list of names.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Report {
+    /// Number of names checked.
+    pub checked: usize,
+    /// Names that broke at least one rule, in list order.
+    pub flagged: Vec<Flagged>,
+}
+
+impl Report {
+    /// Whether every name passed.
+    pub fn is_clean(&self) -> bool {
+        self.flagged.is_empty()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} names flagged",
+            self.flagged.len(),
+            self.checked
+        )?;
+        for flagged in &self.flagged {
+            let reasons: Vec<String> = flagged.violations.iter().map(ToString::to_string).collect();
+            write!(
+                f,
+                "\n  {}: {} {}",
+                flagged.index,
+                flagged.name,
+                reasons.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Rules for screening names, compared on their [`normalize`]d forms so that spacing, casing
+/// and accents cannot slip a name past them.
+///
+/// ```rust
+/// use nominae::{Screen, TotroConfig};
+///
+/// let screen = Screen::default()
+///     .with_blocked(&["darn"])
+///     .with_reserved(&["Admin"])
+///     .with_protected(&["Velmar"], 0.8)
+///     .with_config(&TotroConfig::default().with_forbidden_endings(vec!["q"]));
+/// let report = screen.report(&["Essa", "Darnell", "ADMIN", "Velmor", "Iraq"]);
+/// assert_eq!(report.flagged.len(), 4);
+/// println!("{}", report);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Screen {
+    blocked: Vec<String>,
+    reserved: Vec<String>,
+    protected: Vec<(String, Vec<char>)>,
+    closeness: f64,
+    beginnings: Vec<String>,
+    endings: Vec<String>,
+}
+
+impl Screen {
+    /// Flag names containing any of `words`.
+    pub fn with_blocked<S: AsRef<str>>(mut self, words: &[S]) -> Screen {
+        self.blocked
+            .extend(words.iter().map(|word| normalize(word.as_ref())));
+        self.blocked.retain(|word| !word.is_empty());
+        self
+    }
+
+    /// Flag names containing any word of the built-in blocklist of English profanity and slurs,
+    /// for names that will be shown to players.
+    #[cfg(feature = "blocklist")]
+    pub fn with_builtin_blocklist(self) -> Screen {
+        self.with_blocked(&crate::blocklist::WORDS)
+    }
+
+    /// Flag names that are any of `names`.
+    pub fn with_reserved<S: AsRef<str>>(mut self, names: &[S]) -> Screen {
+        self.reserved
+            .extend(names.iter().map(|name| normalize(name.as_ref())));
+        self
+    }
+
+    /// Flag names at least `closeness` alike to any of `names` without being the same name,
+    /// such as near-copies of existing characters.
+    pub fn with_protected<S: AsRef<str>>(mut self, names: &[S], closeness: f64) -> Screen {
+        assert!(
+            (0.0..=1.0).contains(&closeness),
+            "closeness must be within 0..=1"
+        );
+        self.protected.extend(names.iter().map(|name| {
+            let name = name.as_ref().to_owned();
+            let chars = normalize(&name).chars().collect();
+            (name, chars)
+        }));
+        self.closeness = closeness;
+        self
+    }
+
+    /// Flag names breaking the forbidden beginnings and endings of `config`.
+    pub fn with_config(mut self, config: &TotroConfig) -> Screen {
+        self.beginnings
+            .extend(config.forbidden_beginnings().iter().cloned());
+        self.endings
+            .extend(config.forbidden_endings().iter().cloned());
+        self
+    }
+
+    /// Every rule `name` breaks.
+    pub fn check(&self, name: &str) -> Vec<Violation> {
+        let normalized = normalize(name);
+        let mut violations = Vec::new();
+        for word in &self.blocked {
+            if normalized.contains(word.as_str()) {
+                violations.push(Violation::Blocked(word.clone()));
+            }
+        }
+        if let Some(reserved) = self
+            .reserved
+            .iter()
+            .find(|&reserved| *reserved == normalized)
+        {
+            violations.push(Violation::Reserved(reserved.clone()));
+        }
+        let chars: Vec<char> = normalized.chars().collect();
+        for (protected, protected_chars) in &self.protected {
+            let alike = closeness(&chars, protected_chars);
+            if alike >= self.closeness && alike < 1.0 {
+                violations.push(Violation::Similar {
+                    name: protected.clone(),
+                    closeness: alike,
+                });
+            }
+        }
+        let lower = name.to_lowercase();
+        for beginning in &self.beginnings {
+            if lower.starts_with(beginning.as_str()) {
+                violations.push(Violation::ForbiddenBeginning(beginning.clone()));
+            }
+        }
+        for ending in &self.endings {
+            if lower.ends_with(ending.as_str()) {
+                violations.push(Violation::ForbiddenEnding(ending.clone()));
+            }
+        }
+        violations
+    }
+
+    /// Whether `name` breaks no rule.
+    pub fn passes(&self, name: &str) -> bool {
+        self.check(name).is_empty()
+    }
+
+    /// Check every name in `names`, such as previously shipped content.
+    pub fn report<S: AsRef<str>>(&self, names: &[S]) -> Report {
+        let flagged = names
+            .iter()
+            .enumerate()
+            .filter_map(|(index, name)| {
+                let violations = self.check(name.as_ref());
+                if violations.is_empty() {
+                    return None;
+                }
+                Some(Flagged {
+                    index,
+                    name: name.as_ref().to_owned(),
+                    violations,
+                })
+            })
+            .collect();
+        Report {
+            checked: names.len(),
+            flagged,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Screen, Violation};
+
+    #[test]
+    fn test_check() {
+        let screen = Screen::default()
+            .with_blocked(&["Darn"])
+            .with_reserved(&["Game Master"])
+            .with_protected(&["Velmar"], 0.8);
+        assert_eq!(
+            screen.check("D'arnell"),
+            vec![Violation::Blocked("darn".into())]
+        );
+        assert_eq!(
+            screen.check("gamemaster"),
+            vec![Violation::Reserved("gamemaster".into())]
+        );
+        assert!(matches!(
+            screen.check("Velmor").as_slice(),
+            [Violation::Similar { name, .. }] if name == "Velmar"
+        ));
+        assert!(screen.passes("Velmar"));
+        assert!(screen.passes("Essa"));
+    }
+
+    #[test]
+    fn test_report() {
+        let screen = Screen::default().with_blocked(&["darn"]);
+        let report = screen.report(&["Essa", "Darnell", "Maren"]);
+        assert_eq!(report.checked, 3);
+        assert_eq!(report.flagged.len(), 1);
+        assert_eq!(report.flagged[0].index, 1);
+        assert_eq!(
+            report.to_string(),
+            "1 of 3 names flagged\n  1: Darnell contains blocked word \"darn\""
+        );
+        assert!(Screen::default().report(&["Darnell"]).is_clean());
+    }
+
+    #[cfg(feature = "blocklist")]
+    #[test]
+    fn test_builtin_blocklist() {
+        let screen = Screen::default().with_builtin_blocklist();
+        assert!(!screen.passes("Shitaro"));
+        assert!(screen.passes("Velmar"));
+    }
+}
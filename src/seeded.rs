@@ -0,0 +1,133 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Seeded Generator Hierarchies
+
+use crate::generator::NameGenerator;
+use crate::stable::{mix, StableRng};
+use alloc::string::String;
+use rand::SeedableRng;
+
+/// A name generator bound to its own seed and random stream.
+///
+/// [`derive`](Seeded::derive) makes a child with a seed computed from the parent's seed and a
+/// key, never from the parent's stream, so each level of a hierarchy such as galaxy, system,
+/// planet and city gets a reproducible stream no matter how many names its parent has drawn,
+/// and siblings with different keys get uncorrelated streams.
+///
+/// ```rust
+/// use nominae::{Seeded, TotroConfig};
+///
+/// let galaxy = Seeded::new(TotroConfig::default(), 42);
+/// let mut system = galaxy.derive("system-7");
+/// let mut planet = system.derive("planet-3");
+/// println!("{} orbits {}", planet.generate(), system.generate());
+/// assert_eq!(planet.seed(), galaxy.derive("system-7").derive("planet-3").seed());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Seeded<G> {
+    generator: G,
+    seed: u64,
+    rng: StableRng,
+}
+
+impl<G: NameGenerator> Seeded<G> {
+    /// Bind `generator` to a stream starting from `seed`.
+    pub fn new(generator: G, seed: u64) -> Seeded<G> {
+        Seeded {
+            generator,
+            seed,
+            rng: StableRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Seed this generator's stream started from.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The underlying generator.
+    pub fn generator(&self) -> &G {
+        &self.generator
+    }
+
+    /// Random stream used by [`generate`](Seeded::generate), for other draws at the same level.
+    pub fn rng(&mut self) -> &mut StableRng {
+        &mut self.rng
+    }
+
+    /// Independent child generator for `child_key`, starting from a fresh stream.
+    pub fn derive(&self, child_key: &str) -> Seeded<G>
+    where
+        G: Clone,
+    {
+        self.derive_with(child_key, self.generator.clone())
+    }
+
+    /// Independent child for `child_key` using a different generator, such as a city name
+    /// generator below a planet name generator.
+    pub fn derive_with<H: NameGenerator>(&self, child_key: &str, generator: H) -> Seeded<H> {
+        Seeded::new(generator, derive_seed(self.seed, child_key))
+    }
+
+    /// Generate the next name in this generator's stream.
+    pub fn generate(&mut self) -> String {
+        self.generator.generate(&mut self.rng)
+    }
+}
+
+impl<G: NameGenerator> Iterator for Seeded<G> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        Some(self.generate())
+    }
+}
+
+/// Seed of the child of `seed` named `key`.
+///
+/// The key is folded one byte at a time through the SplitMix64 finalizer, starting from the
+/// parent seed, and finished with its length, so keys that are prefixes of one another differ.
+fn derive_seed(seed: u64, key: &str) -> u64 {
+    let folded = key
+        .bytes()
+        .fold(mix(seed ^ 0x6A09_E667_F3BC_C908), |h, byte| {
+            mix(h.wrapping_add(0x9E37_79B9_7F4A_7C15) ^ u64::from(byte))
+        });
+    mix(folded ^ key.len() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Seeded;
+    use crate::totro::TotroConfig;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_derive() {
+        let mut galaxy = Seeded::new(TotroConfig::default(), 7);
+        let before: Vec<_> = galaxy.derive("system-1").take(3).collect();
+        galaxy.generate();
+        let after: Vec<_> = galaxy.derive("system-1").take(3).collect();
+        assert_eq!(before, after);
+        let sibling: Vec<_> = galaxy.derive("system-2").take(3).collect();
+        assert_ne!(before, sibling);
+        assert_ne!(galaxy.derive("a").seed(), galaxy.derive("a\0").seed());
+        assert_ne!(galaxy.derive("").seed(), galaxy.seed());
+        let planet = galaxy.derive("system-1").derive("planet-1");
+        assert_ne!(planet.seed(), galaxy.derive("planet-1").seed());
+    }
+}
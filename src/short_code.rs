@@ -0,0 +1,153 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Short Codes
+
+use crate::name::Name;
+use crate::normalize::fold_letters;
+use std::collections::HashSet;
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y')
+}
+
+/// Letters of `text` folded to uppercase ASCII.
+fn letters(text: &str) -> Vec<char> {
+    fold_letters(text)
+        .chars()
+        .filter(char::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Positions of `letters` from most to least preferred in a code: the first letter, then
+/// consonants opening a syllable, then other consonants, then vowels.
+fn ranked(letters: &[char]) -> Vec<usize> {
+    let vowel = |idx: usize| is_vowel(letters[idx].to_ascii_lowercase());
+    let onset = |idx: usize| !vowel(idx) && idx + 1 < letters.len() && vowel(idx + 1);
+    let mut positions: Vec<usize> = (1..letters.len()).collect();
+    positions.sort_by_key(|&idx| match (onset(idx), vowel(idx)) {
+        (true, _) => 0,
+        (false, false) => 1,
+        (false, true) => 2,
+    });
+    if !letters.is_empty() {
+        positions.insert(0, 0);
+    }
+    positions
+}
+
+/// Codes for `text` of up to `len` letters, from most to least preferred.
+///
+/// The preferred code is followed by codes varying its last letter, then codes ending in a
+/// counter, which never run out.
+fn candidates(text: &str, len: usize) -> impl Iterator<Item = String> {
+    let letters = letters(text);
+    let ranked = ranked(&letters);
+    let keep = len.min(ranked.len()).saturating_sub(1);
+    let mut lettered: Vec<String> = (keep..ranked.len())
+        .map(|last| {
+            let mut positions: Vec<usize> = ranked[..keep].to_vec();
+            positions.push(ranked[last]);
+            positions.sort_unstable();
+            positions.iter().map(|&idx| letters[idx]).collect()
+        })
+        .collect();
+    if lettered.is_empty() {
+        lettered.push(String::new());
+    }
+    let stem = lettered[0].clone();
+    let counted = (2u64..).map(move |counter| {
+        let counter = counter.to_string();
+        let mut stem = stem.clone();
+        stem.truncate(len.saturating_sub(counter.len()));
+        format!("{}{}", stem, counter)
+    });
+    lettered.into_iter().chain(counted)
+}
+
+impl Name {
+    /// A deterministic abbreviation of up to `len` uppercase letters for map markers and
+    /// roster tables, such as "VLM" for "Velmar".
+    ///
+    /// Codes keep the first letter and otherwise favor consonants that open a syllable, keeping
+    /// the letters in the order they are written.
+    pub fn short_code(&self, len: usize) -> String {
+        candidates(self.text(), len)
+            .next()
+            .expect("there is always a preferred code")
+    }
+
+    /// Like [`Name::short_code`], but varying the code until it is not in `taken`.
+    ///
+    /// Alternatives first swap the last letter for another from the name, then end in a number,
+    /// as in "VMR" or "VL2" for "Velmar".
+    pub fn short_code_among(&self, len: usize, taken: &HashSet<String>) -> String {
+        candidates(self.text(), len)
+            .find(|code| !taken.contains(code))
+            .expect("numbered codes never run out")
+    }
+}
+
+/// Distinct short codes for each of `names`, in order, as assigned by
+/// [`Name::short_code_among`].
+///
+/// ```rust
+/// use nominae::short_codes;
+///
+/// let codes = short_codes(&["Velmar", "Velmira", "Ashport"], 3);
+/// assert_eq!(codes, vec!["VLM", "VMR", "ASP"]);
+/// ```
+pub fn short_codes<S: AsRef<str>>(names: &[S], len: usize) -> Vec<String> {
+    let mut taken = HashSet::with_capacity(names.len());
+    names
+        .iter()
+        .map(|name| {
+            let code = Name::new(name.as_ref()).short_code_among(len, &taken);
+            taken.insert(code.clone());
+            code
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::short_codes;
+    use crate::name::Name;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_short_code() {
+        assert_eq!(Name::new("Velmar").short_code(3), "VLM");
+        assert_eq!(Name::new("D'Vára").short_code(2), "DV");
+        assert_eq!(Name::new("Io").short_code(3), "IO");
+        assert_eq!(Name::new("'").short_code(3), "");
+        let mut taken: HashSet<String> = ["VLM"].iter().map(|&code| code.into()).collect();
+        assert_eq!(Name::new("Velmar").short_code_among(3, &taken), "VMR");
+        taken.extend(["VMR", "VEM", "VMA"].iter().map(|&code| code.to_owned()));
+        assert_eq!(Name::new("Velmar").short_code_among(3, &taken), "VL2");
+    }
+
+    #[test]
+    fn test_short_codes() {
+        let names = vec!["Ka"; 12];
+        let codes = short_codes(&names, 2);
+        let distinct: HashSet<&String> = codes.iter().collect();
+        assert_eq!(distinct.len(), 12);
+        assert_eq!(&codes[..3], ["KA", "K2", "K3"]);
+        assert_eq!(codes[9], "10");
+    }
+}
@@ -0,0 +1,219 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Matched Sibling Name Generation
+
+use crate::normalize::eq_normalized;
+use crate::totro::TotroConfig;
+use rand::Rng;
+
+/// Number of candidates drawn per sibling before falling back to splicing the shared feature.
+const SIBLING_ATTEMPTS: usize = 2000;
+
+/// Stylistic feature shared by a set of sibling names.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Similarity {
+    /// Siblings start with the same letter ("Mara", "Milo", "Mendi").
+    Alliteration,
+    /// Siblings share their first `n` letters ("Velra", "Veldan", "Velis"), or the whole first
+    /// name when it is shorter than `n`.
+    Prefix(usize),
+    /// Siblings share their last `n` letters ("Kaden", "Toden", "Araden"), or the whole first
+    /// name when it is shorter than `n`.
+    Rhyme(usize),
+}
+
+impl Similarity {
+    /// Check whether `candidate` shares this feature with `root`.
+    pub fn matches(&self, root: &str, candidate: &str) -> bool {
+        let root = root.to_lowercase();
+        let candidate = candidate.to_lowercase();
+        let len = root.chars().count();
+        match *self {
+            Similarity::Alliteration => root.chars().next() == candidate.chars().next(),
+            Similarity::Prefix(n) => {
+                let n = n.min(len);
+                candidate.chars().count() >= n && root.chars().take(n).eq(candidate.chars().take(n))
+            }
+            Similarity::Rhyme(n) => {
+                let n = n.min(len);
+                candidate.chars().count() >= n
+                    && root
+                        .chars()
+                        .rev()
+                        .take(n)
+                        .eq(candidate.chars().rev().take(n))
+            }
+        }
+    }
+
+    /// Force `candidate` to share this feature with `root` by splicing it in.
+    fn splice(&self, root: &str, candidate: &str) -> String {
+        let root: Vec<char> = root.to_lowercase().chars().collect();
+        let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+        let len = root.len();
+        let spliced: String = match *self {
+            Similarity::Alliteration => root
+                .iter()
+                .take(1)
+                .chain(candidate.iter().skip(1))
+                .collect(),
+            Similarity::Prefix(n) => {
+                let n = n.min(len);
+                root.iter()
+                    .take(n)
+                    .chain(candidate.iter().skip(n))
+                    .collect()
+            }
+            Similarity::Rhyme(n) => {
+                let n = n.min(len);
+                let keep = candidate.len().saturating_sub(n);
+                candidate[..keep]
+                    .iter()
+                    .chain(root[len - n..].iter())
+                    .collect()
+            }
+        };
+        capitalize(spliced)
+    }
+}
+
+impl TotroConfig {
+    /// Generate `n` distinct sibling names sharing the feature described by `similarity`.
+    ///
+    /// The first name is generated freely and the rest are drawn until they share its feature.
+    /// If no such name turns up within a bounded number of attempts, the feature is spliced onto
+    /// a fresh name instead so that the call always terminates. Fewer names are returned if
+    /// `self` cannot produce `n` distinct names.
+    ///
+    /// ```rust
+    /// use nominae::{Similarity, TotroConfig};
+    /// use rand::SeedableRng;
+    /// use rand::rngs::SmallRng;
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    /// let siblings = TotroConfig::default().generate_siblings(3, Similarity::Rhyme(2), &mut rng);
+    /// assert_eq!(siblings.len(), 3);
+    /// ```
+    pub fn generate_siblings<T: Rng + ?Sized>(
+        &self,
+        n: usize,
+        similarity: Similarity,
+        rng: &mut T,
+    ) -> Vec<String> {
+        let mut siblings: Vec<String> = Vec::with_capacity(n);
+        if n == 0 {
+            return siblings;
+        }
+        siblings.push(self.generate(rng));
+        let mut rounds = n.saturating_mul(10);
+        while siblings.len() < n && rounds > 0 {
+            rounds -= 1;
+            let root = &siblings[0];
+            let fresh =
+                |candidate: &String| !siblings.iter().any(|other| eq_normalized(other, candidate));
+            let found = (0..SIBLING_ATTEMPTS)
+                .map(|_| self.generate(rng))
+                .find(|candidate| fresh(candidate) && similarity.matches(root, candidate));
+            let sibling = match found {
+                Some(sibling) => sibling,
+                None => {
+                    event!(
+                        debug,
+                        attempts = SIBLING_ATTEMPTS,
+                        ?similarity,
+                        "no candidate matched, splicing"
+                    );
+                    similarity.splice(root, &self.generate(rng))
+                }
+            };
+            if fresh(&sibling) {
+                siblings.push(sibling);
+            }
+        }
+        if siblings.len() < n {
+            event!(
+                warn,
+                wanted = n,
+                found = siblings.len(),
+                "ran out of siblings"
+            );
+        }
+        siblings
+    }
+}
+
+fn capitalize(mut name: String) -> String {
+    if let Some(first) = name.get_mut(0..1) {
+        first.make_ascii_uppercase();
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Similarity;
+    use crate::TotroConfig;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_matches() {
+        assert!(Similarity::Alliteration.matches("Mara", "milo"));
+        assert!(Similarity::Prefix(3).matches("Velra", "Veldan"));
+        assert!(!Similarity::Prefix(3).matches("Velra", "Vorin"));
+        assert!(Similarity::Rhyme(3).matches("Kaden", "Araden"));
+        // A root shorter than `n` must be shared whole.
+        assert!(Similarity::Rhyme(4).matches("Den", "Aden"));
+        assert!(!Similarity::Rhyme(4).matches("Den", "En"));
+        assert!(Similarity::Prefix(5).matches("Ka", "Kaden"));
+        assert_eq!(Similarity::Prefix(5).splice("Ka", "T"), "Ka");
+        assert_eq!(Similarity::Rhyme(2).splice("Kaden", "Toral"), "Toren");
+    }
+
+    #[test]
+    fn test_siblings() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let config = TotroConfig::default();
+        for &similarity in [
+            Similarity::Alliteration,
+            Similarity::Prefix(2),
+            Similarity::Rhyme(2),
+        ]
+        .iter()
+        {
+            let siblings = config.generate_siblings(4, similarity, &mut rng);
+            assert_eq!(siblings.len(), 4);
+            for sibling in &siblings[1..] {
+                assert!(similarity.matches(&siblings[0], sibling), "{:?}", siblings);
+                assert_eq!(siblings.iter().filter(|other| *other == sibling).count(), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_short_root() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let config = TotroConfig::default().with_length(2, 2);
+        for &similarity in [Similarity::Prefix(8), Similarity::Rhyme(8)].iter() {
+            let siblings = config.generate_siblings(3, similarity, &mut rng);
+            assert!(siblings[0].chars().count() < 8, "{:?}", siblings);
+            for sibling in &siblings[1..] {
+                assert!(similarity.matches(&siblings[0], sibling), "{:?}", siblings);
+            }
+        }
+    }
+}
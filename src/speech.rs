@@ -0,0 +1,218 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Syllabified Output for Speech
+
+use crate::table::{Attribute, Token};
+use crate::totro::TotroConfig;
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::fmt;
+use rand::Rng;
+
+/// Key of the [`Attribute::Text`] giving a token's pronunciation in IPA, such as `"ʃ"` for
+/// "sh".
+pub const IPA_ATTRIBUTE: &str = "ipa";
+
+/// A generated name split into syllables, for dialogue and text-to-speech pipelines.
+///
+/// Each syllable holds one vowel token. A consonant token between two vowels starts the next
+/// syllable, and of a longer run only the last one does, so "Velmara" splits as "Vel-ma-ra".
+///
+/// ```rust
+/// use nominae::{Table, Token, TotroConfig, AIW, IPA_ATTRIBUTE};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let vowels = Table::new(vec![
+///     Token::new("a", AIW).with_attribute(IPA_ATTRIBUTE, "a"),
+///     Token::new("ei", AIW).with_attribute(IPA_ATTRIBUTE, "eɪ"),
+/// ])
+/// .unwrap();
+/// let consonants = Table::new(vec![
+///     Token::new("th", AIW).with_attribute(IPA_ATTRIBUTE, "θ"),
+///     Token::new("v", AIW).with_attribute(IPA_ATTRIBUTE, "v"),
+/// ])
+/// .unwrap();
+/// let config = TotroConfig::new(vowels, consonants).with_length(3, 5);
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let name = config.generate_syllabified(&mut rng);
+/// println!("{} is said {}", name, name.ssml().unwrap());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Syllabified {
+    syllables: Vec<String>,
+    ipa: Option<String>,
+}
+
+impl Syllabified {
+    /// Split `name`, assembled from `tokens` flagged by whether each is a vowel, into
+    /// syllables.
+    pub(crate) fn split(name: &str, tokens: &[(&Token, bool)]) -> Syllabified {
+        let vowels: Vec<usize> = (0..tokens.len()).filter(|&idx| tokens[idx].1).collect();
+        let mut starts = vec![0];
+        for pair in vowels.windows(2) {
+            starts.push(if pair[1] - pair[0] > 1 {
+                pair[1] - 1
+            } else {
+                pair[1]
+            });
+        }
+        starts.push(tokens.len());
+        let offset = |end: usize| -> usize {
+            tokens[..end]
+                .iter()
+                .map(|(token, _)| token.text().len())
+                .sum()
+        };
+        let syllables = starts
+            .windows(2)
+            .map(|range| String::from(&name[offset(range[0])..offset(range[1])]))
+            .filter(|syllable| !syllable.is_empty())
+            .collect();
+        let ipa = starts
+            .windows(2)
+            .map(|range| {
+                tokens[range[0]..range[1]]
+                    .iter()
+                    .map(|(token, _)| match token.attribute(IPA_ATTRIBUTE) {
+                        Some(Attribute::Text(ipa)) => Some(ipa.as_str()),
+                        _ => None,
+                    })
+                    .collect::<Option<String>>()
+            })
+            .collect::<Option<Vec<String>>>()
+            .filter(|_| !tokens.is_empty())
+            .map(|syllables| syllables.join("."));
+        Syllabified { syllables, ipa }
+    }
+
+    /// The syllables in order, the first capitalized as in the name.
+    pub fn syllables(&self) -> &[String] {
+        &self.syllables
+    }
+
+    /// The whole name.
+    pub fn name(&self) -> String {
+        self.syllables.concat()
+    }
+
+    /// The name with its syllables separated by hyphens, such as "Vel-ma-ra".
+    pub fn hyphenated(&self) -> String {
+        self.syllables.join("-")
+    }
+
+    /// IPA transcription with syllables separated by ".", when every token of the name has an
+    /// [`IPA_ATTRIBUTE`].
+    pub fn ipa(&self) -> Option<&str> {
+        self.ipa.as_deref()
+    }
+
+    /// SSML `phoneme` element telling a speech engine how to say the name, when an
+    /// [IPA transcription](Syllabified::ipa) is known.
+    pub fn ssml(&self) -> Option<String> {
+        let ipa = self.ipa.as_ref()?;
+        Some(format!(
+            "<phoneme alphabet=\"ipa\" ph=\"{}\">{}</phoneme>",
+            escape(ipa),
+            escape(&self.name())
+        ))
+    }
+}
+
+/// Displays the [hyphenated](Syllabified::hyphenated) name.
+impl fmt::Display for Syllabified {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.hyphenated())
+    }
+}
+
+impl TotroConfig {
+    /// Generate a name split into syllables, with an IPA transcription when the tables carry
+    /// [`IPA_ATTRIBUTE`]s.
+    pub fn generate_syllabified<T: Rng + ?Sized>(&self, rng: &mut T) -> Syllabified {
+        let explanation = self.explain(rng);
+        let vowels = self.vowels().tokens();
+        let tokens: Vec<(&Token, bool)> = explanation
+            .tokens()
+            .iter()
+            .map(|&token| {
+                let vowel = vowels.iter().any(|vowel| core::ptr::eq(vowel, token));
+                (token, vowel)
+            })
+            .collect();
+        Syllabified::split(explanation.name(), &tokens)
+    }
+}
+
+/// Escape `text` for use in XML text and attribute values.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Syllabified, IPA_ATTRIBUTE};
+    use crate::table::{Token, AIW};
+
+    #[test]
+    fn test_split() {
+        let token = |text: &'static str, ipa: &'static str| {
+            Token::new(text, AIW).with_attribute(IPA_ATTRIBUTE, ipa)
+        };
+        let (v, e, l, m, a, r) = (
+            token("v", "v"),
+            token("e", "ɛ"),
+            token("l", "l"),
+            token("m", "m"),
+            token("a", "ɑ"),
+            token("r", "ɹ"),
+        );
+        let tokens = [
+            (&v, false),
+            (&e, true),
+            (&l, false),
+            (&m, false),
+            (&a, true),
+            (&r, false),
+            (&a, true),
+        ];
+        let name = Syllabified::split("Velmara", &tokens);
+        assert_eq!(name.to_string(), "Vel-ma-ra");
+        assert_eq!(name.ipa(), Some("vɛl.mɑ.ɹɑ"));
+        assert_eq!(
+            name.ssml().unwrap(),
+            "<phoneme alphabet=\"ipa\" ph=\"vɛl.mɑ.ɹɑ\">Velmara</phoneme>"
+        );
+        let plain = Token::new("'", AIW);
+        let tokens = [(&e, true), (&plain, false), (&a, true)];
+        let name = Syllabified::split("E'a", &tokens);
+        assert_eq!(name.syllables(), ["E", "'a"]);
+        assert_eq!((name.ipa(), name.ssml()), (None, None));
+    }
+}
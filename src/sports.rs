@@ -0,0 +1,196 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Sports Team and League Name Generation
+
+use crate::totro::TotroConfig;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::fmt;
+
+const MASCOTS: [&str; 30] = [
+    "Bears", "Bulls", "Comets", "Dragons", "Eagles", "Falcons", "Foxes", "Griffins", "Hawks",
+    "Hounds", "Knights", "Lancers", "Lions", "Lynx", "Mariners", "Miners", "Owls", "Panthers",
+    "Pioneers", "Raiders", "Ravens", "Rams", "Sabres", "Stags", "Storm", "Titans", "Vipers",
+    "Warriors", "Wolves", "Wyverns",
+];
+
+/// Cities generated per team before a league gives up on finding a new one.
+const CITY_ATTEMPTS: usize = 1000;
+
+/// A generated sports team, displayed as "Velmar Vipers".
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Team {
+    /// Home city.
+    pub city: String,
+    /// Plural mascot, such as "Vipers".
+    pub mascot: String,
+}
+
+impl fmt::Display for Team {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.city, self.mascot)
+    }
+}
+
+/// Generator for [`Team`] names and whole leagues of them.
+///
+/// Mascots are chosen to alliterate with the city when possible, with a configurable
+/// preference. Within a league no two teams share a city or a mascot.
+///
+/// ```rust
+/// use nominae::LeagueGenerator;
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// for team in LeagueGenerator::default().league(8, &mut rng) {
+///     println!("{}", team);
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct LeagueGenerator {
+    cities: TotroConfig,
+    mascots: Vec<String>,
+    alliteration: f64,
+}
+
+impl Default for LeagueGenerator {
+    fn default() -> LeagueGenerator {
+        LeagueGenerator {
+            cities: TotroConfig::alphabetic().with_length(2, 4),
+            mascots: MASCOTS.iter().map(|&mascot| mascot.to_owned()).collect(),
+            alliteration: 0.7,
+        }
+    }
+}
+
+impl LeagueGenerator {
+    /// Set the configuration used to generate city names.
+    pub fn with_cities(mut self, cities: TotroConfig) -> LeagueGenerator {
+        self.cities = cities;
+        self
+    }
+
+    /// Replace the mascot pool.
+    pub fn with_mascots<S: Into<String>>(mut self, mascots: Vec<S>) -> LeagueGenerator {
+        assert!(!mascots.is_empty(), "at least one mascot is required");
+        self.mascots = mascots.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the probability of preferring a mascot that alliterates with the city.
+    pub fn with_alliteration(mut self, probability: f64) -> LeagueGenerator {
+        assert!(
+            (0.0..=1.0).contains(&probability),
+            "alliteration probability must be within 0..=1"
+        );
+        self.alliteration = probability;
+        self
+    }
+
+    /// Generate a single team.
+    pub fn team<T: Rng + ?Sized>(&self, rng: &mut T) -> Team {
+        let city = self.cities.generate(rng);
+        let mascots: Vec<&str> = self.mascots.iter().map(String::as_str).collect();
+        self.sign(city, &mascots, rng)
+    }
+
+    /// Generate a league of up to `n` teams with distinct cities and mascots.
+    ///
+    /// Fewer teams are returned when the mascot pool or city names run out.
+    pub fn league<T: Rng + ?Sized>(&self, n: usize, rng: &mut T) -> Vec<Team> {
+        let mut league: Vec<Team> = Vec::with_capacity(n);
+        while league.len() < n {
+            let mascots: Vec<&str> = self
+                .mascots
+                .iter()
+                .map(String::as_str)
+                .filter(|mascot| league.iter().all(|team| team.mascot != *mascot))
+                .collect();
+            let city = (0..CITY_ATTEMPTS)
+                .map(|_| self.cities.generate(rng))
+                .find(|city| league.iter().all(|team| team.city != *city));
+            match city {
+                Some(city) if !mascots.is_empty() => {
+                    let team = self.sign(city, &mascots, rng);
+                    league.push(team);
+                }
+                _ => break,
+            }
+        }
+        league
+    }
+
+    fn sign<T: Rng + ?Sized>(&self, city: String, mascots: &[&str], rng: &mut T) -> Team {
+        let initial = city.chars().next();
+        let alliterative: Vec<&str> = mascots
+            .iter()
+            .copied()
+            .filter(|mascot| mascot.chars().next() == initial)
+            .collect();
+        let pool = if !alliterative.is_empty() && rng.gen_bool(self.alliteration) {
+            &alliterative
+        } else {
+            mascots
+        };
+        Team {
+            mascot: pool.choose(rng).unwrap().to_string(),
+            city,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LeagueGenerator;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_alliteration() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let generator = LeagueGenerator::default()
+            .with_mascots(vec!["Vipers", "Bears"])
+            .with_alliteration(1.0);
+        for _ in 0..50 {
+            let team = generator.team(&mut rng);
+            if team.city.starts_with('V') {
+                assert_eq!(team.mascot, "Vipers");
+            } else if team.city.starts_with('B') {
+                assert_eq!(team.mascot, "Bears");
+            }
+        }
+    }
+
+    #[test]
+    fn test_league() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let league = LeagueGenerator::default().league(12, &mut rng);
+        assert_eq!(league.len(), 12);
+        for team in &league {
+            let same = league
+                .iter()
+                .filter(|other| other.city == team.city || other.mascot == team.mascot)
+                .count();
+            assert_eq!(same, 1);
+        }
+        let small = LeagueGenerator::default()
+            .with_mascots(vec!["Owls", "Rams"])
+            .league(5, &mut rng);
+        assert_eq!(small.len(), 2);
+    }
+}
@@ -0,0 +1,220 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Reproducible Output
+//!
+//! For a given seed, configuration and crate minor version, the generators reproduce the same
+//! names on every platform. Two things make that hold across `rand` releases and target
+//! architectures:
+//!
+//! - [`StableRng`] is a fixed algorithm owned by this crate, unlike `rand`'s `SmallRng`, whose
+//!   algorithm may differ between platforms and releases.
+//! - Random draws made by [`TotroConfig`](crate::TotroConfig) go through the helpers below, which
+//!   consume whole `u64`s in a documented way rather than relying on `rand`'s distribution
+//!   implementations or on the width of `usize`.
+//!
+//! A change to either, or to the built-in tables, is only made in a new minor version.
+
+use rand::{Error, RngCore, SeedableRng};
+
+/// Pinned pseudo-random generator for reproducible output (xoshiro256++).
+///
+/// Seeding from a `u64` expands it through SplitMix64, and seeding from bytes reads them as four
+/// little-endian words, so a seed yields the same stream on every platform and endianness.
+///
+/// ```rust
+/// use nominae::{StableRng, TotroConfig};
+/// use rand::SeedableRng;
+///
+/// let config = TotroConfig::default();
+/// let first = config.generate(&mut StableRng::seed_from_u64(7));
+/// assert_eq!(first, config.generate(&mut StableRng::seed_from_u64(7)));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StableRng {
+    state: [u64; 4],
+}
+
+impl RngCore for StableRng {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[0].wrapping_add(s[3]).rotate_left(23).wrapping_add(s[0]);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl SeedableRng for StableRng {
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: [u8; 32]) -> StableRng {
+        let mut state = [0; 4];
+        for (word, bytes) in state.iter_mut().zip(seed.chunks_exact(8)) {
+            let mut le = [0; 8];
+            le.copy_from_slice(bytes);
+            *word = u64::from_le_bytes(le);
+        }
+        if state == [0; 4] {
+            // The all-zero state is a fixed point; start from the zero u64 seed instead.
+            return StableRng::seed_from_u64(0);
+        }
+        StableRng { state }
+    }
+
+    fn seed_from_u64(seed: u64) -> StableRng {
+        let mut z = seed;
+        let mut state = [0; 4];
+        for word in state.iter_mut() {
+            z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            *word = mix(z);
+        }
+        StableRng { state }
+    }
+}
+
+/// Seed from arbitrary bytes, such as a world name or a URL parameter, for
+/// [`StableRng::seed_from_u64`].
+///
+/// The seed is the 64-bit FNV-1a hash of `bytes`, which needs no entropy source and is simple
+/// enough to reproduce in other languages.
+///
+/// ```rust
+/// use nominae::{seed_from_str, StableRng, TotroConfig};
+/// use rand::SeedableRng;
+///
+/// let config = TotroConfig::default();
+/// let mut rng = StableRng::seed_from_u64(seed_from_str("velmar-campaign"));
+/// let name = config.generate(&mut rng);
+/// let mut again = StableRng::seed_from_u64(seed_from_str("velmar-campaign"));
+/// assert_eq!(name, config.generate(&mut again));
+/// ```
+pub fn seed_from_bytes(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xCBF2_9CE4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01B3)
+    })
+}
+
+/// Seed from the UTF-8 bytes of `text`, as [`seed_from_bytes`].
+pub fn seed_from_str(text: &str) -> u64 {
+    seed_from_bytes(text.as_bytes())
+}
+
+/// SplitMix64 finalizer, a bijective 64-bit mix.
+pub(crate) fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Uniform draw from `0..n`, which must not be empty.
+///
+/// Uses Lemire's widening multiply with rejection, consuming one `u64` per attempt.
+#[inline]
+pub(crate) fn below<T: RngCore + ?Sized>(rng: &mut T, n: u64) -> u64 {
+    assert!(n > 0, "cannot draw from an empty range");
+    let threshold = n.wrapping_neg() % n;
+    loop {
+        let wide = rng.next_u64() as u128 * n as u128;
+        if wide as u64 >= threshold {
+            return (wide >> 64) as u64;
+        }
+    }
+}
+
+/// Draw `true` with `probability`, consuming exactly one `u64`.
+///
+/// The probability is scaled to 64-bit fixed point, which is exact IEEE arithmetic.
+#[inline]
+pub(crate) fn chance<T: RngCore + ?Sized>(rng: &mut T, probability: f64) -> bool {
+    let bits = rng.next_u64();
+    if probability >= 1.0 {
+        true
+    } else {
+        bits < (probability * 18_446_744_073_709_551_616.0) as u64
+    }
+}
+
+/// Uniform draw from `0.0..1.0`, consuming exactly one `u64`.
+///
+/// The top 53 bits are scaled by 2^-53, which is exact IEEE arithmetic.
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn fraction<T: RngCore + ?Sized>(rng: &mut T) -> f64 {
+    (rng.next_u64() >> 11) as f64 / 9_007_199_254_740_992.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{below, chance, fraction, seed_from_bytes, seed_from_str, StableRng};
+    use rand::{RngCore, SeedableRng};
+
+    #[test]
+    fn test_stream() {
+        // Reference vectors for SplitMix64 and xoshiro256++; these must never change.
+        assert_eq!(StableRng::seed_from_u64(0).state[0], 0xE220_A839_7B1D_CDAF);
+        let mut seed = [0; 32];
+        for (idx, word) in seed.chunks_exact_mut(8).enumerate() {
+            word[0] = idx as u8 + 1;
+        }
+        let mut rng = StableRng::from_seed(seed);
+        let stream: Vec<u64> = (0..4).map(|_| rng.next_u64()).collect();
+        assert_eq!(
+            stream,
+            vec![41943041, 58720359, 3588806011781223, 3591011842654386]
+        );
+    }
+
+    #[test]
+    fn test_seed_from_bytes() {
+        assert_eq!(seed_from_bytes(b""), 0xCBF2_9CE4_8422_2325);
+        assert_eq!(seed_from_str("a"), 0xAF63_DC4C_8601_EC8C);
+        assert_ne!(seed_from_str("velmar"), seed_from_str("velmara"));
+    }
+
+    #[test]
+    fn test_draws() {
+        let mut rng = StableRng::seed_from_u64(0);
+        assert!((0..1000).all(|_| below(&mut rng, 7) < 7));
+        assert!((0..1000).all(|_| below(&mut rng, 1) == 0));
+        assert!((0..1000).all(|_| chance(&mut rng, 1.0)));
+        assert!((0..1000).all(|_| !chance(&mut rng, 0.0)));
+        let hits = (0..10_000).filter(|_| chance(&mut rng, 0.25)).count();
+        assert!((2_000..3_000).contains(&hits), "{}", hits);
+        assert!((0..1000).all(|_| (0.0..1.0).contains(&fraction(&mut rng))));
+    }
+}
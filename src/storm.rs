@@ -0,0 +1,311 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Seasonal Storm Name Series
+
+use crate::error::NominaeError;
+use crate::normalize::{eq_normalized, normalize};
+use crate::totro::TotroConfig;
+use rand::Rng;
+use std::collections::BTreeSet;
+
+/// Initial letters used for storm names, leaving out letters few names start with.
+const LETTERS: &str = "ABCDEFGHIJKLMNOPRSTVW";
+
+/// Names tried per letter before that letter is skipped for the season.
+const LETTER_ATTEMPTS: usize = 1000;
+
+/// A rotating series of alphabetical storm name lists, as used for hurricane names.
+///
+/// Each season gets a list with one name per letter, in alphabetical order, and storms are named
+/// from it in turn. Lists rotate every few seasons, so a list comes back unchanged except for
+/// names that were retired, which are replaced by fresh names with the same initial. Retired
+/// names are never used again by the series.
+///
+/// ```rust
+/// use nominae::{presets, StormSeries};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let mut series = StormSeries::new(presets::pet());
+/// series.start_season(&mut rng);
+/// let first = series.next_storm().unwrap();
+/// assert!(first.starts_with('A'));
+/// series.retire(&first);
+///
+/// // Save the series between sessions and pick up where it left off.
+/// let state = series.state();
+/// let mut restored = StormSeries::restore(presets::pet(), state).unwrap();
+/// assert_eq!(restored.next_storm(), series.next_storm());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct StormSeries {
+    config: TotroConfig,
+    rotation: usize,
+    lists: Vec<Vec<String>>,
+    retired: BTreeSet<String>,
+    season: Option<usize>,
+    named: usize,
+}
+
+/// Saved progress of a [`StormSeries`], taken with [`StormSeries::state`] and restored with
+/// [`StormSeries::restore`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StormState {
+    /// Number of seasons before a list is reused.
+    pub rotation: usize,
+    /// Name lists in rotation order, one for each season started so far up to `rotation`.
+    pub lists: Vec<Vec<String>>,
+    /// Names retired so far.
+    pub retired: BTreeSet<String>,
+    /// Zero-based number of the current season, if one has started.
+    pub season: Option<usize>,
+    /// Storms named so far in the current season.
+    pub named: usize,
+}
+
+impl StormSeries {
+    /// Default number of seasons before a list is reused.
+    pub const DEFAULT_ROTATION: usize = 6;
+
+    /// Create a series whose names are generated from `config`.
+    pub fn new(config: TotroConfig) -> StormSeries {
+        StormSeries {
+            config,
+            rotation: StormSeries::DEFAULT_ROTATION,
+            lists: Vec::new(),
+            retired: BTreeSet::new(),
+            season: None,
+            named: 0,
+        }
+    }
+
+    /// Set the number of seasons before a list is reused.
+    pub fn with_rotation(mut self, rotation: usize) -> StormSeries {
+        assert!(rotation > 0, "rotation must be at least one season");
+        self.rotation = rotation;
+        self
+    }
+
+    /// Restore a series saved with [`StormSeries::state`], generating new names from `config`.
+    ///
+    /// Returns [`NominaeError::InvalidState`] if the lists do not fit the rotation and season,
+    /// or more storms were named than the current list holds.
+    pub fn restore(config: TotroConfig, state: StormState) -> Result<StormSeries, NominaeError> {
+        let invalid = |message: String| Err(NominaeError::InvalidState(message));
+        if state.rotation == 0 {
+            return invalid("rotation must be at least one season".into());
+        }
+        let lists = state
+            .season
+            .map_or(0, |season| season.saturating_add(1).min(state.rotation));
+        if state.lists.len() != lists {
+            return invalid(format!(
+                "expected {} lists for season {:?}, found {}",
+                lists,
+                state.season,
+                state.lists.len()
+            ));
+        }
+        let current = match state.season {
+            Some(season) => state.lists[season % state.rotation].len(),
+            None => 0,
+        };
+        if state.named > current {
+            return invalid(format!(
+                "{} storms named from a list of {}",
+                state.named, current
+            ));
+        }
+        Ok(StormSeries {
+            config,
+            rotation: state.rotation,
+            lists: state.lists,
+            retired: state.retired,
+            season: state.season,
+            named: state.named,
+        })
+    }
+
+    /// Snapshot of the rotation, lists, retired names, season and storms named so far, for
+    /// saving the series between sessions.
+    pub fn state(&self) -> StormState {
+        StormState {
+            rotation: self.rotation,
+            lists: self.lists.clone(),
+            retired: self.retired.clone(),
+            season: self.season,
+            named: self.named,
+        }
+    }
+
+    /// Begin the next season, preparing its list, and return it.
+    pub fn start_season<T: Rng + ?Sized>(&mut self, rng: &mut T) -> &[String] {
+        let season = self.season.map_or(0, |season| season + 1);
+        let index = season % self.rotation;
+        if index == self.lists.len() {
+            self.lists.push(Vec::new());
+        }
+        let mut list = std::mem::take(&mut self.lists[index]);
+        list.retain(|name| !self.is_retired(name));
+        for letter in LETTERS.chars() {
+            if !list.iter().any(|name| name.starts_with(letter)) {
+                if let Some(name) = self.fresh(letter, rng) {
+                    list.push(name);
+                }
+            }
+        }
+        list.sort();
+        self.lists[index] = list;
+        self.season = Some(season);
+        self.named = 0;
+        &self.lists[index]
+    }
+
+    /// Name the next storm of the current season, or `None` when the list is used up or no
+    /// season has started.
+    pub fn next_storm(&mut self) -> Option<String> {
+        let name = self.names().get(self.named)?.clone();
+        self.named += 1;
+        Some(name)
+    }
+
+    /// Retire `name` so the series never uses it again.
+    ///
+    /// The current season keeps the name; it is replaced when its list next comes around.
+    pub fn retire(&mut self, name: &str) {
+        self.retired.insert(name.to_owned());
+    }
+
+    /// Names retired so far.
+    pub fn retired(&self) -> &BTreeSet<String> {
+        &self.retired
+    }
+
+    /// Zero-based number of the current season, if one has started.
+    pub fn season(&self) -> Option<usize> {
+        self.season
+    }
+
+    /// Alphabetical names of the current season.
+    pub fn names(&self) -> &[String] {
+        match self.season {
+            Some(season) => &self.lists[season % self.rotation],
+            None => &[],
+        }
+    }
+
+    /// Whether `name` matches a retired name once normalized.
+    fn is_retired(&self, name: &str) -> bool {
+        let name = normalize(name);
+        self.retired
+            .iter()
+            .any(|retired| normalize(retired) == name)
+    }
+
+    fn fresh<T: Rng + ?Sized>(&self, letter: char, rng: &mut T) -> Option<String> {
+        let fresh = (0..LETTER_ATTEMPTS)
+            .map(|_| self.config.generate(rng))
+            .find(|name| {
+                name.starts_with(letter)
+                    && !self.is_retired(name)
+                    && !self
+                        .lists
+                        .iter()
+                        .flatten()
+                        .any(|other| eq_normalized(other, name))
+            });
+        if fresh.is_none() {
+            event!(debug, %letter, attempts = LETTER_ATTEMPTS, "skipping letter");
+        }
+        fresh
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StormSeries, StormState};
+    use crate::error::NominaeError;
+    use crate::totro::TotroConfig;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_season() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut series = StormSeries::new(TotroConfig::alphabetic().with_length(2, 4));
+        assert_eq!(series.next_storm(), None);
+        let list = series.start_season(&mut rng).to_vec();
+        assert!(list.len() > 10);
+        assert!(list.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(series.next_storm().as_ref(), list.first());
+        assert_eq!(series.next_storm().as_ref(), list.get(1));
+    }
+
+    #[test]
+    fn test_rotation_and_retirement() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut series =
+            StormSeries::new(TotroConfig::alphabetic().with_length(2, 4)).with_rotation(2);
+        let first = series.start_season(&mut rng).to_vec();
+        let retired = first[0].clone();
+        series.retire(&retired);
+        series.start_season(&mut rng);
+        let again = series.start_season(&mut rng).to_vec();
+        assert_eq!(series.season(), Some(2));
+        assert!(!again.contains(&retired));
+        assert_eq!(again.len(), first.len());
+        assert!(again[0].starts_with(&retired[..1]));
+        assert_eq!(&again[1..], &first[1..]);
+    }
+
+    #[test]
+    fn test_state() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let config = TotroConfig::alphabetic().with_length(2, 4);
+        let mut series = StormSeries::new(config.clone()).with_rotation(2);
+        let first = series.start_season(&mut rng).to_vec();
+        series.retire(&first[0]);
+        series.start_season(&mut rng);
+        series.next_storm();
+        let state = series.state();
+        assert_eq!(state.lists.len(), 2);
+        assert_eq!((state.season, state.named), (Some(1), 1));
+        let mut restored = StormSeries::restore(config.clone(), state.clone()).unwrap();
+        assert_eq!(restored, series);
+        assert_eq!(restored.next_storm(), series.next_storm());
+        let mut replay = SmallRng::seed_from_u64(1);
+        let mut rng = SmallRng::seed_from_u64(1);
+        assert_eq!(
+            restored.start_season(&mut replay),
+            series.start_season(&mut rng)
+        );
+        let broken = StormState {
+            named: 100,
+            ..state.clone()
+        };
+        assert!(matches!(
+            StormSeries::restore(config.clone(), broken),
+            Err(NominaeError::InvalidState(_))
+        ));
+        let broken = StormState {
+            season: None,
+            ..state
+        };
+        assert!(StormSeries::restore(config, broken).is_err());
+    }
+}
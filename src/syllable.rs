@@ -0,0 +1,439 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Onset-Nucleus-Coda Syllable Model
+//!
+//! Rather than strictly alternating vowels and consonants, each syllable is built from an
+//! optional consonant onset, a vowel nucleus and an optional consonant coda. Consonants that
+//! meet across a syllable boundary (a coda followed by the next onset) are checked against a
+//! [`Legality`] matrix, which keeps clusters like "rkst" from forming. A [`Rhythm`] shapes how
+//! heavy the syllables of a name are relative to one another.
+
+use crate::error::{check_probability, NominaeError};
+use crate::stable::chance;
+use crate::table::{choose, choose_distinct, Table, Token, BOW, EOW, MOW};
+use crate::totro::{draw_length, opens, Fingerprint, Sink, TotroConfig};
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use rand::Rng;
+
+/// Rules deciding which coda may be followed by which onset across a syllable boundary.
+///
+/// By default a pair is legal when the combined cluster is at most three characters long and
+/// does not double a letter ("n" + "n"). Individual pairs can be allowed or forbidden explicitly,
+/// which takes precedence over the default rules.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Legality {
+    max_cluster: usize,
+    doubling: bool,
+    overrides: BTreeMap<(String, String), bool>,
+}
+
+impl Default for Legality {
+    fn default() -> Legality {
+        Legality {
+            max_cluster: 3,
+            doubling: false,
+            overrides: BTreeMap::new(),
+        }
+    }
+}
+
+impl Legality {
+    /// Set the maximum number of characters in a coda and onset cluster.
+    pub fn with_max_cluster(mut self, max_cluster: usize) -> Legality {
+        self.max_cluster = max_cluster;
+        self
+    }
+
+    /// Set whether a coda may end with the letter the following onset starts with.
+    pub fn with_doubling(mut self, doubling: bool) -> Legality {
+        self.doubling = doubling;
+        self
+    }
+
+    /// Always allow `coda` to be followed by `onset`.
+    pub fn allow(mut self, coda: &str, onset: &str) -> Legality {
+        self.overrides
+            .insert((coda.to_owned(), onset.to_owned()), true);
+        self
+    }
+
+    /// Never allow `coda` to be followed by `onset`.
+    pub fn forbid(mut self, coda: &str, onset: &str) -> Legality {
+        self.overrides
+            .insert((coda.to_owned(), onset.to_owned()), false);
+        self
+    }
+
+    /// Check whether `coda` may be followed by `onset`.
+    pub fn is_legal(&self, coda: &str, onset: &str) -> bool {
+        if let Some(&legal) = self.overrides.get(&(coda.to_owned(), onset.to_owned())) {
+            return legal;
+        }
+        if coda.chars().count() + onset.chars().count() > self.max_cluster {
+            return false;
+        }
+        self.doubling || coda.chars().last() != onset.chars().next()
+    }
+
+    /// Build the full legality matrix for a consonant table, indexed as `[coda][onset]` in
+    /// table order. Useful for displaying or auditing the rules in editor tooling.
+    pub fn matrix(&self, consonants: &Table) -> Vec<Vec<bool>> {
+        let tokens = consonants.tokens();
+        tokens
+            .iter()
+            .map(|coda| {
+                tokens
+                    .iter()
+                    .map(|onset| self.is_legal(coda.text(), onset.text()))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// How syllable weight varies across a name.
+///
+/// A heavy syllable is closed by a coda; a light one is open and has a single-letter nucleus.
+/// Two cultures sharing the same tables can be told apart by rhythm alone: "Kartesvan" and
+/// "Kalimeno" differ less in their sounds than in how those sounds are spaced.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Rhythm {
+    /// Every syllable is built independently at the configured onset and coda probabilities.
+    #[default]
+    Free,
+    /// Heavy syllables alternate with light ones, starting with a heavy first syllable, as in
+    /// English or Russian. Names lean toward the short end of the length range, since their
+    /// heavy syllables already carry more sound.
+    StressTimed,
+    /// Every syllable has a single-letter nucleus and codas stay at the configured
+    /// probability, keeping syllables of even weight as in Spanish or Japanese. Names lean
+    /// toward the long end of the length range.
+    SyllableTimed,
+}
+
+/// Settings for [`Mode::Syllabic`](crate::Mode::Syllabic) generation.
+///
+/// Onsets come from consonants that may begin (first syllable) or sit inside a word, nuclei
+/// come from vowels, and codas come from consonants that may end (last syllable) or sit inside
+/// a word. A syllable following an open syllable always receives an onset so vowels never run
+/// together, and whether the first syllable has an onset follows the configured vowel start
+/// probability.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Syllabic {
+    onset: f64,
+    coda: f64,
+    legality: Legality,
+    rhythm: Rhythm,
+}
+
+impl Default for Syllabic {
+    fn default() -> Syllabic {
+        Syllabic {
+            onset: 0.8,
+            coda: 0.3,
+            legality: Legality::default(),
+            rhythm: Rhythm::default(),
+        }
+    }
+}
+
+impl Syllabic {
+    /// Set the probability that a syllable receives an onset. A probability outside `0..=1` is
+    /// reported by [`Syllabic::validate`].
+    pub fn with_onset(mut self, probability: f64) -> Syllabic {
+        self.onset = probability;
+        self
+    }
+
+    /// Set the probability that a syllable receives a coda. A probability outside `0..=1` is
+    /// reported by [`Syllabic::validate`].
+    pub fn with_coda(mut self, probability: f64) -> Syllabic {
+        self.coda = probability;
+        self
+    }
+
+    /// Check that the onset and coda probabilities are within `0..=1`.
+    ///
+    /// [`TotroConfig::validate`] runs this for configurations in syllabic mode.
+    pub fn validate(&self) -> Result<(), NominaeError> {
+        check_probability("onset probability", self.onset)?;
+        check_probability("coda probability", self.coda)
+    }
+
+    /// Set the rules for clusters across syllable boundaries.
+    pub fn with_legality(mut self, legality: Legality) -> Syllabic {
+        self.legality = legality;
+        self
+    }
+
+    /// Rules for clusters across syllable boundaries.
+    pub fn legality(&self) -> &Legality {
+        &self.legality
+    }
+
+    /// Set how syllable weight varies across a name.
+    pub fn with_rhythm(mut self, rhythm: Rhythm) -> Syllabic {
+        self.rhythm = rhythm;
+        self
+    }
+
+    /// How syllable weight varies across a name.
+    pub fn rhythm(&self) -> Rhythm {
+        self.rhythm
+    }
+
+    /// Write every setting into `hasher` for [`TotroConfig::fingerprint`].
+    pub(crate) fn fingerprint(&self, hasher: &mut Fingerprint) {
+        hasher.number(self.onset.to_bits());
+        hasher.number(self.coda.to_bits());
+        hasher.number(self.legality.max_cluster as u64);
+        hasher.flag(self.legality.doubling);
+        hasher.number(self.legality.overrides.len() as u64);
+        for ((coda, onset), legal) in &self.legality.overrides {
+            hasher.text(coda);
+            hasher.text(onset);
+            hasher.flag(*legal);
+        }
+        // Written only when set so that fingerprints from before rhythms existed still match.
+        match self.rhythm {
+            Rhythm::Free => {}
+            Rhythm::StressTimed => hasher.number(1),
+            Rhythm::SyllableTimed => hasher.number(2),
+        }
+    }
+
+    /// Draw a number of syllables from `min..max`, leaning toward one end of the range as the
+    /// rhythm asks by keeping the shorter or longer of two draws.
+    pub(crate) fn draw_length<T: Rng + ?Sized>(&self, min: u8, max: u8, rng: &mut T) -> usize {
+        let length = draw_length(min, max, rng);
+        match self.rhythm {
+            Rhythm::Free => length,
+            Rhythm::StressTimed => length.min(draw_length(min, max, rng)),
+            Rhythm::SyllableTimed => length.max(draw_length(min, max, rng)),
+        }
+    }
+
+    /// Coda probability of syllable `idx`, and whether its nucleus must be a single letter.
+    fn shape(&self, idx: usize) -> (f64, bool) {
+        match self.rhythm {
+            Rhythm::Free => (self.coda, false),
+            Rhythm::StressTimed if idx.is_multiple_of(2) => (1.0, false),
+            Rhythm::StressTimed => (0.0, true),
+            Rhythm::SyllableTimed => (self.coda, true),
+        }
+    }
+
+    /// Assemble `length` syllables from the vowel and consonant tables.
+    pub(crate) fn assemble<'a, S: Sink<'a>, T: Rng + ?Sized>(
+        &self,
+        config: &'a TotroConfig,
+        length: usize,
+        initial: Option<char>,
+        sink: &mut S,
+        rng: &mut T,
+    ) {
+        let vowels = config.vowels();
+        let consonants = config.consonants();
+        let mut previous: Option<&Token> = None;
+        let restricted = config.restricted();
+        let mut prefix = String::new();
+        for idx in 0..length {
+            let first = idx == 0;
+            let last = idx + 1 == length;
+            let (closing, short) = self.shape(idx);
+            // A biased ending decides whether the last syllable is closed by a coda.
+            let closing = match config.vowel_ending() {
+                Some(probability) if last => 1.0 - probability,
+                _ => closing,
+            };
+
+            let wants_onset = if first {
+                !config.starts_with_vowel(initial, None, rng)
+            } else {
+                previous.is_none() || chance(rng, self.onset)
+            };
+            let onset = if wants_onset {
+                let flag = if first { BOW } else { MOW };
+                let legality = &self.legality;
+                let candidates = consonants.tokens().iter().filter(|token| {
+                    token.allows(flag)
+                        && (!first || opens(token, initial))
+                        && previous.is_none_or(|coda| legality.is_legal(coda.text(), token.text()))
+                        && config.follows(
+                            previous.map(|coda| (coda.text(), false)),
+                            (token.text(), false),
+                            false,
+                        )
+                        && (!restricted || config.permits(&prefix, token, false))
+                });
+                choose_distinct(candidates, &config.avoid(previous.map(Token::text)), rng)
+            } else {
+                None
+            };
+
+            let coda = if chance(rng, closing) {
+                let flag = if last { EOW } else { MOW };
+                choose(
+                    consonants.tokens().iter().filter(|token| {
+                        token.allows(flag) && (!restricted || config.permits("", token, last))
+                    }),
+                    rng,
+                )
+            } else {
+                None
+            };
+
+            let mut flag = 0;
+            if first && onset.is_none() {
+                flag |= BOW;
+            }
+            if last && coda.is_none() {
+                flag |= EOW;
+            }
+            if flag == 0 {
+                flag = MOW;
+            }
+            let mut avoid = config.avoid(onset.map(Token::text));
+            avoid.extend(config.avoid(coda.map(Token::text)));
+            let opening = first && onset.is_none();
+            if restricted {
+                prefix.extend(onset.map(|onset| onset.text().to_lowercase()));
+            }
+            let ending = last && coda.is_none();
+            let before = onset.or(previous).map(|token| (token.text(), false));
+            let mut nucleus = |short: bool| {
+                let candidates = vowels.tokens().iter().filter(|token| {
+                    token.allows(flag)
+                        && (!short || token.text().chars().count() == 1)
+                        && config.follows(before, (token.text(), true), ending)
+                        && (!opening || opens(token, initial))
+                        && (!restricted || config.permits(&prefix, token, ending))
+                });
+                choose_distinct(candidates, &avoid, rng)
+            };
+            // A rhythm asking for a short nucleus gives way when no single letter fits.
+            let nucleus = nucleus(short).or_else(|| if short { nucleus(false) } else { None });
+            let nucleus = nucleus.or_else(|| {
+                choose_distinct(
+                    vowels.tokens().iter().filter(|token| token.allows(MOW)),
+                    &avoid,
+                    rng,
+                )
+            });
+            if restricted {
+                prefix.extend(nucleus.map(|token| token.text().to_lowercase()));
+            }
+
+            // The coda was drawn before the nucleus it closes, so check it again against the
+            // syllable so far and redraw it from the tokens that fit.
+            let after = nucleus.map(|token| (token.text(), true));
+            let fits = |token: &Token| {
+                config.permits(&prefix, token, last)
+                    && config.follows(after, (token.text(), false), last)
+            };
+            let coda = match coda {
+                Some(coda) if (restricted || config.linked()) && !fits(coda) => {
+                    let flag = if last { EOW } else { MOW };
+                    let fitting = consonants
+                        .tokens()
+                        .iter()
+                        .filter(|token| token.allows(flag) && fits(token));
+                    choose(fitting, rng).or(Some(coda))
+                }
+                coda => coda,
+            };
+
+            for token in onset.iter().chain(nucleus.iter()).chain(coda.iter()) {
+                sink.emit(token);
+            }
+            if restricted {
+                prefix.extend(coda.map(|token| token.text().to_lowercase()));
+            }
+            previous = coda;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Legality, Rhythm, Syllabic};
+    use crate::{Mode, NominaeError, Table, Token, TotroConfig, AIW};
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_legality() {
+        let legality = Legality::default().allow("n", "n").forbid("r", "t");
+        assert!(legality.is_legal("st", "r"));
+        assert!(!legality.is_legal("st", "tr"));
+        assert!(!legality.is_legal("k", "k"));
+        assert!(legality.is_legal("n", "n"));
+        assert!(!legality.is_legal("r", "t"));
+        assert!(Legality::default().with_doubling(true).is_legal("k", "k"));
+    }
+
+    #[test]
+    fn test_syllabic() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let config = TotroConfig::default()
+            .with_length(2, 4)
+            .with_mode(Mode::Syllabic(Syllabic::default().with_coda(0.5)));
+        for _ in 0..100 {
+            let name = config.generate(&mut rng);
+            assert!(name.len() >= 2);
+            assert!(name.starts_with(|c: char| c.is_ascii_uppercase() || c == '\''));
+        }
+        let invalid = config.with_mode(Mode::Syllabic(Syllabic::default().with_onset(-0.5)));
+        assert_eq!(
+            invalid.validate(),
+            Err(NominaeError::OutOfRange(
+                "onset probability -0.5 must be within 0..=1".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_rhythm() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let vowels = Table::new(vec![Token::new("a", AIW), Token::new("ou", AIW)]).unwrap();
+        let consonants = Table::new(vec![Token::new("k", AIW), Token::new("n", AIW)]).unwrap();
+        let config = |rhythm: Rhythm| {
+            let syllabic = Syllabic::default().with_onset(1.0).with_rhythm(rhythm);
+            TotroConfig::new(vowels.clone(), consonants.clone())
+                .with_length(3, 3)
+                .with_vowel_start(0.0)
+                .with_mode(Mode::Syllabic(syllabic))
+        };
+        let (stressed, even) = (config(Rhythm::StressTimed), config(Rhythm::SyllableTimed));
+        for _ in 0..100 {
+            assert!(!even.generate(&mut rng).contains("ou"));
+            let name: Vec<char> = stressed.generate(&mut rng).chars().collect();
+            let consonant = |c: &char| "kn".contains(*c);
+            assert!(name.windows(2).any(|pair| pair.iter().all(consonant)));
+            assert!(name.last().is_some_and(consonant));
+        }
+        let lengths = |rhythm: Rhythm, rng: &mut SmallRng| -> usize {
+            let syllabic = Syllabic::default().with_rhythm(rhythm);
+            (0..1000).map(|_| syllabic.draw_length(1, 5, rng)).sum()
+        };
+        assert!(lengths(Rhythm::StressTimed, &mut rng) < lengths(Rhythm::Free, &mut rng));
+        assert!(lengths(Rhythm::SyllableTimed, &mut rng) > lengths(Rhythm::Free, &mut rng));
+    }
+}
@@ -0,0 +1,191 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Gang and Criminal Organization Name Generation
+
+use crate::grammar::{Grammar, Phrase};
+use crate::totro::TotroConfig;
+use rand::Rng;
+
+/// How a criminal organization is structured, which controls the templates used to name it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Structure {
+    /// Street gangs, such as "The Hollow Knives".
+    StreetGang,
+    /// Smuggling and trafficking cartels, such as "The Velmar Cartel".
+    Cartel,
+    /// Crime families, such as "Varga Family".
+    Family,
+    /// Secretive cults, such as "Children of the Pale Eye".
+    Cult,
+}
+
+impl Structure {
+    /// Grammar symbol naming organizations of this structure.
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Structure::StreetGang => "street_gang",
+            Structure::Cartel => "cartel",
+            Structure::Family => "family",
+            Structure::Cult => "cult",
+        }
+    }
+}
+
+/// Generator for syndicate names, with template pools chosen by [`Structure`].
+///
+/// The underlying [`Grammar`] has one starting rule per structure (see [`Structure::symbol`]),
+/// sharing the `adjective`, `weapon` and `omen` rules, and can be extended like any grammar.
+///
+/// ```rust
+/// use nominae::{Structure, SyndicateGenerator};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let syndicates = SyndicateGenerator::default();
+/// println!("{}", syndicates.generate(Structure::Family, &mut rng));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SyndicateGenerator {
+    grammar: Grammar,
+}
+
+impl Default for SyndicateGenerator {
+    fn default() -> SyndicateGenerator {
+        let grammar = Grammar::default()
+            .rule(
+                "street_gang",
+                &[
+                    "The {adjective} {weapon}",
+                    "The {name} Street {weapon}",
+                    "The {weapon} of {name}",
+                    "{adjective} Boys",
+                ],
+            )
+            .rule(
+                "cartel",
+                &[
+                    "The {name} Cartel",
+                    "{name} Syndicate",
+                    "The {adjective} Company",
+                    "The {name} Consortium",
+                ],
+            )
+            .rule(
+                "family",
+                &[
+                    "{name} Family",
+                    "House {name}",
+                    "The {name} Clan",
+                    "{name} & Sons",
+                ],
+            )
+            .rule(
+                "cult",
+                &[
+                    "Children of the {omen}",
+                    "The Order of the {omen}",
+                    "Disciples of {name}",
+                    "The {adjective} Communion",
+                ],
+            )
+            .rule(
+                "adjective",
+                &[
+                    "Hollow", "Crimson", "Rusted", "Grinning", "Silent", "Broken", "Gilded",
+                    "Black",
+                ],
+            )
+            .rule(
+                "weapon",
+                &[
+                    "Knives", "Hooks", "Razors", "Chains", "Daggers", "Needles", "Cleavers",
+                ],
+            )
+            .rule(
+                "omen",
+                &[
+                    "Pale Eye",
+                    "Drowned Star",
+                    "Ninth Bell",
+                    "Black Sun",
+                    "Weeping Moon",
+                ],
+            );
+        SyndicateGenerator { grammar }
+    }
+}
+
+impl SyndicateGenerator {
+    /// Set the configuration used to generate proper nouns.
+    pub fn with_names(mut self, names: TotroConfig) -> SyndicateGenerator {
+        self.grammar = self.grammar.with_names(names);
+        self
+    }
+
+    /// Add alternatives to one of the syndicate grammar's rules.
+    pub fn rule(mut self, symbol: &str, alternatives: &[&str]) -> SyndicateGenerator {
+        self.grammar = self.grammar.rule(symbol, alternatives);
+        self
+    }
+
+    /// Grammar used to generate syndicate names.
+    pub fn grammar(&self) -> &Grammar {
+        &self.grammar
+    }
+
+    /// Generate the name of an organization with the given structure.
+    pub fn generate<T: Rng + ?Sized>(&self, structure: Structure, rng: &mut T) -> Phrase {
+        self.grammar.generate(structure.symbol(), rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Structure, SyndicateGenerator};
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_structures() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let syndicates = SyndicateGenerator::default();
+        let structures = [
+            Structure::StreetGang,
+            Structure::Cartel,
+            Structure::Family,
+            Structure::Cult,
+        ];
+        for _ in 0..20 {
+            for &structure in structures.iter() {
+                let phrase = syndicates.generate(structure, &mut rng);
+                assert!(!phrase.text.contains('{'), "{}", phrase);
+            }
+        }
+    }
+
+    #[test]
+    fn test_family() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let syndicates = SyndicateGenerator::default();
+        for _ in 0..20 {
+            let family = syndicates.generate(Structure::Family, &mut rng);
+            assert_eq!(family.names.len(), 1);
+            assert!(family.text.contains(family.names[0].as_str()));
+        }
+    }
+}
@@ -0,0 +1,512 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Syllable Token Tables
+
+use crate::stable::below;
+use alloc::borrow::Cow;
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use rand::Rng;
+
+/// 0 dot-in-word
+pub const NIW: u8 = 0b00000000;
+/// 4 beginning-of-word
+pub const BOW: u8 = 0b00000100;
+/// 2 middle-of-word
+pub const MOW: u8 = 0b00000010;
+/// 1 end-of-word
+pub const EOW: u8 = 0b00000001;
+/// 6 beginning-middle-word
+pub const BMW: u8 = BOW | MOW;
+/// 5 beginning-end-word
+pub const BEW: u8 = BOW | EOW;
+/// 3 middle-end-word
+pub const MEW: u8 = MOW | EOW;
+/// 7 all-in-word
+pub const AIW: u8 = BOW | MOW | EOW;
+
+/// A user-defined value attached to a [`Token`], such as a phoneme class or origin tag.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Attribute {
+    /// Presence alone carries the meaning, as with a "hard" tag.
+    Flag,
+    /// A text value, such as "plosive" for a phoneme class.
+    Text(String),
+    /// A numeric value, such as a stress level.
+    Number(i64),
+}
+
+impl fmt::Display for Attribute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Attribute::Flag => Ok(()),
+            Attribute::Text(text) => f.write_str(text),
+            Attribute::Number(number) => write!(f, "{}", number),
+        }
+    }
+}
+
+impl From<&str> for Attribute {
+    fn from(text: &str) -> Attribute {
+        Attribute::Text(text.to_owned())
+    }
+}
+
+impl From<String> for Attribute {
+    fn from(text: String) -> Attribute {
+        Attribute::Text(text)
+    }
+}
+
+impl From<i64> for Attribute {
+    fn from(number: i64) -> Attribute {
+        Attribute::Number(number)
+    }
+}
+
+/// A single syllable piece along with the positions it may be placed at and its relative weight.
+///
+/// Tokens may also carry named [`Attribute`]s, which generation ignores but which constraints
+/// and explanations can read:
+///
+/// ```rust
+/// use nominae::{Attribute, Token, AIW};
+///
+/// let token = Token::new("k", AIW)
+///     .with_tag("hard")
+///     .with_attribute("class", "plosive");
+/// assert!(token.has("hard"));
+/// assert_eq!(token.attribute("class"), Some(&Attribute::from("plosive")));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Token {
+    text: Cow<'static, str>,
+    flags: u8,
+    weight: u32,
+    attributes: BTreeMap<Cow<'static, str>, Attribute>,
+}
+
+impl Token {
+    /// Create a token from static text with a weight of 1.
+    pub const fn new(text: &'static str, flags: u8) -> Token {
+        Token {
+            text: Cow::Borrowed(text),
+            flags,
+            weight: 1,
+            attributes: BTreeMap::new(),
+        }
+    }
+
+    /// Create a token from owned text, such as text read from a file or typed into an editor.
+    pub fn owned<S: Into<String>>(text: S, flags: u8, weight: u32) -> Token {
+        Token {
+            text: Cow::Owned(text.into()),
+            flags,
+            weight,
+            attributes: BTreeMap::new(),
+        }
+    }
+
+    /// Return a copy of this token with the provided weight.
+    pub fn with_weight(mut self, weight: u32) -> Token {
+        self.weight = weight;
+        self
+    }
+
+    /// Return a copy of this token with the attribute `key` set to `value`.
+    pub fn with_attribute<K, V>(mut self, key: K, value: V) -> Token
+    where
+        K: Into<Cow<'static, str>>,
+        V: Into<Attribute>,
+    {
+        self.attributes.insert(key.into(), value.into());
+        self
+    }
+
+    /// Return a copy of this token tagged with the [`Attribute::Flag`] `key`.
+    pub fn with_tag<K: Into<Cow<'static, str>>>(self, key: K) -> Token {
+        self.with_attribute(key, Attribute::Flag)
+    }
+
+    /// Value of the attribute `key`, if set.
+    pub fn attribute(&self, key: &str) -> Option<&Attribute> {
+        self.attributes.get(key)
+    }
+
+    /// Check whether the attribute `key` is set.
+    pub fn has(&self, key: &str) -> bool {
+        self.attributes.contains_key(key)
+    }
+
+    /// Every attribute of this token, ordered by key.
+    pub fn attributes(&self) -> impl Iterator<Item = (&str, &Attribute)> {
+        self.attributes
+            .iter()
+            .map(|(key, value)| (key.as_ref(), value))
+    }
+
+    /// Text emitted when this token is selected.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Position flags (`BOW`, `MOW`, `EOW`) this token may be placed at.
+    pub fn flags(&self) -> u8 {
+        self.flags
+    }
+
+    /// Relative selection weight of this token.
+    pub fn weight(&self) -> u32 {
+        self.weight
+    }
+
+    /// Check whether this token may be placed at every position in `flags`.
+    pub fn allows(&self, flags: u8) -> bool {
+        (self.flags & flags) == flags
+    }
+}
+
+/// Errors produced when editing or validating a [`Table`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TableError {
+    /// Token text was empty.
+    EmptyToken,
+    /// Token weight was zero.
+    ZeroWeight(String),
+    /// Token flags contained bits other than `BOW`, `MOW` and `EOW`.
+    InvalidFlags(String, u8),
+    /// Token text is already present in the table.
+    DuplicateToken(String),
+    /// Token text is not present in the table.
+    UnknownToken(String),
+    /// No token in the table may be placed at the given position.
+    UncoveredPosition(u8),
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TableError::EmptyToken => write!(f, "token text must not be empty"),
+            TableError::ZeroWeight(text) => {
+                write!(f, "token {:?} must have a weight above zero", text)
+            }
+            TableError::InvalidFlags(text, flags) => {
+                write!(
+                    f,
+                    "token {:?} has invalid position flags {:#05b}",
+                    text, flags
+                )
+            }
+            TableError::DuplicateToken(text) => {
+                write!(f, "token {:?} is already in the table", text)
+            }
+            TableError::UnknownToken(text) => write!(f, "token {:?} is not in the table", text),
+            TableError::UncoveredPosition(flag) => {
+                write!(
+                    f,
+                    "no token can be placed at the {} of a word",
+                    position_name(*flag)
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TableError {}
+
+fn position_name(flag: u8) -> &'static str {
+    match flag {
+        BOW => "beginning",
+        MOW => "middle",
+        EOW => "end",
+        _ => "unknown position",
+    }
+}
+
+/// An editable, validated list of syllable tokens.
+///
+/// Every edit is validated before it is applied, so a table can never be left in a state where
+/// some position of a word has no candidate token. Token text is unique within a table.
+///
+/// ```rust
+/// use nominae::{Table, Token, AIW, BOW, EOW};
+///
+/// let mut table = Table::new(vec![Token::new("k", AIW), Token::new("r", AIW)]).unwrap();
+/// table.add(Token::new("th", BOW)).unwrap();
+/// table.set_weight("k", 3).unwrap();
+/// table.set_flags("r", EOW).unwrap();
+/// assert!(table.remove("k").is_err()); // "k" is the only token left for the middle of words.
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Table {
+    tokens: Vec<Token>,
+}
+
+impl Table {
+    /// Create a table from a list of tokens, validating it.
+    pub fn new(tokens: Vec<Token>) -> Result<Table, TableError> {
+        let table = Table { tokens };
+        table.validate()?;
+        Ok(table)
+    }
+
+    /// Create a table from built-in `(text, flags, weight)` entries.
+    pub(crate) fn from_entries(entries: &[(&'static str, u8, u32)]) -> Table {
+        let tokens = entries
+            .iter()
+            .map(|&(text, flags, weight)| Token::new(text, flags).with_weight(weight))
+            .collect();
+        Table { tokens }
+    }
+
+    /// All tokens in this table.
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    /// Look up a token by its text.
+    pub fn get(&self, text: &str) -> Option<&Token> {
+        self.tokens.iter().find(|token| token.text == text)
+    }
+
+    /// Sum of the weights of every token that may be placed at `flags`.
+    pub fn total_weight(&self, flags: u8) -> u64 {
+        self.tokens
+            .iter()
+            .filter(|token| token.allows(flags))
+            .map(|token| token.weight as u64)
+            .sum()
+    }
+
+    /// Add a new token to the table.
+    pub fn add(&mut self, token: Token) -> Result<(), TableError> {
+        validate_token(&token)?;
+        if self.get(&token.text).is_some() {
+            return Err(TableError::DuplicateToken(token.text.into_owned()));
+        }
+        self.tokens.push(token);
+        Ok(())
+    }
+
+    /// Remove a token from the table, returning it.
+    pub fn remove(&mut self, text: &str) -> Result<Token, TableError> {
+        let index = self.index_of(text)?;
+        self.edit(|tokens| Ok(tokens.remove(index)))
+    }
+
+    /// Replace the text of a token while keeping its flags and weight.
+    pub fn retarget(&mut self, text: &str, new_text: &str) -> Result<(), TableError> {
+        let index = self.index_of(text)?;
+        if new_text != text && self.get(new_text).is_some() {
+            return Err(TableError::DuplicateToken(new_text.to_owned()));
+        }
+        self.edit(|tokens| {
+            tokens[index].text = Cow::Owned(new_text.to_owned());
+            Ok(())
+        })
+    }
+
+    /// Change the position flags of a token.
+    pub fn set_flags(&mut self, text: &str, flags: u8) -> Result<(), TableError> {
+        let index = self.index_of(text)?;
+        self.edit(|tokens| {
+            tokens[index].flags = flags;
+            Ok(())
+        })
+    }
+
+    /// Change the weight of a token.
+    pub fn set_weight(&mut self, text: &str, weight: u32) -> Result<(), TableError> {
+        let index = self.index_of(text)?;
+        self.edit(|tokens| {
+            tokens[index].weight = weight;
+            Ok(())
+        })
+    }
+
+    /// Set the attribute `key` of a token to `value`.
+    pub fn set_attribute<K, V>(&mut self, text: &str, key: K, value: V) -> Result<(), TableError>
+    where
+        K: Into<Cow<'static, str>>,
+        V: Into<Attribute>,
+    {
+        let index = self.index_of(text)?;
+        self.tokens[index]
+            .attributes
+            .insert(key.into(), value.into());
+        Ok(())
+    }
+
+    /// Keep only the tokens matching `keep`. The remaining weights are untouched, so each
+    /// surviving token keeps its share relative to the others.
+    pub fn retain<F: FnMut(&Token) -> bool>(&mut self, keep: F) -> Result<(), TableError> {
+        self.edit(|tokens| {
+            tokens.retain(keep);
+            Ok(())
+        })
+    }
+
+    /// Validate every token and check that each position of a word has at least one candidate.
+    pub fn validate(&self) -> Result<(), TableError> {
+        for (idx, token) in self.tokens.iter().enumerate() {
+            validate_token(token)?;
+            if self.tokens[..idx]
+                .iter()
+                .any(|other| other.text == token.text)
+            {
+                return Err(TableError::DuplicateToken(token.text.clone().into_owned()));
+            }
+        }
+        // Every token in a word must allow the middle, as well as the beginning or the end.
+        for &(flag, needs) in [(BOW, BMW), (MOW, MOW), (EOW, MEW)].iter() {
+            if self.total_weight(needs) == 0 {
+                return Err(TableError::UncoveredPosition(flag));
+            }
+        }
+        Ok(())
+    }
+
+    fn index_of(&self, text: &str) -> Result<usize, TableError> {
+        self.tokens
+            .iter()
+            .position(|token| token.text == text)
+            .ok_or_else(|| TableError::UnknownToken(text.to_owned()))
+    }
+
+    /// Apply an edit to a scratch copy of the tokens and keep it only if the result validates.
+    fn edit<T, F>(&mut self, op: F) -> Result<T, TableError>
+    where
+        F: FnOnce(&mut Vec<Token>) -> Result<T, TableError>,
+    {
+        let mut scratch = Table {
+            tokens: self.tokens.clone(),
+        };
+        let result = op(&mut scratch.tokens)?;
+        scratch.validate()?;
+        *self = scratch;
+        Ok(result)
+    }
+}
+
+/// Select a token from `tokens` with probability proportional to its weight.
+pub(crate) fn choose<'a, I, T>(tokens: I, rng: &mut T) -> Option<&'a Token>
+where
+    I: Iterator<Item = &'a Token> + Clone,
+    T: Rng + ?Sized,
+{
+    let total: u64 = tokens.clone().map(|token| token.weight as u64).sum();
+    if total == 0 {
+        return None;
+    }
+    let mut roll = below(rng, total);
+    for token in tokens {
+        let weight = token.weight as u64;
+        if roll < weight {
+            return Some(token);
+        }
+        roll -= weight;
+    }
+    None
+}
+
+/// Select a token like [`choose`], skipping tokens whose text is in `avoid` unless nothing else
+/// is available.
+pub(crate) fn choose_distinct<'a, I, T>(tokens: I, avoid: &[&str], rng: &mut T) -> Option<&'a Token>
+where
+    I: Iterator<Item = &'a Token> + Clone,
+    T: Rng + ?Sized,
+{
+    let distinct = tokens
+        .clone()
+        .filter(|token| !avoid.contains(&token.text()));
+    if avoid.is_empty() || distinct.clone().next().is_none() {
+        choose(tokens, rng)
+    } else {
+        choose(distinct, rng)
+    }
+}
+
+fn validate_token(token: &Token) -> Result<(), TableError> {
+    if token.text.is_empty() {
+        Err(TableError::EmptyToken)
+    } else if token.weight == 0 {
+        Err(TableError::ZeroWeight(token.text.clone().into_owned()))
+    } else if (token.flags & !AIW) != 0 {
+        Err(TableError::InvalidFlags(
+            token.text.clone().into_owned(),
+            token.flags,
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Table, TableError, Token, AIW, BOW, EOW, MOW};
+
+    fn table() -> Table {
+        Table::new(vec![Token::new("a", AIW), Token::new("e", AIW)]).unwrap()
+    }
+
+    #[test]
+    fn test_edit() {
+        let mut table = table();
+        table.add(Token::new("ou", MOW)).unwrap();
+        table.set_weight("a", 4).unwrap();
+        table.retarget("e", "ei").unwrap();
+        assert_eq!(table.get("a").unwrap().weight(), 4);
+        assert_eq!(table.get("ei").unwrap().flags(), AIW);
+        assert!(table.get("e").is_none());
+        assert_eq!(table.remove("ou").unwrap().text(), "ou");
+        assert_eq!(table.tokens().len(), 2);
+        table.add(Token::new("'", MOW)).unwrap();
+        table.retain(|token| token.text() != "'").unwrap();
+        assert_eq!(table.tokens().len(), 2);
+    }
+
+    #[test]
+    fn test_validation() {
+        let mut table = table();
+        assert_eq!(
+            table.add(Token::new("a", AIW)),
+            Err(TableError::DuplicateToken("a".into()))
+        );
+        assert_eq!(table.add(Token::new("", AIW)), Err(TableError::EmptyToken));
+        assert_eq!(
+            table.set_weight("a", 0),
+            Err(TableError::ZeroWeight("a".into()))
+        );
+        assert_eq!(
+            table.set_flags("u", AIW),
+            Err(TableError::UnknownToken("u".into()))
+        );
+        table.set_flags("a", BOW).unwrap();
+        // Rejected edits leave the table untouched.
+        assert_eq!(
+            table.set_flags("e", BOW | MOW),
+            Err(TableError::UncoveredPosition(EOW))
+        );
+        assert_eq!(table.get("e").unwrap().flags(), AIW);
+        // Without "e" nothing may be placed at the middle of a word, so nothing can begin one.
+        assert_eq!(table.remove("e"), Err(TableError::UncoveredPosition(BOW)));
+    }
+}
@@ -0,0 +1,73 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Placeholder Templates
+//!
+//! Templates are plain text with `{placeholder}` slots, such as `"Duke of {realm}"`. Text
+//! outside of braces is copied through unchanged, as is an unclosed `{`.
+
+/// Expand every `{placeholder}` in `template` with the value returned by `resolve`.
+pub(crate) fn expand<F: FnMut(&str) -> String>(template: &str, mut resolve: F) -> String {
+    let mut output = String::with_capacity(template.len() * 2);
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        match rest[open..].find('}') {
+            Some(close) => {
+                output.push_str(&rest[..open]);
+                output.push_str(&resolve(&rest[open + 1..open + close]));
+                rest = &rest[open + close + 1..];
+            }
+            None => break,
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Names of every placeholder in `template`, in order of appearance.
+pub(crate) fn placeholders(template: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        match rest[open..].find('}') {
+            Some(close) => {
+                names.push(&rest[open + 1..open + close]);
+                rest = &rest[open + close + 1..];
+            }
+            None => break,
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expand, placeholders};
+
+    #[test]
+    fn test_expand() {
+        let text = expand(
+            "Duke of {realm}, Warden of the {direction}march",
+            |name| match name {
+                "realm" => "Velmar".to_owned(),
+                _ => "East".to_owned(),
+            },
+        );
+        assert_eq!(text, "Duke of Velmar, Warden of the Eastmarch");
+        assert_eq!(expand("{open", |_| String::new()), "{open");
+        assert_eq!(placeholders("{a} and {b} {c"), vec!["a", "b"]);
+    }
+}
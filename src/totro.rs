@@ -16,24 +16,21 @@
 
 //! Reimplementation of the Totro Name Generation Algorithm created by David A Wheeler.
 
-use rand::Rng;
-
-/// 0 dot-in-word
-const NIW: u8 = 0b00000000;
-/// 4 beginning-of-word
-const BOW: u8 = 0b00000100;
-/// 2 middle-of-word
-const MOW: u8 = 0b00000010;
-/// 1 end-of-word
-const EOW: u8 = 0b00000001;
-/// 6 beginning-middle-word
-const BMW: u8 = BOW | MOW;
-/// 5 beginning-end-word
-const BEW: u8 = BOW | EOW;
-/// 3 middle-end-word
-const MEW: u8 = MOW | EOW;
-/// 7 all-in-word
-const AIW: u8 = BOW | MOW | EOW;
+use crate::error::{check_probability, NominaeError};
+use crate::fit::fit_to;
+use crate::phonotactics::{Adjacency, Constraint, TokenClass};
+use crate::profile::ProfileVersion;
+use crate::sampler::{Cache, Pools};
+use crate::stable::{below, chance, mix, StableRng};
+use crate::syllable::Syllabic;
+use crate::table::{choose_distinct, Table, Token, AIW, BEW, BMW, BOW, EOW, MEW, MOW, NIW};
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::ops::{Range, RangeInclusive};
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
 
 /// The Totro struct generates names using a reimplementation of the `Totro Fantasy Random Name Generator` algorithm
 /// created by [David A. Wheeler](https://dwheeler.com/totro.html).
@@ -58,100 +55,1566 @@ const AIW: u8 = BOW | MOW | EOW;
 pub struct Totro;
 
 impl Totro {
-    pub fn generate<T: Rng>(min: u8, max: u8, rng: &mut T) -> String {
-        let length = if min < max {
-            rng.gen_range(min..max)
-        } else if min == max {
-            min
+    /// Generate a name of `min..max` syllables using the default configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `min` is greater than `max`. Use [`Totro::try_generate`] when the bounds
+    /// come from user input or configuration files.
+    pub fn generate<T: Rng + ?Sized>(min: u8, max: u8, rng: &mut T) -> String {
+        match Totro::try_generate(min, max, rng) {
+            Ok(name) => name,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Generate a name of `min..max` syllables using the default configuration, or
+    /// [`NominaeError::InvalidRange`] when `min` is greater than `max`.
+    pub fn try_generate<T: Rng + ?Sized>(
+        min: u8,
+        max: u8,
+        rng: &mut T,
+    ) -> Result<String, NominaeError> {
+        if min > max {
+            return Err(NominaeError::InvalidRange {
+                min: min as usize,
+                max: max as usize,
+            });
+        }
+        let mut name = String::new();
+        with_default(ProfileVersion::LATEST, |config| {
+            generate(config, min, max, None, &mut name, rng)
+        });
+        Ok(name)
+    }
+
+    /// Generate `n` names of `min..max` syllables using the default configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `min` is greater than `max`.
+    pub fn generate_many<T: Rng + ?Sized>(min: u8, max: u8, n: usize, rng: &mut T) -> Vec<String> {
+        Totro::iter(min, max, rng).take(n).collect()
+    }
+
+    /// An endless iterator of names of `min..max` syllables using the default configuration.
+    ///
+    /// ```rust
+    /// use nominae::Totro;
+    /// use rand::SeedableRng;
+    /// use rand::rngs::SmallRng;
+    ///
+    /// let rng = SmallRng::seed_from_u64(0);
+    /// let short: Vec<String> = Totro::iter(2, 5, rng)
+    ///     .filter(|name| name.len() < 6)
+    ///     .take(10)
+    ///     .collect();
+    /// assert_eq!(short.len(), 10);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when `min` is greater than `max`.
+    pub fn iter<T: Rng>(min: u8, max: u8, rng: T) -> Names<T> {
+        assert!(
+            min <= max,
+            "{}",
+            NominaeError::InvalidRange {
+                min: min as usize,
+                max: max as usize,
+            }
+        );
+        Names { min, max, rng }
+    }
+
+    /// The name of the entity numbered `id`, such as a star or NPC, under `config`.
+    ///
+    /// The name is generated from a [`StableRng`] seeded with `id` alone, so the same entity
+    /// gets the same name in every session and on every platform without it being stored.
+    /// Different worlds that reuse ids can fold a world seed into `id` first, or use
+    /// [`Seeded`](crate::Seeded) for a hierarchy of seeds.
+    ///
+    /// ```rust
+    /// use nominae::{Totro, TotroConfig};
+    ///
+    /// let config = TotroConfig::default();
+    /// let star = Totro::for_id(1_000_000, &config);
+    /// assert_eq!(star, Totro::for_id(1_000_000, &config));
+    /// ```
+    pub fn for_id(id: u64, config: &TotroConfig) -> String {
+        config.generate(&mut StableRng::seed_from_u64(id))
+    }
+}
+
+/// Endless iterator of names returned by [`Totro::iter`].
+#[derive(Clone, Debug)]
+pub struct Names<T> {
+    min: u8,
+    max: u8,
+    rng: T,
+}
+
+impl<T: Rng> Iterator for Names<T> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let (min, max) = (self.min, self.max);
+        let mut name = String::new();
+        with_default(ProfileVersion::LATEST, |config| {
+            generate(config, min, max, None, &mut name, &mut self.rng)
+        });
+        Some(name)
+    }
+}
+
+/// Lightweight per-call adjustments to a shared [`TotroConfig`].
+///
+/// Servers can keep one configuration behind an `Arc` and vary parameters per request without
+/// cloning its tables:
+///
+/// ```rust
+/// use nominae::{Overrides, TotroConfig};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+/// use std::sync::Arc;
+///
+/// let shared = Arc::new(TotroConfig::default());
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let overrides = Overrides::default().with_length(3, 3).with_initial('V');
+/// let name = shared.generate_with(&overrides, &mut rng);
+/// assert!(name.starts_with('V'));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Overrides {
+    length: Option<(u8, u8)>,
+    initial: Option<char>,
+}
+
+impl Overrides {
+    /// Use this range of syllables instead of the configuration's. `max` is exclusive unless it
+    /// is equal to `min`.
+    pub fn with_length(mut self, min: u8, max: u8) -> Overrides {
+        self.length = Some((min, max));
+        self
+    }
+
+    /// Use this range of syllables instead of the configuration's, with explicit bounds.
+    pub fn with_length_range<R: Into<LengthRange>>(mut self, range: R) -> Overrides {
+        self.length = Some(range.into().bounds());
+        self
+    }
+
+    /// Start names with `initial`, compared case-insensitively.
+    ///
+    /// Ignored when no token that can begin a word starts with `initial`.
+    pub fn with_initial(mut self, initial: char) -> Overrides {
+        self.initial = Some(initial);
+        self
+    }
+
+    /// Overridden range of syllables, if any.
+    pub fn length(&self) -> Option<(u8, u8)> {
+        self.length
+    }
+
+    /// Overridden starting letter, if any.
+    pub fn initial(&self) -> Option<char> {
+        self.initial
+    }
+}
+
+/// How a [`TotroConfig`] assembles tokens into names.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Mode {
+    /// Strictly alternate between vowel and consonant tokens, one token per syllable. This is
+    /// the original Totro algorithm.
+    Alternating,
+    /// Build each syllable from an optional onset, a nucleus and an optional coda.
+    Syllabic(Syllabic),
+}
+
+/// How the `min` and `max` lengths of a [`TotroConfig`] are measured.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LengthModel {
+    /// Count tokens, so "str" and "a" each count as one. This is the original Totro behavior.
+    Tokens,
+    /// Count characters, so "str" counts as three and "a" as one. Candidate tokens are limited
+    /// to those that still fit the remaining length, making output length predictable when
+    /// tables mix single letters with digraphs and trigraphs.
+    ///
+    /// Only [`Mode::Alternating`] honors this model; syllabic generation always counts syllables.
+    Characters,
+}
+
+/// Range of lengths to generate, saying outright whether `max` can be drawn.
+///
+/// [`TotroConfig::with_length`] has always treated `max` as exclusive, so asking it for 2 to 5
+/// syllables never gives 5. That cannot change without changing every name generated from a
+/// seed, so inclusive bounds are asked for with this type instead. Both kinds of range draw
+/// each length with equal probability, and an inclusive range gives exactly the names of the
+/// exclusive range one wider, so no new [`ProfileVersion`] is needed to use them.
+///
+/// ```rust
+/// use nominae::{LengthModel, LengthRange, TotroConfig};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let config = TotroConfig::default()
+///     .with_length_model(LengthModel::Characters)
+///     .with_length_range(4..=6);
+/// assert_eq!(config.length_range(), LengthRange::inclusive(4, 6));
+/// assert_eq!(config.length(), (4, 7));
+/// assert!((4..=6).contains(&config.generate(&mut rng).len()));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LengthRange {
+    min: u8,
+    max: u8,
+    inclusive: bool,
+}
+
+impl LengthRange {
+    /// Lengths from `min` up to but not including `max`, or exactly `min` when the two are
+    /// equal, as [`TotroConfig::with_length`] takes them.
+    pub fn exclusive(min: u8, max: u8) -> LengthRange {
+        LengthRange {
+            min,
+            max,
+            inclusive: false,
+        }
+    }
+
+    /// Lengths from `min` up to and including `max`.
+    ///
+    /// A `max` of 255 is drawn as 254, the longest length the exclusive form can express.
+    pub fn inclusive(min: u8, max: u8) -> LengthRange {
+        LengthRange {
+            min,
+            max,
+            inclusive: true,
+        }
+    }
+
+    /// Shortest length in the range.
+    pub fn min(&self) -> u8 {
+        self.min
+    }
+
+    /// Upper bound of the range, drawn only when [`is_inclusive`](LengthRange::is_inclusive).
+    pub fn max(&self) -> u8 {
+        self.max
+    }
+
+    /// Whether `max` can be drawn.
+    pub fn is_inclusive(&self) -> bool {
+        self.inclusive
+    }
+
+    /// Whether a name of `length` can be drawn from the range.
+    pub fn contains(&self, length: usize) -> bool {
+        let (min, max) = self.bounds();
+        let (min, max) = (min as usize, max as usize);
+        length == min || (min..max).contains(&length)
+    }
+
+    /// The range in the form generation draws from, where `max` is exclusive unless it equals
+    /// `min`.
+    pub(crate) fn bounds(&self) -> (u8, u8) {
+        if self.inclusive && self.min < self.max {
+            (self.min, self.max.saturating_add(1))
         } else {
-            panic!("min must be less than or equal to max: {} <= {}", min, max);
-        } as usize;
-        let mut output = String::with_capacity(length * 2);
-        let mut vowel = rng.gen();
-        for idx in 0..length {
-            loop {
-                let token = if vowel {
-                    VOWELS.get(rng.gen::<usize>() % VOWELS.len()).unwrap()
-                } else {
-                    CONSONANTS.get(rng.gen::<usize>() % CONSONANTS.len()).unwrap()
+            (self.min, self.max)
+        }
+    }
+}
+
+impl From<Range<u8>> for LengthRange {
+    fn from(range: Range<u8>) -> LengthRange {
+        LengthRange::exclusive(range.start, range.end)
+    }
+}
+
+impl From<RangeInclusive<u8>> for LengthRange {
+    fn from(range: RangeInclusive<u8>) -> LengthRange {
+        LengthRange::inclusive(*range.start(), *range.end())
+    }
+}
+
+/// Seed used by [`TotroConfig::preview`].
+const PREVIEW_SEED: u64 = 0;
+
+/// Configuration for the Totro algorithm: the vowel and consonant tables to alternate between
+/// and the range of syllables to generate.
+///
+/// The default configuration uses the built-in tables and is editable in place, which makes it
+/// a convenient starting point for editor tooling:
+///
+/// ```rust
+/// use nominae::{TotroConfig, Token, BOW, MOW};
+///
+/// let mut config = TotroConfig::default();
+/// config.consonants_mut().add(Token::new("zh", BOW | MOW)).unwrap();
+/// config.consonants_mut().set_weight("z", 10).unwrap();
+/// config.vowels_mut().remove("'").unwrap();
+/// for name in config.preview(5) {
+///     println!("{}", name);
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TotroConfig {
+    vowels: Table,
+    consonants: Table,
+    min: u8,
+    max: u8,
+    length_range: Option<LengthRange>,
+    mode: Mode,
+    length_model: LengthModel,
+    vowel_start: f64,
+    vowel_ending: Option<f64>,
+    distinct_adjacent: bool,
+    forbidden_beginnings: Vec<String>,
+    forbidden_endings: Vec<String>,
+    constraints: Vec<Constraint>,
+    adjacencies: Vec<Adjacency>,
+    min_chars: Option<usize>,
+    max_chars: Option<usize>,
+    version: ProfileVersion,
+    cache: Cache,
+}
+
+impl Default for TotroConfig {
+    fn default() -> TotroConfig {
+        TotroConfig::versioned(ProfileVersion::LATEST)
+    }
+}
+
+impl TotroConfig {
+    /// A configuration using custom `vowels` and `consonants` tables, with every other option at
+    /// its default.
+    ///
+    /// ```rust
+    /// use nominae::{Table, Token, TotroConfig, AIW, BOW, EOW, MOW};
+    ///
+    /// let vowels = Table::new(vec![Token::new("a", AIW), Token::new("u", AIW)]).unwrap();
+    /// let consonants = Table::new(vec![
+    ///     Token::new("k", AIW),
+    ///     Token::new("gr", BOW | MOW),
+    ///     Token::new("z", MOW | EOW),
+    /// ])
+    /// .unwrap();
+    /// let harsh = TotroConfig::new(vowels, consonants);
+    /// println!("{}", harsh.preview(1)[0]);
+    /// ```
+    pub fn new(vowels: Table, consonants: Table) -> TotroConfig {
+        TotroConfig::default()
+            .with_vowels(vowels)
+            .with_consonants(consonants)
+    }
+
+    /// The default configuration as of `version`, which keeps generating the same names for a
+    /// seed even after later versions change the built-in tables or algorithm.
+    pub fn versioned(version: ProfileVersion) -> TotroConfig {
+        with_default(version, TotroConfig::clone)
+    }
+
+    /// Profile version whose generation algorithm this configuration follows.
+    pub fn version(&self) -> ProfileVersion {
+        self.version
+    }
+
+    /// The default configuration without any non-alphabetic tokens such as `'`, for names that
+    /// must be safe to use as database keys or file names.
+    pub fn alphabetic() -> TotroConfig {
+        let mut config = TotroConfig::default();
+        for table in [&mut config.vowels, &mut config.consonants].iter_mut() {
+            table
+                .retain(|token| token.text().chars().all(char::is_alphabetic))
+                .expect("built-in tables contain alphabetic tokens for every position");
+        }
+        config.cache = Cache::default();
+        config
+    }
+
+    /// Set the range of syllables to generate. `max` is exclusive unless it is equal to `min`;
+    /// use [`with_length_range`](TotroConfig::with_length_range) for an inclusive `max`.
+    pub fn with_length(mut self, min: u8, max: u8) -> TotroConfig {
+        self.min = min;
+        self.max = max;
+        self.length_range = None;
+        self
+    }
+
+    /// Set the range of syllables to generate from `range`, such as `2..=5` for names of two
+    /// to five syllables.
+    pub fn with_length_range<R: Into<LengthRange>>(self, range: R) -> TotroConfig {
+        let range = range.into();
+        let (min, max) = range.bounds();
+        let mut config = self.with_length(min, max);
+        config.length_range = Some(range);
+        config
+    }
+
+    /// Range of syllables generated, inclusive if it was set with an inclusive range and in the
+    /// exclusive form [`with_length`](Self::with_length) takes otherwise.
+    pub fn length_range(&self) -> LengthRange {
+        self.length_range
+            .unwrap_or_else(|| LengthRange::exclusive(self.min, self.max))
+    }
+
+    /// Range of syllables generated as `(min, max)`.
+    pub fn length(&self) -> (u8, u8) {
+        (self.min, self.max)
+    }
+
+    /// Set how the length range is measured.
+    pub fn with_length_model(mut self, length_model: LengthModel) -> TotroConfig {
+        self.length_model = length_model;
+        self
+    }
+
+    /// How the length range is measured.
+    pub fn length_model(&self) -> LengthModel {
+        self.length_model
+    }
+
+    /// Set the probability that a name starts with a vowel. `1.0` forces vowel-initial names and
+    /// `0.0` forces consonant-initial names.
+    ///
+    /// A probability outside `0..=1` is reported by [`TotroConfig::validate`].
+    pub fn with_vowel_start(mut self, probability: f64) -> TotroConfig {
+        self.vowel_start = probability;
+        self
+    }
+
+    /// Probability that a name starts with a vowel.
+    pub fn vowel_start(&self) -> f64 {
+        self.vowel_start
+    }
+
+    /// Set the probability that a name ends on a vowel, or `None` to leave endings as the
+    /// tables produce them.
+    ///
+    /// The bias is applied while the last token is selected rather than by drawing names
+    /// again, so it holds in the alternating and syllabic modes but not when lengths are
+    /// counted in characters, where the last token is not known in advance.
+    #[cfg(feature = "std")]
+    pub(crate) fn with_vowel_ending(mut self, probability: Option<f64>) -> TotroConfig {
+        self.vowel_ending = probability;
+        self
+    }
+
+    /// Probability that a name ends on a vowel, if endings are biased.
+    pub(crate) fn vowel_ending(&self) -> Option<f64> {
+        self.vowel_ending
+    }
+
+    /// Set whether the same token may be emitted twice in a row. When suppressed, a repeat is
+    /// only allowed if no other token can be placed at that position.
+    pub fn with_distinct_adjacent(mut self, distinct: bool) -> TotroConfig {
+        self.distinct_adjacent = distinct;
+        self
+    }
+
+    /// Whether the same token is prevented from being emitted twice in a row.
+    pub fn distinct_adjacent(&self) -> bool {
+        self.distinct_adjacent
+    }
+
+    /// Set strings that names must not begin with, such as "x". Comparison ignores case.
+    ///
+    /// The rule is enforced while tokens are selected rather than by rejecting finished names.
+    /// When alternating between tables, earlier tokens are chosen again where the ones already
+    /// chosen leave no way to satisfy it, and the rule is relaxed only if that fails too.
+    pub fn with_forbidden_beginnings<S: Into<String>>(mut self, beginnings: Vec<S>) -> TotroConfig {
+        self.forbidden_beginnings = lowercase(beginnings);
+        self
+    }
+
+    /// Lowercased strings that names must not begin with.
+    pub fn forbidden_beginnings(&self) -> &[String] {
+        &self.forbidden_beginnings
+    }
+
+    /// Set strings that names must not end with, such as "q" or "'". Comparison ignores case.
+    ///
+    /// Enforced like [`TotroConfig::with_forbidden_beginnings`].
+    pub fn with_forbidden_endings<S: Into<String>>(mut self, endings: Vec<S>) -> TotroConfig {
+        self.forbidden_endings = lowercase(endings);
+        self
+    }
+
+    /// Lowercased strings that names must not end with.
+    pub fn forbidden_endings(&self) -> &[String] {
+        &self.forbidden_endings
+    }
+
+    /// Add a phonotactic rule, such as "no more than two vowels in a row".
+    ///
+    /// Enforced like [`TotroConfig::with_forbidden_beginnings`]: a token breaking the rule is
+    /// passed over while choosing, going back over earlier tokens when none at a position
+    /// keeps to it.
+    pub fn with_constraint(mut self, constraint: Constraint) -> TotroConfig {
+        self.constraints.push(constraint.lowercased());
+        self
+    }
+
+    /// Phonotactic rules names are generated to follow.
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    /// Add a rule on which tokens may directly follow which, such as "'qu' is always followed
+    /// by a vowel token".
+    ///
+    /// Tokens breaking the rule are passed over while choosing, and enforced like
+    /// [`TotroConfig::with_forbidden_beginnings`] where none at a position keep to it. When
+    /// alternating between tables, tokens that nothing at the next position could follow are
+    /// passed over too, so a rule like [`Adjacency::Require`] rarely needs earlier tokens taken
+    /// back.
+    pub fn with_adjacency(mut self, adjacency: Adjacency) -> TotroConfig {
+        self.adjacencies.push(adjacency);
+        self
+    }
+
+    /// Rules on which tokens may directly follow which.
+    pub fn adjacencies(&self) -> &[Adjacency] {
+        &self.adjacencies
+    }
+
+    /// Only generate names of at least `min` characters.
+    ///
+    /// Unlike the syllable range this measures what ends up on screen. Names outside the
+    /// character limits are drawn again, and when none fit after 100 draws the closest is used.
+    pub fn with_min_chars(mut self, min: usize) -> TotroConfig {
+        self.min_chars = Some(min);
+        self
+    }
+
+    /// Shortest name generated in characters, if limited.
+    pub fn min_chars(&self) -> Option<usize> {
+        self.min_chars
+    }
+
+    /// Only generate names of at most `max` characters, such as the width of a nameplate or a
+    /// database column.
+    ///
+    /// Names outside the character limits are drawn again like those of
+    /// [`with_min_chars`](TotroConfig::with_min_chars), and the closest one is shortened to `max`
+    /// characters at a syllable boundary with [`fit_to`](crate::fit_to) when none fit, so the
+    /// limit always holds.
+    ///
+    /// ```rust
+    /// use nominae::TotroConfig;
+    /// use rand::SeedableRng;
+    /// use rand::rngs::SmallRng;
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    /// let config = TotroConfig::default().with_length(3, 6).with_max_chars(8);
+    /// assert!(config.generate(&mut rng).chars().count() <= 8);
+    /// ```
+    pub fn with_max_chars(mut self, max: usize) -> TotroConfig {
+        self.max_chars = Some(max);
+        self
+    }
+
+    /// Longest name generated in characters, if limited.
+    pub fn max_chars(&self) -> Option<usize> {
+        self.max_chars
+    }
+
+    /// Whether any adjacency rules are configured.
+    pub(crate) fn linked(&self) -> bool {
+        !self.adjacencies.is_empty()
+    }
+
+    /// Whether `token` may directly follow `previous` and, if `last`, end the name. Both are
+    /// given as their text and whether they are vowel tokens.
+    pub(crate) fn follows(
+        &self,
+        previous: Option<(&str, bool)>,
+        token: (&str, bool),
+        last: bool,
+    ) -> bool {
+        self.adjacencies.iter().all(|rule| {
+            previous.is_none_or(|previous| rule.admits(previous, Some(token)))
+                && (!last || rule.admits(token, None))
+        })
+    }
+
+    /// Whether any forbidden beginnings or endings or phonotactic rules are configured.
+    pub(crate) fn restricted(&self) -> bool {
+        !self.forbidden_beginnings.is_empty()
+            || !self.forbidden_endings.is_empty()
+            || !self.constraints.is_empty()
+    }
+
+    /// Whether `token` may follow `prefix`, the lowercased name so far, without the name
+    /// beginning with a forbidden string, breaking a phonotactic rule or, if `last`, ending
+    /// with a forbidden string.
+    pub(crate) fn permits(&self, prefix: &str, token: &Token, last: bool) -> bool {
+        let beginnings = self
+            .forbidden_beginnings
+            .iter()
+            .filter(|forbidden| prefix.len() < forbidden.len());
+        let endings = self.forbidden_endings.iter().filter(|_| last);
+        if beginnings.clone().chain(endings.clone()).next().is_none() && self.constraints.is_empty()
+        {
+            return true;
+        }
+        let name = format!("{}{}", prefix, token.text().to_lowercase());
+        !beginnings
+            .clone()
+            .any(|forbidden| name.starts_with(forbidden.as_str()))
+            && !endings
+                .clone()
+                .any(|forbidden| name.ends_with(forbidden.as_str()))
+            && self
+                .constraints
+                .iter()
+                .all(|constraint| constraint.admits(&name, last))
+    }
+
+    /// Texts to avoid when choosing the token following `previous`.
+    pub(crate) fn avoid<'a>(&self, previous: Option<&'a str>) -> Vec<&'a str> {
+        match previous {
+            Some(text) if self.distinct_adjacent => vec![text],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Set how tokens are assembled into names.
+    pub fn with_mode(mut self, mode: Mode) -> TotroConfig {
+        self.mode = mode;
+        self
+    }
+
+    /// How tokens are assembled into names.
+    pub fn mode(&self) -> &Mode {
+        &self.mode
+    }
+
+    /// Table of vowel tokens.
+    pub fn vowels(&self) -> &Table {
+        &self.vowels
+    }
+
+    /// Replace the table of vowel tokens.
+    pub fn with_vowels(mut self, vowels: Table) -> TotroConfig {
+        self.vowels = vowels;
+        self.cache = Cache::default();
+        self
+    }
+
+    /// Mutable table of vowel tokens.
+    pub fn vowels_mut(&mut self) -> &mut Table {
+        self.cache = Cache::default();
+        &mut self.vowels
+    }
+
+    /// Table of consonant tokens.
+    pub fn consonants(&self) -> &Table {
+        &self.consonants
+    }
+
+    /// Replace the table of consonant tokens.
+    pub fn with_consonants(mut self, consonants: Table) -> TotroConfig {
+        self.consonants = consonants;
+        self.cache = Cache::default();
+        self
+    }
+
+    /// Mutable table of consonant tokens.
+    pub fn consonants_mut(&mut self) -> &mut Table {
+        self.cache = Cache::default();
+        &mut self.consonants
+    }
+
+    /// Sampling pools for the vowel and consonant tables.
+    pub(crate) fn pools(&self) -> &(Pools, Pools) {
+        self.cache.get(&self.vowels, &self.consonants)
+    }
+
+    /// Check that names can be generated from this configuration.
+    ///
+    /// Besides ranges, probabilities and tables that can never produce a name, this reports
+    /// [`NominaeError::MinimumLength`] when a one-token name can be drawn but no token may stand
+    /// alone, since generation would otherwise quietly make those names two tokens long.
+    pub fn validate(&self) -> Result<(), NominaeError> {
+        check_probability("vowel start probability", self.vowel_start)?;
+        if let Some(probability) = self.vowel_ending {
+            check_probability("vowel ending probability", probability)?;
+        }
+        if let Mode::Syllabic(syllabic) = &self.mode {
+            syllabic.validate()?;
+        }
+        if self.min > self.max {
+            return Err(NominaeError::InvalidRange {
+                min: self.min as usize,
+                max: self.max as usize,
+            });
+        }
+        if let (Some(min), Some(max)) = (self.min_chars, self.max_chars) {
+            if min > max {
+                return Err(NominaeError::InvalidRange { min, max });
+            }
+        }
+        self.vowels.validate()?;
+        self.consonants.validate()?;
+        let single = self.min == 1 || (self.min == 0 && self.max > 1);
+        let alternating =
+            self.mode == Mode::Alternating && self.length_model == LengthModel::Tokens;
+        let alone = |table: &Table| table.tokens().iter().any(|token| token.allows(AIW));
+        if single && alternating && !alone(&self.vowels) && !alone(&self.consonants) {
+            return Err(NominaeError::MinimumLength {
+                min: self.min as usize,
+                shortest: 2,
+            });
+        }
+        Ok(())
+    }
+
+    /// Generate a name using this configuration.
+    pub fn generate<T: Rng + ?Sized>(&self, rng: &mut T) -> String {
+        let mut name = String::new();
+        generate(self, self.min, self.max, None, &mut name, rng);
+        name
+    }
+
+    /// Generate a name into `buffer`, replacing its contents.
+    ///
+    /// Reusing one buffer across calls avoids allocating a string per name, which matters when
+    /// generating millions of names for world generation.
+    ///
+    /// ```rust
+    /// use nominae::TotroConfig;
+    /// use rand::SeedableRng;
+    /// use rand::rngs::SmallRng;
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    /// let config = TotroConfig::default();
+    /// let mut name = String::with_capacity(32);
+    /// for _ in 0..3 {
+    ///     config.generate_into(&mut name, &mut rng);
+    ///     println!("{}", name);
+    /// }
+    /// ```
+    pub fn generate_into<T: Rng + ?Sized>(&self, buffer: &mut String, rng: &mut T) {
+        buffer.clear();
+        generate(self, self.min, self.max, None, buffer, rng);
+    }
+
+    /// Generate a name using this configuration adjusted by `overrides`.
+    pub fn generate_with<T: Rng + ?Sized>(&self, overrides: &Overrides, rng: &mut T) -> String {
+        let (min, max) = overrides.length.unwrap_or((self.min, self.max));
+        let initial = overrides.initial.filter(|&initial| {
+            [&self.vowels, &self.consonants].iter().any(|table| {
+                table
+                    .tokens()
+                    .iter()
+                    .any(|token| token.allows(BMW) && opens(token, Some(initial)))
+            })
+        });
+        let mut name = String::new();
+        generate(self, min, max, initial, &mut name, rng);
+        name
+    }
+
+    /// Decide whether a name starts with a vowel, honoring a required `initial` letter.
+    ///
+    /// When endings are biased and the name will be `length` tokens long, the start is instead
+    /// chosen so that the last token comes from the table the ending bias draws, since the two
+    /// tables alternate.
+    pub(crate) fn starts_with_vowel<T: Rng + ?Sized>(
+        &self,
+        initial: Option<char>,
+        length: Option<usize>,
+        rng: &mut T,
+    ) -> bool {
+        let vowel = match (self.vowel_ending, length) {
+            (Some(probability), Some(length)) => chance(rng, probability) == (length % 2 == 1),
+            _ => chance(rng, self.vowel_start),
+        };
+        let opening = |table: &Table| {
+            table
+                .tokens()
+                .iter()
+                .any(|token| token.allows(BMW) && opens(token, initial))
+        };
+        match initial {
+            Some(_) => match (opening(&self.vowels), opening(&self.consonants)) {
+                (true, false) => true,
+                (false, true) => false,
+                _ => vowel,
+            },
+            None => vowel,
+        }
+    }
+
+    /// Generate `n` names from a fixed seed.
+    ///
+    /// Because the seed never changes, calling `preview` after each edit shows how that edit
+    /// alone changed the output, which is what interactive table editors want to display. The
+    /// preview uses [`StableRng`], so it is also the same on every platform.
+    pub fn preview(&self, n: usize) -> Vec<String> {
+        let mut rng = StableRng::seed_from_u64(PREVIEW_SEED);
+        (0..n).map(|_| self.generate(&mut rng)).collect()
+    }
+
+    /// Hash of every setting that affects generation, for checking that a stored name is being
+    /// regenerated with the configuration that first produced it.
+    ///
+    /// Like generated names, the fingerprint is the same on every platform and only changes
+    /// between minor versions of the crate.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fingerprint(0);
+        hasher.table(&self.vowels);
+        hasher.table(&self.consonants);
+        hasher.number(u64::from(self.min));
+        hasher.number(u64::from(self.max));
+        match &self.mode {
+            Mode::Alternating => hasher.number(0),
+            Mode::Syllabic(syllabic) => {
+                hasher.number(1);
+                syllabic.fingerprint(&mut hasher);
+            }
+        }
+        hasher.number(match self.length_model {
+            LengthModel::Tokens => 0,
+            LengthModel::Characters => 1,
+        });
+        hasher.number(self.vowel_start.to_bits());
+        hasher.flag(self.distinct_adjacent);
+        hasher.texts(&self.forbidden_beginnings);
+        hasher.texts(&self.forbidden_endings);
+        hasher.number(u64::from(self.version.number()));
+        // Written only when present so that fingerprints from before rules existed still match.
+        if !self.constraints.is_empty() || !self.adjacencies.is_empty() {
+            hasher.number(self.constraints.len() as u64);
+            for constraint in &self.constraints {
+                hasher.constraint(constraint);
+            }
+            hasher.number(self.adjacencies.len() as u64);
+            for adjacency in &self.adjacencies {
+                let (tag, first, second) = match adjacency {
+                    Adjacency::Forbid(first, second) => (0, first, second),
+                    Adjacency::Require(first, second) => (1, first, second),
                 };
-                if idx == 0 && ((token.1 & BOW) != BOW) {
-                    continue;
-                } else if idx == (length - 1) && ((token.1 & EOW) != EOW) {
-                    continue;
-                } else if (token.1 & MOW) != MOW {
+                hasher.number(tag);
+                hasher.class(first);
+                hasher.class(second);
+            }
+        }
+        if self.min_chars.is_some() || self.max_chars.is_some() {
+            hasher.limit(self.min_chars);
+            hasher.limit(self.max_chars);
+        }
+        if let Some(probability) = self.vowel_ending {
+            hasher.number(probability.to_bits());
+        }
+        hasher.0
+    }
+}
+
+/// Hash of a configuration, folding every byte written through the SplitMix64 finalizer.
+///
+/// Numbers are written as little-endian `u64`s and text is prefixed with its length, so no two
+/// configurations write the same bytes and the hash never depends on a `Debug` format.
+pub(crate) struct Fingerprint(u64);
+
+impl Fingerprint {
+    fn bytes(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = mix(self.0.wrapping_add(0x9E37_79B9_7F4A_7C15) ^ u64::from(byte));
+        }
+    }
+
+    pub(crate) fn number(&mut self, value: u64) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    pub(crate) fn flag(&mut self, value: bool) {
+        self.number(u64::from(value));
+    }
+
+    pub(crate) fn text(&mut self, text: &str) {
+        self.number(text.len() as u64);
+        self.bytes(text.as_bytes());
+    }
+
+    fn texts(&mut self, texts: &[String]) {
+        self.number(texts.len() as u64);
+        for text in texts {
+            self.text(text);
+        }
+    }
+
+    /// Every token's text, positions and weight. Attributes do not affect generation and are
+    /// left out.
+    fn table(&mut self, table: &Table) {
+        self.number(table.tokens().len() as u64);
+        for token in table.tokens() {
+            self.text(token.text());
+            self.number(u64::from(token.flags()));
+            self.number(u64::from(token.weight()));
+        }
+    }
+
+    fn constraint(&mut self, constraint: &Constraint) {
+        match constraint {
+            Constraint::MaxVowelRun(max) => {
+                self.number(0);
+                self.number(*max as u64);
+            }
+            Constraint::MaxConsonantRun(max) => {
+                self.number(1);
+                self.number(*max as u64);
+            }
+            Constraint::NoSequence(sequence) => {
+                self.number(2);
+                self.text(sequence);
+            }
+            Constraint::NoEnding(ending) => {
+                self.number(3);
+                self.text(ending);
+            }
+        }
+    }
+
+    fn class(&mut self, class: &TokenClass) {
+        match class {
+            TokenClass::Text(text) => {
+                self.number(0);
+                self.text(text);
+            }
+            TokenClass::Vowel => self.number(1),
+            TokenClass::Consonant => self.number(2),
+            TokenClass::VowelBlend => self.number(3),
+            TokenClass::ConsonantBlend => self.number(4),
+        }
+    }
+
+    fn limit(&mut self, limit: Option<usize>) {
+        match limit {
+            Some(limit) => {
+                self.number(1);
+                self.number(limit as u64);
+            }
+            None => self.number(0),
+        }
+    }
+}
+
+/// Run `f` with the default configuration as of `version`.
+///
+/// Without `std` there is no thread-safe cell to share the defaults through, so they are
+/// rebuilt on every call.
+fn with_default<R, F: FnOnce(&TotroConfig) -> R>(version: ProfileVersion, f: F) -> R {
+    #[cfg(feature = "std")]
+    {
+        static V1: OnceLock<TotroConfig> = OnceLock::new();
+        match version {
+            ProfileVersion::V1 => f(V1.get_or_init(v1_config)),
+        }
+    }
+    #[cfg(not(feature = "std"))]
+    match version {
+        ProfileVersion::V1 => f(&v1_config()),
+    }
+}
+
+/// The built-in tables and defaults frozen as [`ProfileVersion::V1`].
+fn v1_config() -> TotroConfig {
+    TotroConfig {
+        vowels: Table::from_entries(&VOWELS),
+        consonants: Table::from_entries(&CONSONANTS),
+        min: 2,
+        max: 5,
+        length_range: None,
+        mode: Mode::Alternating,
+        length_model: LengthModel::Tokens,
+        vowel_start: 0.5,
+        vowel_ending: None,
+        distinct_adjacent: false,
+        forbidden_beginnings: Vec::new(),
+        forbidden_endings: Vec::new(),
+        constraints: Vec::new(),
+        adjacencies: Vec::new(),
+        min_chars: None,
+        max_chars: None,
+        version: ProfileVersion::V1,
+        cache: Cache::default(),
+    }
+}
+
+/// Receiver of the tokens a name is assembled from, in order.
+pub(crate) trait Sink<'a> {
+    fn emit(&mut self, token: &'a Token);
+}
+
+impl<'a> Sink<'a> for String {
+    #[inline]
+    fn emit(&mut self, token: &'a Token) {
+        self.push_str(token.text());
+    }
+}
+
+impl<'a> Sink<'a> for Vec<&'a Token> {
+    fn emit(&mut self, token: &'a Token) {
+        self.push(token);
+    }
+}
+
+fn generate<T: Rng + ?Sized>(
+    config: &TotroConfig,
+    min: u8,
+    max: u8,
+    initial: Option<char>,
+    output: &mut String,
+    rng: &mut T,
+) {
+    if config.min_chars.is_some() || config.max_chars.is_some() {
+        fit_characters(config, min, max, initial, output, rng);
+    } else {
+        assemble(config, min, max, initial, output, rng);
+    }
+    if let Some(first) = output.get_mut(0..1) {
+        first.make_ascii_uppercase();
+    }
+}
+
+/// Candidates drawn for a name within the character limits before settling for the closest.
+const CHARACTER_ATTEMPTS: usize = 100;
+
+/// Append a name within the character limits of `config` to `output`, drawing until one fits.
+///
+/// When none do, the candidate nearest the limits is used, shortened with [`fit_to`] if too
+/// long.
+fn fit_characters<T: Rng + ?Sized>(
+    config: &TotroConfig,
+    min: u8,
+    max: u8,
+    initial: Option<char>,
+    output: &mut String,
+    rng: &mut T,
+) {
+    let shortest = config.min_chars.unwrap_or(0);
+    let longest = config.max_chars.unwrap_or(usize::MAX);
+    let distance = |name: &str| {
+        let length = name.chars().count();
+        shortest.saturating_sub(length) + length.saturating_sub(longest)
+    };
+    let mut candidate = String::new();
+    let mut closest: Option<(usize, String)> = None;
+    for _ in 0..CHARACTER_ATTEMPTS {
+        candidate.clear();
+        assemble(config, min, max, initial, &mut candidate, rng);
+        let miss = distance(&candidate);
+        if miss == 0 {
+            output.push_str(&candidate);
+            return;
+        }
+        if closest.as_ref().is_none_or(|(best, _)| miss < *best) {
+            closest = Some((miss, candidate.clone()));
+        }
+    }
+    let (_, name) = closest.expect("at least one candidate is drawn");
+    event!(
+        trace,
+        name = name.as_str(),
+        "no candidate within the character limits"
+    );
+    output.push_str(&fit_to(&name, longest));
+}
+
+/// Select the tokens of a name and pass them to `sink`, without capitalizing.
+pub(crate) fn assemble<'a, S: Sink<'a>, T: Rng + ?Sized>(
+    config: &'a TotroConfig,
+    min: u8,
+    max: u8,
+    initial: Option<char>,
+    sink: &mut S,
+    rng: &mut T,
+) {
+    let length = match &config.mode {
+        Mode::Syllabic(model) => model.draw_length(min, max, rng),
+        Mode::Alternating => draw_length(min, max, rng),
+    };
+    let _span = span!("generate", length);
+    match &config.mode {
+        Mode::Alternating if config.length_model == LengthModel::Characters => {
+            alternate_characters(config, length, initial, sink, rng)
+        }
+        Mode::Alternating if length == 1 => single(config, initial, sink, rng),
+        Mode::Alternating if config.restricted() || config.linked() => {
+            constrained(config, length, initial, sink, rng)
+        }
+        Mode::Alternating => {
+            let vowel = config.starts_with_vowel(initial, Some(length), rng);
+            alternate(config, length, vowel, initial, sink, rng);
+        }
+        Mode::Syllabic(model) => model.assemble(config, length, initial, sink, rng),
+    }
+}
+
+/// Lowercase each of `texts`, dropping empty ones.
+fn lowercase<S: Into<String>>(texts: Vec<S>) -> Vec<String> {
+    texts
+        .into_iter()
+        .map(|text| text.into().to_lowercase())
+        .filter(|text| !text.is_empty())
+        .collect()
+}
+
+/// Draw a length from `min..max`, or `min` itself when the two are equal.
+pub(crate) fn draw_length<T: Rng + ?Sized>(min: u8, max: u8, rng: &mut T) -> usize {
+    if min < max {
+        (min + below(rng, (max - min) as u64) as u8) as usize
+    } else if min == max {
+        min as usize
+    } else {
+        panic!("min must be less than or equal to max: {} <= {}", min, max);
+    }
+}
+
+/// Select the only token of a one-token name, which must both begin and end it.
+///
+/// Draws from the pool of tokens that may stand alone, switching to the other table when the
+/// one chosen to start with has none, and generates the shortest possible name instead when
+/// neither has any. Forbidden strings and a required initial are honored with a single weighted
+/// draw over the tokens they permit rather than by rejection.
+fn single<'a, S: Sink<'a>, T: Rng + ?Sized>(
+    config: &'a TotroConfig,
+    initial: Option<char>,
+    sink: &mut S,
+    rng: &mut T,
+) {
+    let (vowel_pools, consonant_pools) = config.pools();
+    let alone = |vowel: bool| {
+        if vowel {
+            vowel_pools.alone().map(|pool| (&config.vowels, pool))
+        } else {
+            consonant_pools
+                .alone()
+                .map(|pool| (&config.consonants, pool))
+        }
+    };
+    let vowel = config.starts_with_vowel(initial, Some(1), rng);
+    let (table, pool) = match alone(vowel).or_else(|| alone(!vowel)) {
+        Some(found) => found,
+        None => {
+            event!(trace, "no token may stand alone, generating two tokens");
+            let vowel = config.starts_with_vowel(initial, Some(2), rng);
+            alternate(config, 2, vowel, initial, sink, rng);
+            return;
+        }
+    };
+    let tokens = table.tokens();
+    let vowel = core::ptr::eq(table, &config.vowels);
+    let permits = |token: &Token| {
+        config.permits("", token, true) && config.follows(None, (token.text(), vowel), true)
+    };
+    let forbid =
+        (config.restricted() || config.linked()) && pool.indices().any(|idx| permits(&tokens[idx]));
+    let permitted = |token: &Token| !forbid || permits(token);
+    let initial = initial.filter(|_| {
+        pool.indices()
+            .any(|idx| opens(&tokens[idx], initial) && permitted(&tokens[idx]))
+    });
+    let index = if !forbid && initial.is_none() {
+        pool.sample(rng)
+    } else {
+        let candidates = || {
+            pool.indices()
+                .filter(|&idx| opens(&tokens[idx], initial) && permitted(&tokens[idx]))
+        };
+        let total = candidates().map(|idx| tokens[idx].weight() as u64).sum();
+        let mut roll = below(rng, total);
+        candidates()
+            .find(|&idx| {
+                let weight = tokens[idx].weight() as u64;
+                roll = match roll.checked_sub(weight) {
+                    Some(rest) => rest,
+                    None => return true,
+                };
+                false
+            })
+            .expect("roll is below the total weight")
+    };
+    sink.emit(&tokens[index]);
+}
+
+/// Most earlier choices [`backtrack`] may take back before giving up.
+const BACKTRACK_BUDGET: usize = 64;
+
+/// Alternate between the tables under forbidden strings or phonotactic or adjacency rules,
+/// going back over earlier choices when one leaves no token that keeps to the rules.
+///
+/// The tokens are first chosen as [`alternate`] chooses them, so that names which never run
+/// into such a dead end are the same as before backtracking existed.
+fn constrained<'a, S: Sink<'a>, T: Rng + ?Sized>(
+    config: &'a TotroConfig,
+    length: usize,
+    initial: Option<char>,
+    sink: &mut S,
+    rng: &mut T,
+) {
+    let vowel = config.starts_with_vowel(initial, Some(length), rng);
+    let mut tokens = Vec::with_capacity(length);
+    if alternate(config, length, vowel, initial, &mut tokens, rng) {
+        if let Some(found) = backtrack(config, length, vowel, initial, rng) {
+            tokens = found;
+        }
+    }
+    for token in tokens {
+        sink.emit(token);
+    }
+}
+
+/// Alternately select `length` tokens, starting from the vowel table if `vowel`, that break
+/// no forbidden string and no phonotactic or adjacency rule, taking back earlier choices
+/// whenever one leaves nothing to follow it.
+///
+/// Returns `None` when no such name turns up within [`BACKTRACK_BUDGET`] steps back.
+fn backtrack<'a, T: Rng + ?Sized>(
+    config: &'a TotroConfig,
+    length: usize,
+    vowel: bool,
+    initial: Option<char>,
+    rng: &mut T,
+) -> Option<Vec<&'a Token>> {
+    let (vowel_pools, consonant_pools) = config.pools();
+    let mut chosen: Vec<&'a Token> = Vec::with_capacity(length);
+    // Untried candidates at each position reached, with the prefix length before it.
+    let mut levels: Vec<(Vec<usize>, usize)> = Vec::with_capacity(length);
+    let mut prefix = String::new();
+    let mut budget = BACKTRACK_BUDGET;
+    while chosen.len() < length {
+        let idx = chosen.len();
+        let vowel = if idx.is_multiple_of(2) { vowel } else { !vowel };
+        let (table, pools) = if vowel {
+            (&config.vowels, vowel_pools)
+        } else {
+            (&config.consonants, consonant_pools)
+        };
+        let tokens = table.tokens();
+        if levels.len() == idx {
+            let pool = pools
+                .get(position(idx, length))
+                .expect("validated tables cover every position");
+            let last = idx + 1 == length;
+            let previous = chosen.last().map(|token| (token.text(), !vowel));
+            let mut candidates: Vec<usize> = pool
+                .indices()
+                .filter(|&i| {
+                    config.permits(&prefix, &tokens[i], last)
+                        && config.follows(previous, (tokens[i].text(), vowel), last)
+                })
+                .collect();
+            // The initial and distinct neighbours are kept to only where some candidate can.
+            prefer(&mut candidates, |i| idx != 0 || opens(&tokens[i], initial));
+            prefer(&mut candidates, |i| {
+                !config.distinct_adjacent
+                    || previous.is_none_or(|(text, _)| tokens[i].text() != text)
+            });
+            levels.push((candidates, prefix.len()));
+        }
+        if levels[idx].0.is_empty() {
+            levels.pop();
+            chosen.pop()?;
+            budget = budget.checked_sub(1)?;
+            prefix.truncate(levels[idx - 1].1);
+            event!(trace, position = idx, "backtracked");
+            continue;
+        }
+        let candidates = &mut levels[idx].0;
+        let total = candidates.iter().map(|&i| tokens[i].weight() as u64).sum();
+        let mut roll = below(rng, total);
+        let pick = candidates
+            .iter()
+            .position(|&i| {
+                let weight = tokens[i].weight() as u64;
+                roll = match roll.checked_sub(weight) {
+                    Some(rest) => rest,
+                    None => return true,
+                };
+                false
+            })
+            .expect("roll is below the total weight");
+        let token = &tokens[candidates.swap_remove(pick)];
+        prefix.push_str(&token.text().to_lowercase());
+        chosen.push(token);
+    }
+    Some(chosen)
+}
+
+/// Keep only the candidates that are `preferred`, unless none are.
+fn prefer<F: Fn(usize) -> bool>(candidates: &mut Vec<usize>, preferred: F) {
+    if candidates.iter().any(|&idx| preferred(idx)) {
+        candidates.retain(|&idx| preferred(idx));
+    }
+}
+
+/// Alternately select tokens from the vowel and consonant tables, starting from the vowel table
+/// if `vowel`, and pass them to `sink`.
+///
+/// Returns whether a forbidden string or a phonotactic or adjacency rule had to be relaxed
+/// because no token at some position could keep to it.
+fn alternate<'a, S: Sink<'a>, T: Rng + ?Sized>(
+    config: &'a TotroConfig,
+    length: usize,
+    mut vowel: bool,
+    initial: Option<char>,
+    sink: &mut S,
+    rng: &mut T,
+) -> bool {
+    let (vowel_pools, consonant_pools) = config.pools();
+    let mut relaxed = false;
+    let mut previous: Option<&str> = None;
+    let restricted = config.restricted();
+    let mut prefix = String::new();
+    for idx in 0..length {
+        let position = position(idx, length);
+        let last = idx + 1 == length;
+        let (table, pools) = if vowel {
+            (&config.vowels, vowel_pools)
+        } else {
+            (&config.consonants, consonant_pools)
+        };
+        let tokens = table.tokens();
+        let pool = pools
+            .get(position)
+            .expect("validated tables cover every position");
+        // Only keep to forbidden beginnings and endings if some token here can.
+        let forbid = restricted
+            && pool
+                .indices()
+                .any(|idx| config.permits(&prefix, &tokens[idx], last));
+        let spelled = |token: &Token| !forbid || config.permits(&prefix, token, last);
+        // Tokens at the next position, to look ahead to for adjacency rules.
+        let next = (!last).then(|| {
+            let (table, pools) = if vowel {
+                (&config.consonants, consonant_pools)
+            } else {
+                (&config.vowels, vowel_pools)
+            };
+            let pool = pools
+                .get(self::position(idx + 1, length))
+                .expect("validated tables cover every position");
+            (table.tokens(), pool)
+        });
+        let fits = |token: &Token| {
+            let placed = (token.text(), vowel);
+            config.follows(previous.map(|text| (text, !vowel)), placed, last)
+                && next.is_none_or(|(tokens, pool)| {
+                    let last = idx + 2 == length;
+                    pool.indices()
+                        .any(|idx| config.follows(Some(placed), (tokens[idx].text(), !vowel), last))
+                })
+        };
+        // Likewise only keep to adjacency rules if some token here can.
+        let linked = config.linked()
+            && pool
+                .indices()
+                .any(|idx| spelled(&tokens[idx]) && fits(&tokens[idx]));
+        let permits = |token: &Token| spelled(token) && (!linked || fits(token));
+        relaxed |= (restricted && !forbid) || (config.linked() && !linked);
+        // Only avoid repeating the previous token if something else can take its place.
+        let avoid = previous.filter(|&text| {
+            config.distinct_adjacent
+                && pool
+                    .indices()
+                    .any(|idx| tokens[idx].text() != text && permits(&tokens[idx]))
+        });
+        // Only require the initial if some token at this position provides it.
+        let initial = initial.filter(|_| {
+            idx == 0
+                && pool
+                    .indices()
+                    .any(|idx| opens(&tokens[idx], initial) && permits(&tokens[idx]))
+        });
+        let token = if avoid.is_none() && initial.is_none() && !forbid && !linked {
+            &tokens[pool.sample(rng)]
+        } else {
+            loop {
+                let token = &tokens[pool.sample(rng)];
+                if avoid == Some(token.text()) || !opens(token, initial) || !permits(token) {
+                    event!(trace, token = token.text(), position, "rejected token");
                     continue;
                 }
+                break token;
+            }
+        };
+        vowel = !vowel;
+        sink.emit(token);
+        previous = Some(token.text());
+        if restricted {
+            prefix.push_str(&token.text().to_lowercase());
+        }
+    }
+    relaxed
+}
+
+/// Alternately select tokens from the vowel and consonant tables until `length` characters have
+/// been emitted, only considering tokens that fit in the characters remaining.
+fn alternate_characters<'a, S: Sink<'a>, T: Rng + ?Sized>(
+    config: &'a TotroConfig,
+    length: usize,
+    initial: Option<char>,
+    sink: &mut S,
+    rng: &mut T,
+) {
+    let mut used = 0;
+    let mut vowel = config.starts_with_vowel(initial, None, rng);
+    let mut previous: Option<&str> = None;
+    let restricted = config.restricted();
+    let mut prefix = String::new();
+    while used < length {
+        let remaining = length - used;
+        let table = if vowel {
+            &config.vowels
+        } else {
+            &config.consonants
+        };
+        let candidates = table.tokens().iter().filter(|token| {
+            let chars = token.text().chars().count();
+            let mut position = if used == 0 { BOW } else { 0 };
+            if chars == remaining {
+                position |= EOW;
+            } else if chars > remaining {
+                return false;
+            } else if used != 0 {
+                position |= MOW;
+            }
+            token.allows(position)
+        });
+        let candidates: Vec<&Token> = if restricted {
+            let permitted: Vec<&Token> = candidates
+                .clone()
+                .filter(|token| {
+                    let last = token.text().chars().count() == remaining;
+                    config.permits(&prefix, token, last)
+                })
+                .collect();
+            if permitted.is_empty() {
+                candidates.collect()
+            } else {
+                permitted
+            }
+        } else {
+            candidates.collect()
+        };
+        let candidates: Vec<&Token> = if config.linked() {
+            let fitting: Vec<&Token> = candidates
+                .iter()
+                .copied()
+                .filter(|token| {
+                    let last = token.text().chars().count() == remaining;
+                    let previous = previous.map(|text| (text, !vowel));
+                    config.follows(previous, (token.text(), vowel), last)
+                })
+                .collect();
+            if fitting.is_empty() {
+                candidates
+            } else {
+                fitting
+            }
+        } else {
+            candidates
+        };
+        let candidates = candidates.iter().copied();
+        let avoid = config.avoid(previous);
+        let chosen = match initial {
+            Some(_) if used == 0 => {
+                let opening = candidates.clone().filter(|token| opens(token, initial));
+                choose_distinct(opening, &avoid, rng)
+                    .or_else(|| choose_distinct(candidates, &avoid, rng))
+            }
+            _ => choose_distinct(candidates, &avoid, rng),
+        };
+        match chosen {
+            Some(token) => {
+                used += token.text().chars().count();
+                sink.emit(token);
+                if restricted {
+                    prefix.push_str(&token.text().to_lowercase());
+                }
+                previous = Some(token.text());
                 vowel = !vowel;
-                output.push_str(&token.0);
-                break;
             }
+            None => break,
         }
-        output.get_mut(0..1).unwrap().make_ascii_uppercase();
-        output
     }
 }
 
-const CONSONANTS: [(&str, u8); 91] = [
+/// Whether `token` can open a name that must start with `initial`.
+pub(crate) fn opens(token: &Token, initial: Option<char>) -> bool {
+    initial.is_none_or(|initial| {
+        token
+            .text()
+            .chars()
+            .next()
+            .is_some_and(|first| first.eq_ignore_ascii_case(&initial))
+    })
+}
+
+/// Position flags a token must carry to be placed at `idx` in a word of `length` tokens.
+///
+/// As in the original algorithm every token must allow `MOW`, and the first and last tokens
+/// must also allow `BOW` and `EOW`.
+pub(crate) fn position(idx: usize, length: usize) -> u8 {
+    let mut position = MOW;
+    if idx == 0 {
+        position |= BOW;
+    }
+    if idx + 1 == length {
+        position |= EOW;
+    }
+    position
+}
+
+const CONSONANTS: [(&str, u8, u32); 52] = [
     // Letter Singles
-    ("b", AIW), ("c", AIW), ("d", AIW), ("f", AIW),
-    ("g", AIW), ("h", AIW), ("j", AIW), ("k", AIW),
-    ("l", AIW), ("m", AIW), ("n", AIW), ("p", AIW),
-    ("qu", BMW), ("r", AIW), ("s", AIW), ("t", AIW),
-    ("v", AIW), ("w", AIW), ("x", AIW), ("y", AIW),
-    ("z", AIW),
-    ("sc", AIW),
+    ("b", AIW, 3), ("c", AIW, 3), ("d", AIW, 3), ("f", AIW, 3),
+    ("g", AIW, 3), ("h", AIW, 3), ("j", AIW, 3), ("k", AIW, 3),
+    ("l", AIW, 3), ("m", AIW, 3), ("n", AIW, 3), ("p", AIW, 3),
+    ("qu", BMW, 1), ("r", AIW, 3), ("s", AIW, 3), ("t", AIW, 3),
+    ("v", AIW, 3), ("w", AIW, 3), ("x", AIW, 1), ("y", AIW, 1),
+    ("z", AIW, 1),
+    ("sc", AIW, 1),
     // Blends
-    ("ch", AIW), ("gh", AIW), ("ph", AIW), ("sh", AIW),
-    ("th", AIW), ("wh", BMW), ("ck", BEW), ("nk", BEW),
-    ("rk", BEW), ("sk", AIW), ("wk", NIW),
-    ("cl", BMW), ("fl", BMW), ("gl", BMW), ("kl", BMW),
-    ("ll", BMW), ("pl", BMW), ("sl", BMW),
-    ("br", BMW), ("cr", BMW), ("dr", BMW), ("fr", BMW),
-    ("gr", BMW), ("kr", BMW), ("pr", BMW), ("sr", BMW),
-    ("tr", BMW),
-    ("ss", BEW),
-    ("st", AIW),
-    ("str", BMW),
-    // More copies to increase frequency
-    ("b", AIW), ("c", AIW), ("d", AIW), ("f", AIW),
-    ("g", AIW), ("h", AIW), ("j", AIW), ("k", AIW),
-    ("l", AIW), ("m", AIW), ("n", AIW), ("p", AIW),
-    ("r", AIW), ("s", AIW), ("t", AIW), ("v", AIW),
-    ("w", AIW), ("b", AIW), ("c", AIW), ("d", AIW),
-    ("f", AIW), ("g", AIW), ("h", AIW), ("j", AIW),
-    ("k", AIW), ("l", AIW), ("m", AIW), ("n", AIW),
-    ("p", AIW), ("r", AIW), ("s", AIW), ("t", AIW),
-    ("v", AIW), ("w", AIW), ("br", BMW), ("dr", BMW),
-    ("fr", BMW), ("gr", BMW), ("kr", BMW),
+    ("ch", AIW, 1), ("gh", AIW, 1), ("ph", AIW, 1), ("sh", AIW, 1),
+    ("th", AIW, 1), ("wh", BMW, 1), ("ck", BEW, 1), ("nk", BEW, 1),
+    ("rk", BEW, 1), ("sk", AIW, 1), ("wk", NIW, 1),
+    ("cl", BMW, 1), ("fl", BMW, 1), ("gl", BMW, 1), ("kl", BMW, 1),
+    ("ll", BMW, 1), ("pl", BMW, 1), ("sl", BMW, 1),
+    ("br", BMW, 2), ("cr", BMW, 1), ("dr", BMW, 2), ("fr", BMW, 2),
+    ("gr", BMW, 2), ("kr", BMW, 2), ("pr", BMW, 1), ("sr", BMW, 1),
+    ("tr", BMW, 1),
+    ("ss", BEW, 1),
+    ("st", AIW, 1),
+    ("str", BMW, 1),
 ];
 
-const VOWELS: [(&str, u8); 83] = [
-    ("a", AIW), ("e", AIW), ("i", AIW), ("o", AIW), ("u", AIW),
-    ("a", AIW), ("e", AIW), ("i", AIW), ("o", AIW), ("u", AIW),
-    ("a", AIW), ("e", AIW), ("i", AIW), ("o", AIW), ("u", AIW),
-    ("a", AIW), ("e", AIW), ("i", AIW), ("o", AIW), ("u", AIW),
-    ("a", AIW), ("e", AIW), ("i", AIW), ("o", AIW), ("u", AIW),
-    ("a", AIW), ("e", AIW), ("i", AIW), ("o", AIW), ("u", AIW),
-    ("a", AIW), ("e", AIW), ("i", AIW), ("o", AIW), ("u", AIW),
-    ("a", AIW), ("e", AIW), ("i", AIW), ("o", AIW), ("u", AIW),
-    ("a", AIW), ("e", AIW), ("i", AIW), ("o", AIW), ("u", AIW),
-    ("a", AIW), ("e", AIW), ("i", AIW), ("o", AIW), ("u", AIW),
-    ("a", AIW), ("e", AIW), ("i", AIW), ("o", AIW), ("u", AIW),
-    ("a", AIW), ("e", AIW), ("i", AIW), ("o", AIW), ("u", AIW),
+const VOWELS: [(&str, u8, u32); 28] = [
+    ("a", AIW, 12), ("e", AIW, 12), ("i", AIW, 12), ("o", AIW, 12), ("u", AIW, 12),
     // Vowel Blends
-    ("aa", AIW), ("ae", AIW), ("ai", AIW), ("ao", AIW), ("au", AIW),
-    ("ea", AIW), ("ee", AIW), ("ei", AIW), ("eo", AIW), ("eu", AIW),
-    ("ia", AIW), ("ie", AIW), ("ii", AIW), ("io", AIW), ("iu", AIW),
-    ("oa", AIW), ("oe", AIW), ("oi", AIW), ("oo", AIW), ("ou", AIW),
-    ("eau", AIW), ("'", MEW), ("y", AIW),
+    ("aa", AIW, 1), ("ae", AIW, 1), ("ai", AIW, 1), ("ao", AIW, 1), ("au", AIW, 1),
+    ("ea", AIW, 1), ("ee", AIW, 1), ("ei", AIW, 1), ("eo", AIW, 1), ("eu", AIW, 1),
+    ("ia", AIW, 1), ("ie", AIW, 1), ("ii", AIW, 1), ("io", AIW, 1), ("iu", AIW, 1),
+    ("oa", AIW, 1), ("oe", AIW, 1), ("oi", AIW, 1), ("oo", AIW, 1), ("ou", AIW, 1),
+    ("eau", AIW, 1), ("'", MEW, 1), ("y", AIW, 1),
 ];
 
 #[cfg(test)]
 mod tests {
-    use super::Totro;
-    use rand::SeedableRng;
+    use super::{position, LengthModel, LengthRange, Mode, Overrides, Totro, TotroConfig};
+    use crate::error::NominaeError;
+    use crate::phonotactics::Constraint;
+    use crate::syllable::Syllabic;
+    use crate::table::{Table, Token, AIW, BMW, BOW, MEW, MOW};
     use rand::rngs::SmallRng;
+    use rand::SeedableRng;
 
     #[test]
     fn test_normal() {
@@ -162,9 +1625,327 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
+    #[should_panic(expected = "min must be less than or equal to max: 5 <= 3")]
     fn test_panic() {
         let mut rng = SmallRng::seed_from_u64(0);
         Totro::generate(5, 3, &mut rng);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_position() {
+        assert_eq!(position(0, 1), AIW);
+        assert_eq!(position(0, 3), BMW);
+        assert_eq!(position(1, 3), MOW);
+        assert_eq!(position(2, 3), MEW);
+        // Tokens that may only begin and end words, such as "ck", are never drawn.
+        let mut rng = SmallRng::seed_from_u64(0);
+        assert!((0..500).all(|_| !Totro::generate(2, 5, &mut rng).contains("ck")));
+    }
+
+    #[test]
+    fn test_generate_many() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let expected: Vec<String> = (0..20).map(|_| Totro::generate(2, 5, &mut rng)).collect();
+        let mut rng = SmallRng::seed_from_u64(0);
+        assert_eq!(Totro::generate_many(2, 5, 20, &mut rng), expected);
+        let iterated: Vec<String> = Totro::iter(2, 5, SmallRng::seed_from_u64(0))
+            .take(20)
+            .collect();
+        assert_eq!(iterated, expected);
+    }
+
+    #[test]
+    fn test_for_id() {
+        let config = TotroConfig::default();
+        assert_eq!(Totro::for_id(7, &config), Totro::for_id(7, &config));
+        let names: Vec<String> = (0..20).map(|id| Totro::for_id(id, &config)).collect();
+        assert!(names.windows(2).any(|pair| pair[0] != pair[1]));
+        let config = config.with_length(3, 3);
+        assert_ne!(Totro::for_id(7, &config), Totro::for_id(8, &config));
+    }
+
+    #[test]
+    fn test_try_generate() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        assert_eq!(
+            Totro::try_generate(5, 3, &mut rng),
+            Err(NominaeError::InvalidRange { min: 5, max: 3 })
+        );
+        let name = Totro::try_generate(2, 5, &mut SmallRng::seed_from_u64(0)).unwrap();
+        assert_eq!(name, Totro::generate(2, 5, &mut rng));
+    }
+
+    #[test]
+    fn test_character_length() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let config = TotroConfig::default()
+            .with_length(4, 7)
+            .with_length_model(LengthModel::Characters);
+        for _ in 0..200 {
+            let length = config.generate(&mut rng).chars().count();
+            assert!((4..7).contains(&length), "{} characters", length);
+        }
+    }
+
+    #[test]
+    fn test_character_limits() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let config = TotroConfig::default()
+            .with_length(2, 6)
+            .with_min_chars(5)
+            .with_max_chars(7);
+        for _ in 0..200 {
+            let length = config.generate(&mut rng).chars().count();
+            assert!((5..=7).contains(&length), "{} characters", length);
+        }
+        let cramped = TotroConfig::default().with_length(5, 5).with_max_chars(3);
+        assert!(cramped.generate(&mut rng).chars().count() <= 3);
+        assert_eq!(
+            TotroConfig::default()
+                .with_min_chars(9)
+                .with_max_chars(8)
+                .validate(),
+            Err(NominaeError::InvalidRange { min: 9, max: 8 })
+        );
+        assert_ne!(
+            TotroConfig::default().with_max_chars(16).fingerprint(),
+            TotroConfig::default().fingerprint()
+        );
+    }
+
+    #[test]
+    fn test_length_range() {
+        let inclusive = LengthRange::from(2..=5);
+        assert_eq!(inclusive.bounds(), (2, 6));
+        assert!(inclusive.contains(5) && !inclusive.contains(6));
+        assert_eq!(LengthRange::from(2..5).bounds(), (2, 5));
+        assert_eq!(LengthRange::inclusive(3, 3).bounds(), (3, 3));
+        assert_eq!(LengthRange::inclusive(4, 255).bounds(), (4, 255));
+        let mut rng = SmallRng::seed_from_u64(0);
+        let config = TotroConfig::default()
+            .with_length_model(LengthModel::Characters)
+            .with_length_range(3..=4);
+        assert_eq!(config.length_range(), LengthRange::inclusive(3, 4));
+        assert_eq!(
+            config.clone().with_length_range(4..=255).length_range(),
+            LengthRange::inclusive(4, 255)
+        );
+        assert_eq!(
+            config.clone().with_length(3, 5).length_range(),
+            LengthRange::exclusive(3, 5)
+        );
+        let lengths: Vec<usize> = (0..100)
+            .map(|_| config.generate(&mut rng).chars().count())
+            .collect();
+        assert!(lengths.iter().all(|length| (3..=4).contains(length)));
+        assert!(lengths.contains(&4));
+    }
+
+    #[test]
+    fn test_vowel_start() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let vowels = TotroConfig::default().with_vowel_start(1.0);
+        let consonants = TotroConfig::default().with_vowel_start(0.0);
+        for _ in 0..100 {
+            let name = vowels.generate(&mut rng).to_ascii_lowercase();
+            assert!(vowels
+                .vowels()
+                .tokens()
+                .iter()
+                .any(|t| name.starts_with(t.text())));
+            let name = consonants.generate(&mut rng).to_ascii_lowercase();
+            assert!(!name.starts_with(|c| "aeiou'".contains(c)), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_alphabetic() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let config = TotroConfig::alphabetic().with_length(3, 8);
+        assert!(config.vowels().get("'").is_none());
+        for _ in 0..500 {
+            assert!(config.generate(&mut rng).chars().all(char::is_alphabetic));
+        }
+    }
+
+    #[test]
+    fn test_distinct_adjacent() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut config = TotroConfig::default()
+            .with_length(4, 4)
+            .with_distinct_adjacent(true);
+        // "y" is both a vowel and a consonant, so it is the only token that can repeat.
+        config.vowels_mut().set_weight("y", 1000).unwrap();
+        config.consonants_mut().set_weight("y", 1000).unwrap();
+        for _ in 0..100 {
+            assert!(!config
+                .generate(&mut rng)
+                .to_ascii_lowercase()
+                .contains("yy"));
+        }
+    }
+
+    #[test]
+    fn test_preview() {
+        let mut config = TotroConfig::default().with_length(3, 3);
+        let before = config.preview(20);
+        assert_eq!(before, config.preview(20));
+        config.vowels_mut().remove("'").unwrap();
+        let after = config.preview(20);
+        assert!(after.iter().all(|name| !name.contains('\'')));
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(TotroConfig::default().validate(), Ok(()));
+        assert_eq!(
+            TotroConfig::default().with_length(4, 2).validate(),
+            Err(NominaeError::InvalidRange { min: 4, max: 2 })
+        );
+        assert_eq!(
+            TotroConfig::default().with_vowel_start(1.5).validate(),
+            Err(NominaeError::OutOfRange(
+                "vowel start probability 1.5 must be within 0..=1".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_single_token() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let vowels = Table::new(vec![Token::new("a", AIW), Token::new("e", MOW)]).unwrap();
+        let consonants = Table::new(vec![Token::new("k", BMW), Token::new("r", MEW)]).unwrap();
+        let config = TotroConfig::new(vowels, consonants)
+            .with_length(1, 1)
+            .with_vowel_start(0.0);
+        assert_eq!(config.validate(), Ok(()));
+        assert!((0..100).all(|_| config.generate(&mut rng) == "A"));
+        let vowels = Table::new(vec![Token::new("a", BMW), Token::new("e", MEW)]).unwrap();
+        let config = config.with_vowels(vowels);
+        assert_eq!(
+            config.validate(),
+            Err(NominaeError::MinimumLength {
+                min: 1,
+                shortest: 2
+            })
+        );
+        assert!((0..100).all(|_| config.generate(&mut rng).len() == 2));
+        assert_eq!(
+            config.clone().with_length(0, 3).validate(),
+            Err(NominaeError::MinimumLength {
+                min: 0,
+                shortest: 2
+            })
+        );
+        let forbidden = TotroConfig::alphabetic()
+            .with_length(1, 1)
+            .with_forbidden_beginnings(vec!["a", "e", "i", "o"]);
+        assert!((0..100).all(|_| {
+            forbidden
+                .generate_with(&Overrides::default().with_initial('u'), &mut rng)
+                .starts_with('U')
+        }));
+    }
+
+    #[test]
+    fn test_overrides() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let alternating = TotroConfig::default();
+        let syllabic = TotroConfig::default().with_mode(Mode::Syllabic(Syllabic::default()));
+        let characters = TotroConfig::default().with_length_model(LengthModel::Characters);
+        for config in [alternating, syllabic, characters].iter() {
+            for &initial in ['v', 'A', 'S'].iter() {
+                let overrides = Overrides::default().with_length(2, 4).with_initial(initial);
+                for _ in 0..50 {
+                    let name = config.generate_with(&overrides, &mut rng);
+                    assert!(
+                        name.starts_with(initial.to_ascii_uppercase()),
+                        "{} {}",
+                        initial,
+                        name
+                    );
+                }
+            }
+        }
+        let mut rng = SmallRng::seed_from_u64(0);
+        let plain: Vec<String> = (0..20)
+            .map(|_| TotroConfig::default().generate(&mut rng))
+            .collect();
+        let mut rng = SmallRng::seed_from_u64(0);
+        let unchanged: Vec<String> = (0..20)
+            .map(|_| TotroConfig::default().generate_with(&Overrides::default(), &mut rng))
+            .collect();
+        assert_eq!(plain, unchanged);
+    }
+
+    #[test]
+    fn test_forbidden() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let modes = [
+            TotroConfig::default(),
+            TotroConfig::default()
+                .with_length(3, 9)
+                .with_length_model(LengthModel::Characters),
+            TotroConfig::default().with_mode(Mode::Syllabic(Syllabic::default())),
+        ];
+        for config in modes.iter() {
+            let config = config
+                .clone()
+                .with_forbidden_beginnings(vec!["X", "e", "sh"])
+                .with_forbidden_endings(vec!["q", "'", "a"]);
+            assert_eq!(config.forbidden_beginnings(), ["x", "e", "sh"]);
+            for _ in 0..500 {
+                let name = config.generate(&mut rng).to_lowercase();
+                assert!(
+                    !["x", "e", "sh"].iter().any(|f| name.starts_with(f)),
+                    "{}",
+                    name
+                );
+                assert!(
+                    !["q", "'", "a"].iter().any(|f| name.ends_with(f)),
+                    "{}",
+                    name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_backtracking() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let vowels = Table::new(vec![Token::new("a", AIW), Token::new("o", AIW)]).unwrap();
+        let consonants = Table::new(vec![Token::new("k", AIW), Token::new("t", AIW)]).unwrap();
+        let config = TotroConfig::new(vowels, consonants)
+            .with_length(3, 3)
+            .with_vowel_start(0.0);
+        // Once "a" is chosen nothing can end the name, so it must be taken back.
+        let dead_end = config
+            .clone()
+            .with_constraint(Constraint::NoSequence("ak".into()))
+            .with_constraint(Constraint::NoSequence("at".into()));
+        for _ in 0..100 {
+            assert_eq!(&dead_end.generate(&mut rng)[1..2], "o");
+        }
+        // Rules no name can keep to are relaxed once the search runs out.
+        let impossible = config
+            .with_constraint(Constraint::NoSequence("a".into()))
+            .with_constraint(Constraint::NoSequence("o".into()));
+        assert_eq!(impossible.generate(&mut rng).len(), 3);
+    }
+
+    #[test]
+    fn test_custom_tables() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let vowels = Table::new(vec![Token::new("u", AIW)]).unwrap();
+        let consonants =
+            Table::new(vec![Token::new("gr", BOW | MOW), Token::new("k", AIW)]).unwrap();
+        let config = TotroConfig::new(vowels.clone(), consonants).with_length(3, 6);
+        assert_eq!(config.vowels(), &vowels);
+        for _ in 0..100 {
+            let name = config.generate(&mut rng).to_lowercase();
+            assert!(!name.ends_with("gr"), "{}", name);
+            assert!(name.chars().all(|c| "ugrk".contains(c)), "{}", name);
+        }
+    }
+}
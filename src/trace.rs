@@ -0,0 +1,57 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Optional Tracing Instrumentation
+//!
+//! With the `tracing` feature enabled these macros forward to the [`tracing`] crate under the
+//! `nominae` target, so bulk pipelines can see where generation spends its attempts. Without it
+//! they expand to nothing, leaving the hot path untouched.
+//!
+//! [`tracing`]: https://docs.rs/tracing
+
+/// Guard returned by `span!` when tracing is disabled.
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct Disabled;
+
+/// Enter a trace-level span that lasts until the returned guard is dropped.
+#[cfg(feature = "tracing")]
+macro_rules! span {
+    ($($arg:tt)+) => {
+        tracing::trace_span!(target: "nominae", $($arg)+).entered()
+    };
+}
+
+/// Enter a trace-level span that lasts until the returned guard is dropped.
+#[cfg(not(feature = "tracing"))]
+macro_rules! span {
+    ($($arg:tt)+) => {
+        crate::trace::Disabled
+    };
+}
+
+/// Emit an event at the given level, such as `event!(debug, attempts, "gave up")`.
+#[cfg(feature = "tracing")]
+macro_rules! event {
+    ($level:ident, $($arg:tt)+) => {
+        tracing::$level!(target: "nominae", $($arg)+)
+    };
+}
+
+/// Emit an event at the given level, such as `event!(debug, attempts, "gave up")`.
+#[cfg(not(feature = "tracing"))]
+macro_rules! event {
+    ($level:ident, $($arg:tt)+) => {{}};
+}
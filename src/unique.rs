@@ -0,0 +1,248 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Session-Wide Unique Names
+
+use crate::error::NominaeError;
+use crate::generator::NameGenerator;
+use crate::normalize::normalize;
+use crate::stable::mix;
+use rand::RngCore;
+use std::collections::HashSet;
+use std::f64::consts::LN_2;
+
+/// Candidates drawn for each name before giving up, unless set otherwise.
+const UNIQUE_ATTEMPTS: usize = 100;
+
+/// Names handed out so far, in [`normalize`]d form.
+#[derive(Clone, Debug)]
+enum Seen {
+    Exact(HashSet<String>),
+    Approximate(Bloom),
+}
+
+/// A Bloom filter: a fixed-size set that never forgets a member but may claim non-members
+/// now and then.
+#[derive(Clone, Debug)]
+struct Bloom {
+    bits: Vec<u64>,
+    hashes: u32,
+}
+
+impl Bloom {
+    /// A filter sized to hold `capacity` members while claiming a non-member at most
+    /// `false_positives` of the time.
+    fn new(capacity: usize, false_positives: f64) -> Bloom {
+        let capacity = capacity.max(1) as f64;
+        let bits = (-capacity * false_positives.ln() / (LN_2 * LN_2))
+            .ceil()
+            .max(64.0);
+        let hashes = (bits / capacity * LN_2).round().max(1.0) as u32;
+        Bloom {
+            bits: vec![0; (bits as usize).div_ceil(64)],
+            hashes,
+        }
+    }
+
+    /// Bit positions of `key`, by double hashing.
+    fn positions<'a>(&'a self, key: &str) -> impl Iterator<Item = usize> + 'a {
+        let first = key.bytes().fold(0xCBF2_9CE4_8422_2325, |hash, byte| {
+            mix(hash ^ u64::from(byte))
+        });
+        let second = mix(first) | 1;
+        let len = self.bits.len() as u64 * 64;
+        (0..u64::from(self.hashes))
+            .map(move |i| (first.wrapping_add(i.wrapping_mul(second)) % len) as usize)
+    }
+
+    fn contains(&self, key: &str) -> bool {
+        self.positions(key)
+            .all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    fn insert(&mut self, key: &str) {
+        let positions: Vec<usize> = self.positions(key).collect();
+        for bit in positions {
+            self.bits[bit / 64] |= 1 << (bit % 64);
+        }
+    }
+}
+
+/// A generator that never hands out the same name twice, such as for the characters of every
+/// player on a server.
+///
+/// Names are compared in [`normalize`]d form, so "Velmar" also rules out "velmar" and
+/// "Vel'mar". Every name handed out is remembered exactly unless
+/// [`with_bloom`](UniqueNames::with_bloom) trades a little accuracy for a fixed memory size.
+///
+/// ```rust
+/// use nominae::{TotroConfig, UniqueNames};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let mut names = UniqueNames::new(TotroConfig::default());
+/// names.reserve("Velmar");
+/// for _ in 0..100 {
+///     let name = names.generate(&mut rng).unwrap();
+///     assert_ne!(name, "Velmar");
+/// }
+/// assert_eq!(names.len(), 101);
+/// ```
+#[derive(Clone, Debug)]
+pub struct UniqueNames<G> {
+    generator: G,
+    seen: Seen,
+    len: usize,
+    attempts: usize,
+}
+
+impl<G: NameGenerator> UniqueNames<G> {
+    /// Unique names from `generator`, remembering each exactly.
+    pub fn new(generator: G) -> UniqueNames<G> {
+        UniqueNames {
+            generator,
+            seen: Seen::Exact(HashSet::new()),
+            len: 0,
+            attempts: UNIQUE_ATTEMPTS,
+        }
+    }
+
+    /// Remember names in a Bloom filter sized for `capacity` names instead, for sessions too
+    /// large to hold every name in memory.
+    ///
+    /// Names are still never repeated, but up to `false_positives` of fresh candidates are
+    /// passed over as if already taken. Names remembered so far are forgotten.
+    pub fn with_bloom(mut self, capacity: usize, false_positives: f64) -> UniqueNames<G> {
+        assert!(
+            false_positives > 0.0 && false_positives < 1.0,
+            "false positive rate must be within 0..1, exclusive"
+        );
+        self.seen = Seen::Approximate(Bloom::new(capacity, false_positives));
+        self.len = 0;
+        self
+    }
+
+    /// Set how many candidates are drawn for each name before giving up. Defaults to 100.
+    pub fn with_attempts(mut self, attempts: usize) -> UniqueNames<G> {
+        assert!(attempts > 0, "at least one attempt is required");
+        self.attempts = attempts;
+        self
+    }
+
+    /// Mark `name` as taken, such as a name already in the database.
+    pub fn reserve(&mut self, name: &str) {
+        let key = normalize(name);
+        if self.contains_key(&key) {
+            return;
+        }
+        match &mut self.seen {
+            Seen::Exact(names) => {
+                names.insert(key);
+            }
+            Seen::Approximate(bloom) => bloom.insert(&key),
+        }
+        self.len += 1;
+    }
+
+    /// Whether `name`, or a name that normalizes the same, has been handed out or reserved.
+    ///
+    /// With a Bloom filter this is sometimes true for names never seen.
+    pub fn contains(&self, name: &str) -> bool {
+        self.contains_key(&normalize(name))
+    }
+
+    fn contains_key(&self, key: &str) -> bool {
+        match &self.seen {
+            Seen::Exact(names) => names.contains(key),
+            Seen::Approximate(bloom) => bloom.contains(key),
+        }
+    }
+
+    /// Number of names handed out or reserved.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no name has been handed out or reserved yet.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Generate a name not handed out or reserved before, or
+    /// [`NominaeError::RetriesExhausted`] when the generator seems to have run out of new
+    /// names.
+    pub fn generate(&mut self, rng: &mut dyn RngCore) -> Result<String, NominaeError> {
+        for _ in 0..self.attempts {
+            let name = self.generator.generate(rng);
+            if !self.contains(&name) {
+                self.reserve(&name);
+                return Ok(name);
+            }
+            event!(trace, name = name.as_str(), "name taken");
+        }
+        Err(NominaeError::RetriesExhausted {
+            constraint: "unique name".into(),
+            attempts: self.attempts,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UniqueNames;
+    use crate::error::NominaeError;
+    use crate::table::{Table, Token, AIW};
+    use crate::totro::TotroConfig;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_exhausted() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let vowels = Table::new(vec![Token::new("a", AIW), Token::new("o", AIW)]).unwrap();
+        let consonants = Table::new(vec![Token::new("k", AIW)]).unwrap();
+        let config = TotroConfig::new(vowels, consonants)
+            .with_length(2, 2)
+            .with_vowel_start(0.0);
+        let mut names = UniqueNames::new(config);
+        let mut first = vec![names.generate(&mut rng).unwrap()];
+        first.push(names.generate(&mut rng).unwrap());
+        first.sort();
+        assert_eq!(first, ["Ka", "Ko"]);
+        assert_eq!(
+            names.generate(&mut rng),
+            Err(NominaeError::RetriesExhausted {
+                constraint: "unique name".into(),
+                attempts: 100,
+            })
+        );
+        assert!(names.contains("KA") && names.len() == 2);
+    }
+
+    #[test]
+    fn test_bloom() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut names = UniqueNames::new(TotroConfig::default()).with_bloom(1000, 0.01);
+        let mut seen = HashSet::new();
+        for _ in 0..500 {
+            let name = names.generate(&mut rng).unwrap();
+            assert!(seen.insert(name));
+        }
+        assert_eq!(names.len(), 500);
+    }
+}
@@ -0,0 +1,92 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Browser Bindings
+//!
+//! Built with the `wasm` feature for `wasm32-unknown-unknown`. The crate is not a `cdylib` by
+//! default, since that would need an allocator in `no_std` builds, so ask for one when building
+//! the module and then generate the JavaScript glue:
+//!
+//! ```text
+//! cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/nominae.wasm
+//! ```
+//!
+//! and used from JavaScript as:
+//!
+//! ```text
+//! import init, { generate_totro, seed_from_str } from "./pkg/nominae.js";
+//! await init();
+//! generate_totro(2, 4, 42n); // the same name on every call and browser
+//! generate_totro(2, 4);      // a fresh name from the browser's crypto.getRandomValues
+//!
+//! const world = new URLSearchParams(location.search).get("world") ?? "";
+//! generate_totro(2, 4, seed_from_str(world)); // the same name for everyone with the link
+//! ```
+
+use crate::stable::{self, StableRng};
+use crate::totro::Totro;
+use rand::rngs::OsRng;
+use rand::SeedableRng;
+use wasm_bindgen::prelude::*;
+
+/// Generate a name of `min` to `max` syllables from the built-in tables.
+///
+/// With a `seed` the name comes from [`StableRng`], so it matches what Rust code seeded the same
+/// way produces. Without one it is seeded from the operating system, which in the browser is
+/// `crypto.getRandomValues`. An invalid range is thrown as a JavaScript `Error`.
+#[wasm_bindgen]
+pub fn generate_totro(min: u8, max: u8, seed: Option<u64>) -> Result<String, JsError> {
+    let mut rng = match seed {
+        Some(seed) => StableRng::seed_from_u64(seed),
+        None => StableRng::from_rng(OsRng)?,
+    };
+    Ok(Totro::try_generate(min, max, &mut rng)?)
+}
+
+/// Seed for [`generate_totro`] from a string, such as a URL parameter, computed as
+/// [`seed_from_str`](crate::seed_from_str) does in Rust.
+#[wasm_bindgen]
+pub fn seed_from_str(text: &str) -> u64 {
+    stable::seed_from_str(text)
+}
+
+/// Seed for [`generate_totro`] from a `Uint8Array`, computed as
+/// [`seed_from_bytes`](crate::seed_from_bytes) does in Rust.
+#[wasm_bindgen]
+pub fn seed_from_bytes(bytes: &[u8]) -> u64 {
+    stable::seed_from_bytes(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate_totro, seed_from_str};
+    use crate::stable::StableRng;
+    use crate::totro::Totro;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_generate_totro() {
+        let expected = Totro::generate(2, 4, &mut StableRng::seed_from_u64(42));
+        assert_eq!(generate_totro(2, 4, Some(42)).ok(), Some(expected));
+        assert!(generate_totro(2, 4, None).is_ok());
+        let seed = seed_from_str("velmar");
+        assert_eq!(
+            generate_totro(2, 4, Some(seed)).ok(),
+            generate_totro(2, 4, Some(seed)).ok()
+        );
+    }
+}
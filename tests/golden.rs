@@ -0,0 +1,142 @@
+//
+// Copyright 2020 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Golden Output Regression Tests
+//!
+//! Output seeded through [`StableRng`] must not change within a minor version, on any platform.
+//! Frozen profile versions such as `v1.txt` must never change; the other files track the latest
+//! profile and may be regenerated with `NOMINAE_BLESS=1 cargo test --test golden` after an
+//! intended change.
+
+use nominae::{
+    Adjacency, Constraint, LengthModel, Mode, PersonaGenerator, ProfileVersion, Rhythm, StableRng,
+    Syllabic, TokenClass, Totro, TotroConfig,
+};
+use rand::SeedableRng;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Names generated for each golden file.
+const COUNT: usize = 64;
+
+/// Compare `actual` against `tests/golden/<name>`, or rewrite it when blessing.
+fn check(name: &str, expected: &str, actual: String) {
+    if env::var_os("NOMINAE_BLESS").is_some() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/golden")
+            .join(name);
+        fs::write(path, actual).unwrap();
+        return;
+    }
+    for (line, (expected, actual)) in expected.lines().zip(actual.lines()).enumerate() {
+        assert_eq!(expected, actual, "{} differs at line {}", name, line + 1);
+    }
+    assert_eq!(
+        expected.lines().count(),
+        actual.lines().count(),
+        "{} has a different number of lines",
+        name
+    );
+}
+
+fn names(config: &TotroConfig, seed: u64) -> String {
+    let mut rng = StableRng::seed_from_u64(seed);
+    (0..COUNT)
+        .map(|_| config.generate(&mut rng) + "\n")
+        .collect()
+}
+
+#[test]
+fn test_golden_totro() {
+    check(
+        "v1.txt",
+        include_str!("golden/v1.txt"),
+        names(&TotroConfig::versioned(ProfileVersion::V1), 0),
+    );
+    check(
+        "alphabetic.txt",
+        include_str!("golden/alphabetic.txt"),
+        names(
+            &TotroConfig::alphabetic()
+                .with_length(2, 4)
+                .with_distinct_adjacent(true),
+            1,
+        ),
+    );
+    check(
+        "characters.txt",
+        include_str!("golden/characters.txt"),
+        names(
+            &TotroConfig::default()
+                .with_length(4, 9)
+                .with_length_model(LengthModel::Characters),
+            2,
+        ),
+    );
+    check(
+        "syllabic.txt",
+        include_str!("golden/syllabic.txt"),
+        names(
+            &TotroConfig::alphabetic().with_mode(Mode::Syllabic(Syllabic::default())),
+            3,
+        ),
+    );
+    let mut rng = StableRng::seed_from_u64(4);
+    check(
+        "classic.txt",
+        include_str!("golden/classic.txt"),
+        (0..COUNT)
+            .map(|_| Totro::generate(2, 5, &mut rng) + "\n")
+            .collect(),
+    );
+}
+
+#[test]
+fn test_golden_personas() {
+    let mut csv = Vec::new();
+    PersonaGenerator::default()
+        .with_seed(5)
+        .write_csv(COUNT, &mut csv)
+        .unwrap();
+    check(
+        "personas.csv",
+        include_str!("golden/personas.csv"),
+        String::from_utf8(csv).unwrap(),
+    );
+}
+
+#[test]
+fn test_golden_fingerprints() {
+    assert_eq!(
+        TotroConfig::versioned(ProfileVersion::V1).fingerprint(),
+        0xeabb_6179_aa18_e75f
+    );
+    let ruled = TotroConfig::alphabetic()
+        .with_constraint(Constraint::MaxVowelRun(2))
+        .with_adjacency(Adjacency::Forbid(
+            TokenClass::VowelBlend,
+            TokenClass::Text("y".into()),
+        ))
+        .with_max_chars(12);
+    assert_eq!(ruled.fingerprint(), 0x9111_5383_af99_e642);
+    let syllabic = TotroConfig::alphabetic().with_mode(Mode::Syllabic(Syllabic::default()));
+    assert_eq!(syllabic.fingerprint(), 0x48f1_0d94_135d_ac4c);
+    let timed = syllabic.with_mode(Mode::Syllabic(
+        Syllabic::default().with_rhythm(Rhythm::StressTimed),
+    ));
+    assert_ne!(timed.fingerprint(), 0x48f1_0d94_135d_ac4c);
+}